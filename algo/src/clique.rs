@@ -0,0 +1,97 @@
+use crate::graph::Graph;
+use std::collections::HashSet;
+
+// Every clique of `nodes` that no other node of them joins, by Bron–Kerbosch
+// with a pivot. Edges are taken as undirected and those leaving `nodes` ignored
+pub fn maximal_cliques<G: Graph>(
+    graph: &G,
+    nodes: impl IntoIterator<Item = G::Node>,
+) -> Vec<Vec<G::Node>> {
+    let nodes: Vec<G::Node> = nodes.into_iter().collect();
+
+    // Positions in `nodes` of the neighbours of each node
+    let mut position: Vec<Option<usize>> = vec![None; graph.node_count()];
+    for (k, &node) in nodes.iter().enumerate() {
+        position[graph.index(node)] = Some(k);
+    }
+    let mut neighbours: Vec<HashSet<usize>> = vec![HashSet::new(); nodes.len()];
+    for (k, &node) in nodes.iter().enumerate() {
+        for (next, _) in graph.edges(node) {
+            if let Some(n) = position[graph.index(next)].filter(|&n| n != k) {
+                neighbours[k].insert(n);
+                neighbours[n].insert(k);
+            }
+        }
+    }
+
+    let mut cliques: Vec<Vec<usize>> = Vec::new();
+    bron_kerbosch(
+        &neighbours,
+        &mut Vec::new(),
+        (0..nodes.len()).collect(),
+        HashSet::new(),
+        &mut cliques,
+    );
+
+    cliques
+        .into_iter()
+        .map(|clique| clique.into_iter().map(|k| nodes[k]).collect())
+        .collect()
+}
+
+// `clique` grows with the candidates of `p`, `x` holds the ones already tried
+fn bron_kerbosch(
+    neighbours: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    mut p: HashSet<usize>,
+    mut x: HashSet<usize>,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    // The pivot with the most candidates as neighbours leaves the fewest to try
+    let Some(pivot) = p
+        .iter()
+        .chain(&x)
+        .max_by_key(|&&u| p.intersection(&neighbours[u]).count())
+        .copied()
+    else {
+        cliques.push(clique.clone());
+        return;
+    };
+
+    let candidates: Vec<usize> = p.difference(&neighbours[pivot]).copied().collect();
+    for v in candidates {
+        clique.push(v);
+        bron_kerbosch(
+            neighbours,
+            clique,
+            p.intersection(&neighbours[v]).copied().collect(),
+            x.intersection(&neighbours[v]).copied().collect(),
+            cliques,
+        );
+        clique.pop();
+
+        p.remove(&v);
+        x.insert(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Adjacency;
+
+    #[test]
+    fn cliques_of_a_small_graph() {
+        // A triangle 0-1-2 with a tail 2-3, and 4 on its own
+        let graph = Adjacency(vec![vec![1, 2], vec![2], vec![3], vec![], vec![]]);
+        let mut cliques = maximal_cliques(&graph, 0..5);
+        cliques.iter_mut().for_each(|clique| clique.sort());
+        cliques.sort();
+        assert_eq!(cliques, [vec![0, 1, 2], vec![2, 3], vec![4]]);
+
+        let mut cliques = maximal_cliques(&graph, [0, 1, 3]);
+        cliques.iter_mut().for_each(|clique| clique.sort());
+        cliques.sort();
+        assert_eq!(cliques, [vec![0, 1], vec![3]]);
+    }
+}
@@ -0,0 +1,321 @@
+use grid::{Grid, Point};
+use priority_queue::PriorityQueue;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+// Nodes numbered 0..node_count() so the searches keep their state in Vecs,
+// and the edges leaving each node with what taking them costs
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    fn node_count(&self) -> usize;
+    fn index(&self, node: Self::Node) -> usize;
+    fn edges(&self, node: Self::Node) -> impl Iterator<Item = (Self::Node, u64)>;
+}
+
+// The cells of a grid, with a step of cost 1 to each 4-connected neighbour
+// `passable(from, to)` allows
+pub struct GridGraph<'a, T, F> {
+    grid: &'a Grid<T>,
+    passable: F,
+}
+
+impl<'a, T, F: Fn(Point, Point) -> bool> GridGraph<'a, T, F> {
+    pub fn new(grid: &'a Grid<T>, passable: F) -> GridGraph<'a, T, F> {
+        GridGraph { grid, passable }
+    }
+}
+
+impl<T, F: Fn(Point, Point) -> bool> Graph for GridGraph<'_, T, F> {
+    type Node = Point;
+
+    fn node_count(&self) -> usize {
+        self.grid.height() * self.grid.width()
+    }
+
+    fn index(&self, p: Point) -> usize {
+        p.i * self.grid.width() + p.j
+    }
+
+    fn edges(&self, p: Point) -> impl Iterator<Item = (Point, u64)> {
+        self.grid
+            .neighbours(p)
+            .filter(move |&(_, q)| (self.passable)(p, q))
+            .map(|(_, q)| (q, 1))
+    }
+}
+
+// Nodes 0..n with the neighbours of each, every edge costing 1
+pub struct Adjacency(pub Vec<Vec<usize>>);
+
+impl Graph for Adjacency {
+    type Node = usize;
+
+    fn node_count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn index(&self, node: usize) -> usize {
+        node
+    }
+
+    fn edges(&self, node: usize) -> impl Iterator<Item = (usize, u64)> {
+        self.0[node].iter().map(|&next| (next, 1))
+    }
+}
+
+// Fewest edges from the start of a breadth-first search to every node it
+// reached, and the node each was first reached from
+pub struct BfsTree<'a, G: Graph> {
+    graph: &'a G,
+    steps: Vec<Option<usize>>,
+    came_from: Vec<Option<G::Node>>,
+}
+
+impl<G: Graph> BfsTree<'_, G> {
+    pub fn steps(&self, node: G::Node) -> Option<usize> {
+        self.steps[self.graph.index(node)]
+    }
+
+    // Nodes from the start to `to`, both included
+    pub fn path(&self, to: G::Node) -> Option<Vec<G::Node>> {
+        self.steps(to)?;
+
+        let mut path = vec![to];
+        while let Some(prev) = self.came_from[self.graph.index(*path.last().unwrap())] {
+            path.push(prev);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// Edge costs are ignored, every edge is one step
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> BfsTree<'_, G> {
+    bfs_until(graph, start, |_| false)
+}
+
+// Stops once a node where `is_goal` holds is reached, leaving the nodes further
+// away unreached
+pub fn bfs_until<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(G::Node) -> bool,
+) -> BfsTree<'_, G> {
+    let mut tree = BfsTree {
+        graph,
+        steps: vec![None; graph.node_count()],
+        came_from: vec![None; graph.node_count()],
+    };
+
+    tree.steps[graph.index(start)] = Some(0);
+    let mut queue: VecDeque<(G::Node, usize)> = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_goal(node) {
+            break;
+        }
+        for (next, _) in graph.edges(node) {
+            let i = graph.index(next);
+            if tree.steps[i].is_none() {
+                tree.steps[i] = Some(steps + 1);
+                tree.came_from[i] = Some(node);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    tree
+}
+
+// Nodes reachable from `start` that are not `seen` yet, marking them seen.
+// Filling from every unseen node in turn splits a graph into its components
+pub fn flood_fill<G: Graph>(graph: &G, start: G::Node, seen: &mut [bool]) -> Vec<G::Node> {
+    let mut filled: Vec<G::Node> = Vec::new();
+    if seen[graph.index(start)] {
+        return filled;
+    }
+
+    seen[graph.index(start)] = true;
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        filled.push(node);
+        for (next, _) in graph.edges(node) {
+            if !seen[graph.index(next)] {
+                seen[graph.index(next)] = true;
+                stack.push(next);
+            }
+        }
+    }
+
+    filled
+}
+
+// Lowest costs found by Dijkstra's search, with every predecessor a node can
+// be reached from at that cost, so all the best paths can be walked back
+pub struct ShortestPaths<'a, G: Graph> {
+    graph: &'a G,
+    cost: Vec<Option<u64>>,
+    preds: Vec<Vec<G::Node>>,
+    targets: Vec<G::Node>,
+}
+
+impl<G: Graph> ShortestPaths<'_, G> {
+    // Lowest cost to `node` known so far
+    pub fn cost(&self, node: G::Node) -> Option<u64> {
+        self.cost[self.graph.index(node)]
+    }
+
+    // Cost of the nearest targets, None when none was reached
+    pub fn best(&self) -> Option<u64> {
+        self.targets.first().and_then(|&target| self.cost(target))
+    }
+
+    // Every target reached at the best cost
+    pub fn targets(&self) -> &[G::Node] {
+        &self.targets
+    }
+
+    pub fn predecessors(&self, node: G::Node) -> &[G::Node] {
+        &self.preds[self.graph.index(node)]
+    }
+
+    // Nodes on any best path to the nearest targets, the starts and targets included
+    pub fn on_best_paths(&self) -> Vec<G::Node> {
+        let mut seen = vec![false; self.graph.node_count()];
+        let mut nodes: Vec<G::Node> = Vec::new();
+
+        let mut stack = self.targets.clone();
+        for &target in &stack {
+            seen[self.graph.index(target)] = true;
+        }
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            for &prev in self.predecessors(node) {
+                if !seen[self.graph.index(prev)] {
+                    seen[self.graph.index(prev)] = true;
+                    stack.push(prev);
+                }
+            }
+        }
+
+        nodes
+    }
+}
+
+// Searches from all `starts` until the nearest nodes where `is_target` holds,
+// every one of them at that cost included
+pub fn shortest_paths<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_target: impl Fn(G::Node) -> bool,
+) -> ShortestPaths<'_, G> {
+    shortest_paths_with(graph, starts, is_target, |_, _| true).unwrap()
+}
+
+// Calls `observe` with the search so far and each node taken off the queue,
+// the search gives up when it returns false
+pub fn shortest_paths_with<'a, G: Graph>(
+    graph: &'a G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_target: impl Fn(G::Node) -> bool,
+    mut observe: impl FnMut(&ShortestPaths<'a, G>, G::Node) -> bool,
+) -> Option<ShortestPaths<'a, G>> {
+    let mut paths = ShortestPaths {
+        graph,
+        cost: vec![None; graph.node_count()],
+        preds: vec![Vec::new(); graph.node_count()],
+        targets: Vec::new(),
+    };
+
+    let mut queue: PriorityQueue<G::Node, Reverse<u64>> = PriorityQueue::new();
+    for start in starts {
+        paths.cost[graph.index(start)] = Some(0);
+        queue.push(start, Reverse(0));
+    }
+
+    while let Some((node, Reverse(cost))) = queue.pop() {
+        if paths.best().is_some_and(|best| cost > best) {
+            break;
+        }
+        if !observe(&paths, node) {
+            return None;
+        }
+        if is_target(node) {
+            paths.targets.push(node);
+            continue;
+        }
+
+        for (next, step) in graph.edges(node) {
+            let i = graph.index(next);
+            let new_cost = cost + step;
+            match paths.cost[i] {
+                Some(old) if old < new_cost => continue,
+                Some(old) if old == new_cost => paths.preds[i].push(node),
+                _ => {
+                    paths.cost[i] = Some(new_cost);
+                    paths.preds[i] = vec![node];
+                    queue.push_increase(next, Reverse(new_cost));
+                }
+            }
+        }
+    }
+
+    Some(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<u8> {
+        Grid::parse("S..#\n.#..\n...E\n", |c| b"#.SE".contains(&c), "a maze").unwrap()
+    }
+
+    #[test]
+    fn bfs_finds_a_shortest_path() {
+        let map = maze();
+        let graph = GridGraph::new(&map, |_, to| map[to] != b'#');
+        let tree = bfs(&graph, Point::new(0, 0));
+
+        assert_eq!(tree.steps(Point::new(2, 3)), Some(5));
+        assert_eq!(tree.steps(Point::new(0, 3)), None);
+        let path = tree.path(Point::new(2, 3)).unwrap();
+        assert_eq!(path.len(), 6);
+        assert!(path.windows(2).all(|pair| pair[0].manhattan(pair[1]) == 1));
+    }
+
+    #[test]
+    fn flood_fill_splits_components() {
+        let graph = Adjacency(vec![vec![1], vec![0], vec![3], vec![2], vec![]]);
+        let mut seen = vec![false; 5];
+        let mut components: Vec<Vec<usize>> = (0..5)
+            .map(|node| flood_fill(&graph, node, &mut seen))
+            .filter(|component| !component.is_empty())
+            .collect();
+        components.iter_mut().for_each(|component| component.sort());
+        assert_eq!(components, [vec![0, 1], vec![2, 3], vec![4]]);
+    }
+
+    // Both ways around the wall in the middle of the maze are best paths
+    #[test]
+    fn best_paths_cover_every_tie() {
+        let map = maze();
+        let graph = GridGraph::new(&map, |_, to| map[to] != b'#');
+        let paths = shortest_paths(&graph, [Point::new(0, 0)], |p| map[p] == b'E');
+
+        assert_eq!(paths.best(), Some(5));
+        assert_eq!(paths.targets(), [Point::new(2, 3)]);
+        // Every open tile is on one of them
+        assert_eq!(paths.on_best_paths().len(), 10);
+    }
+
+    #[test]
+    fn unreachable_targets_have_no_cost() {
+        let graph = Adjacency(vec![vec![1], vec![0], vec![]]);
+        let paths = shortest_paths(&graph, [0], |node| node == 2);
+        assert_eq!(paths.best(), None);
+        assert!(paths.on_best_paths().is_empty());
+    }
+}
@@ -0,0 +1,13 @@
+// Searches and solvers shared by the days, generic over what they work on
+mod clique;
+mod graph;
+mod linear;
+mod memo;
+
+pub use clique::maximal_cliques;
+pub use graph::{
+    bfs, bfs_until, flood_fill, shortest_paths, shortest_paths_with, Adjacency, BfsTree, Graph,
+    GridGraph, ShortestPaths,
+};
+pub use linear::{solve_2x2, Overflow};
+pub use memo::Memo;
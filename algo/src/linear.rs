@@ -0,0 +1,62 @@
+use num_traits::{CheckedEuclid, CheckedMul, CheckedSub, Zero};
+
+// Some product or difference does not fit in the number type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+// a * d - b * c
+fn determinant<T: CheckedMul + CheckedSub>(a: &T, b: &T, c: &T, d: &T) -> Result<T, Overflow> {
+    let ad = a.checked_mul(d).ok_or(Overflow)?;
+    let bc = b.checked_mul(c).ok_or(Overflow)?;
+    ad.checked_sub(&bc).ok_or(Overflow)
+}
+
+// The quotient when `a` is a multiple of `b`
+fn exact_div<T: CheckedEuclid + Zero>(a: &T, b: &T) -> Option<T> {
+    a.checked_div_rem_euclid(b)
+        .and_then(|(quotient, rest)| rest.is_zero().then_some(quotient))
+}
+
+// The integers x and y with m[0][0] * x + m[0][1] * y = rhs[0] and
+// m[1][0] * x + m[1][1] * y = rhs[1], by Cramer's rule. None when the solution
+// is not made of integers or, for a singular m, is not the only one
+pub fn solve_2x2<T>(m: &[[T; 2]; 2], rhs: &[T; 2]) -> Result<Option<[T; 2]>, Overflow>
+where
+    T: CheckedMul + CheckedSub + CheckedEuclid + Zero,
+{
+    let det = determinant(&m[0][0], &m[0][1], &m[1][0], &m[1][1])?;
+    let det_x = determinant(&rhs[0], &m[0][1], &rhs[1], &m[1][1])?;
+    let det_y = determinant(&m[0][0], &rhs[0], &m[1][0], &rhs[1])?;
+
+    Ok(match (exact_div(&det_x, &det), exact_div(&det_y, &det)) {
+        (Some(x), Some(y)) => Some([x, y]),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn integer_solutions_only() {
+        // 94x + 22y = 8400, 34x + 67y = 5400
+        assert_eq!(
+            solve_2x2(&[[94, 22], [34, 67]], &[8400, 5400]),
+            Ok(Some([80, 40]))
+        );
+        assert_eq!(solve_2x2(&[[94, 22], [34, 67]], &[8401, 5400]), Ok(None));
+        assert_eq!(solve_2x2(&[[1, 2], [2, 4]], &[3, 6]), Ok(None));
+        assert_eq!(solve_2x2(&[[2, 1], [1, 1]], &[-1, 0]), Ok(Some([-1, 1])));
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let m = [[i64::MAX, 1], [1, i64::MAX]];
+        assert_eq!(solve_2x2(&m, &[1, 1]), Err(Overflow));
+
+        let m = m.map(|row| row.map(BigInt::from));
+        assert_eq!(solve_2x2(&m, &[BigInt::from(1), BigInt::from(1)]), Ok(None));
+    }
+}
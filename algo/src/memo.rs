@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Results of a recursive function by argument. The function gets the memo
+// back to look up and store the results of its own calls
+pub struct Memo<K, V> {
+    results: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo {
+            results: HashMap::new(),
+        }
+    }
+
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        match self.try_get_or_insert_with(key, |memo| Ok::<V, ()>(f(memo))) {
+            Ok(value) => value,
+            Err(()) => unreachable!(),
+        }
+    }
+
+    // Errors are not stored, the next call with the key tries again
+    pub fn try_get_or_insert_with<E>(
+        &mut self,
+        key: K,
+        f: impl FnOnce(&mut Self) -> Result<V, E>,
+    ) -> Result<V, E> {
+        if let Some(value) = self.results.get(&key) {
+            return Ok(value.clone());
+        }
+
+        let value = f(self)?;
+        self.results.insert(key, value.clone());
+        Ok(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+    }
+
+    #[test]
+    fn each_argument_is_computed_once() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 89);
+
+        let failed: Result<u64, &str> = memo.try_get_or_insert_with(100, |_| Err("too big"));
+        assert_eq!(failed, Err("too big"));
+        assert_eq!(memo.len(), 89);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::params::Params;
+use crate::solutions::{day14_boundary, day16_costs};
+use day15::MapSimulation;
+use parser::ParseError;
+use viz::Sink;
+
+pub const DAYS: [u32; 4] = [6, 14, 15, 16];
+
+// `part` picks the double-wide warehouse of day 15, the other days only have one animation
+pub fn animate(
+    day: u32,
+    input: &str,
+    params: &Params,
+    part: u8,
+    sink: &mut dyn Sink,
+) -> Result<(), ParseError> {
+    match day {
+        6 => day6::animate_walk(&day6::parse(input)?, sink),
+        14 => {
+            let robots = day14::parse(input)?;
+            let boundary = day14_boundary(params);
+            let (width, height) = boundary;
+            let last = day14::find_easter_egg(&robots, boundary).unwrap_or(width * height - 1);
+            day14::animate(&robots, boundary, 0..last + 1, sink);
+        }
+        15 => {
+            let (map, moves) = day15::parse(input)?;
+            let mut simulation: Box<dyn MapSimulation> = if part == 2 {
+                Box::new(day15::DoubleWideMap::from_normal_map(&map))
+            } else {
+                Box::new(map)
+            };
+            day15::animate(simulation.as_mut(), &moves, sink);
+        }
+        16 => {
+            let map = day16::parse(input)?;
+            day16::animate_search(&map, day16::start_position(&map), day16_costs(params), sink);
+        }
+        _ => unreachable!("day {} has no animation", day),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// Regression check of the real inputs against answers.toml at the workspace root
+use crate::{read_source, run_day, runner, LAST_DAY};
+use parser::Source;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn real_inputs_match_answers() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("answers.toml");
+    let Ok(text) = fs::read_to_string(&path) else {
+        eprintln!("{} not found, skipping", path.display());
+        return;
+    };
+    let answers: toml::Table = text.parse().expect("answers.toml is not valid TOML");
+
+    let mut mismatches: Vec<String> = Vec::new();
+
+    for day in 1..=LAST_DAY {
+        let Some(expected) = answers.get(&format!("day{}", day)) else {
+            continue;
+        };
+
+        let parts: Vec<u8> = [1, 2]
+            .into_iter()
+            .filter(|part| expected.get(format!("part{}", part)).is_some())
+            .collect();
+
+        let input = match read_source(day, &Source::Default) {
+            Ok((_, input)) => input,
+            Err(_) => {
+                mismatches.push(format!("day {}: input missing, run `aoc fetch`", day));
+                continue;
+            }
+        };
+
+        let params = runner(day).params(false, &[]).unwrap();
+        let results = match run_day(day, &input, &params, &parts, None) {
+            Ok(results) => results,
+            Err(e) => {
+                mismatches.push(format!("day {}: {}", day, e));
+                continue;
+            }
+        };
+
+        for result in results {
+            let expected = expected[&format!("part{}", result.part)]
+                .as_str()
+                .expect("answers must be strings");
+
+            if result.answer != expected {
+                mismatches.push(format!(
+                    "day {} part {}: expected {}, got {}",
+                    day, result.part, expected, result.answer
+                ));
+            }
+        }
+    }
+
+    assert!(mismatches.is_empty(), "\n{}", mismatches.join("\n"));
+}
@@ -0,0 +1,187 @@
+use crate::alloc;
+use crate::params::Params;
+use crate::solutions::Solution;
+use parser::ParseError;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Stage> {
+        match name {
+            "parse" => Some(Stage::Parse),
+            "part1" => Some(Stage::Part1),
+            "part2" => Some(Stage::Part2),
+            _ => None,
+        }
+    }
+}
+
+pub struct StageStats {
+    pub day: u32,
+    pub stage: Stage,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    pub peak_bytes: usize,
+}
+
+// Collects the timings of one stage over all iterations
+#[derive(Default)]
+struct Samples {
+    times: Vec<Duration>,
+    peak_bytes: usize,
+}
+
+impl Samples {
+    fn measure<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let start_bytes = alloc::reset_peak();
+        let now = Instant::now();
+        let result = black_box(f());
+        self.times.push(now.elapsed());
+        self.peak_bytes = self.peak_bytes.max(alloc::peak_since(start_bytes));
+        result
+    }
+
+    fn stats(mut self, day: u32, stage: Stage) -> StageStats {
+        self.times.sort();
+
+        StageStats {
+            day,
+            stage,
+            min: self.times[0],
+            median: self.times[self.times.len() / 2],
+            max: self.times[self.times.len() - 1],
+            peak_bytes: self.peak_bytes,
+        }
+    }
+}
+
+// Times parse and the requested parts separately, `iterations` must be at least 1
+pub fn bench<S: Solution>(
+    day: u32,
+    input: &str,
+    params: &Params,
+    parts: &[u8],
+    iterations: usize,
+) -> Result<Vec<StageStats>, ParseError> {
+    let mut parse = Samples::default();
+    let mut part1 = Samples::default();
+    let mut part2 = Samples::default();
+
+    for _ in 0..iterations {
+        let parsed = parse.measure(|| S::parse(black_box(input), params))?;
+
+        if parts.contains(&1) {
+            part1.measure(|| S::part1(&parsed));
+        }
+        if parts.contains(&2) {
+            part2.measure(|| S::part2(&parsed));
+        }
+    }
+
+    let mut stats = vec![parse.stats(day, Stage::Parse)];
+    if parts.contains(&1) {
+        stats.push(part1.stats(day, Stage::Part1));
+    }
+    if parts.contains(&2) {
+        stats.push(part2.stats(day, Stage::Part2));
+    }
+
+    Ok(stats)
+}
+
+// Median times of a previous run, keyed by (day, stage)
+pub type Baseline = HashMap<(u32, Stage), Duration>;
+
+const BASELINE_HEADER: &str = "# day\tstage\tmin_ns\tmedian_ns\tmax_ns\tpeak_bytes";
+
+// One tab-separated line per stage, see BASELINE_HEADER
+pub fn write_baseline(stats: &[StageStats]) -> String {
+    let mut out = format!("{}\n", BASELINE_HEADER);
+
+    for s in stats {
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            s.day,
+            s.stage.name(),
+            s.min.as_nanos(),
+            s.median.as_nanos(),
+            s.max.as_nanos(),
+            s.peak_bytes
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+pub fn read_baseline(text: &str) -> Result<Baseline, String> {
+    let mut baseline = Baseline::new();
+
+    for (i, line) in text.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split('\t').collect();
+        let entry = match fields[..] {
+            [day, stage, _, median, ..] => day
+                .parse::<u32>()
+                .ok()
+                .zip(Stage::from_name(stage))
+                .zip(median.parse::<u64>().ok()),
+            _ => None,
+        };
+
+        let ((day, stage), median) =
+            entry.ok_or_else(|| format!("Invalid baseline entry on line {}: {}", i + 1, line))?;
+        baseline.insert((day, stage), Duration::from_nanos(median));
+    }
+
+    Ok(baseline)
+}
+
+// Relative change of the median against the baseline, in percent
+pub fn change_percent(stats: &StageStats, baseline: &Baseline) -> Option<f64> {
+    let before = baseline.get(&(stats.day, stats.stage))?.as_secs_f64();
+    if before == 0.0 {
+        return None;
+    }
+
+    Some((stats.median.as_secs_f64() - before) / before * 100.0)
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -0,0 +1,1063 @@
+use grid::{Dir, Grid, Point};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+// What size and density mean is up to each day, see the table in the README
+pub struct Params {
+    pub seed: u64,
+    pub size: Option<usize>,
+    pub density: Option<f64>,
+}
+
+impl Params {
+    fn size(&self, default: usize) -> usize {
+        self.size.unwrap_or(default)
+    }
+
+    fn density(&self, default: f64) -> f64 {
+        self.density.unwrap_or(default)
+    }
+}
+
+// A puzzle input along with the answers that are known by construction
+#[derive(Debug, Default)]
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Generated {
+    fn input(input: String) -> Generated {
+        Generated {
+            input,
+            ..Default::default()
+        }
+    }
+}
+
+// SplitMix64, so a seed gives the same input on every platform and toolchain
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in lo..=hi, the modulo bias is negligible for our ranges
+    fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.next() % (hi - lo + 1)
+    }
+
+    fn index(&mut self, len: usize) -> usize {
+        (self.next() % len as u64) as usize
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.index(items.len())]
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+pub fn generate(day: u32, params: &Params) -> Generated {
+    let mut rng = Rng(params.seed);
+
+    match day {
+        1 => location_lists(&mut rng, params),
+        2 => reports(&mut rng, params),
+        3 => corrupted_memory(&mut rng, params),
+        4 => word_search(&mut rng, params),
+        5 => print_queue(&mut rng, params),
+        6 => guard_map(&mut rng, params),
+        7 => calibrations(&mut rng, params),
+        8 => antenna_map(&mut rng, params),
+        9 => disk_map(&mut rng, params),
+        10 => topographic_map(&mut rng, params),
+        11 => stones(&mut rng, params),
+        12 => garden(&mut rng, params),
+        13 => claw_machines(&mut rng, params),
+        14 => robots(&mut rng, params),
+        15 => warehouse(&mut rng, params),
+        16 => reindeer_maze(&mut rng, params),
+        17 => device(&mut rng),
+        18 => falling_bytes(&mut rng, params),
+        19 => towels(&mut rng, params),
+        20 => racetrack(&mut rng, params),
+        21 => door_codes(&mut rng, params),
+        22 => secrets(&mut rng, params),
+        23 => lan_party(&mut rng, params),
+        24 => crossed_wires(&mut rng, params),
+        25 => schematics(&mut rng, params),
+        _ => unreachable!("day {} has no generator", day),
+    }
+}
+
+fn map_input(map: &Grid<u8>) -> String {
+    format!("{}\n", map)
+}
+
+// Density: share of right-hand IDs that also appear in the left list
+fn location_lists(rng: &mut Rng, params: &Params) -> Generated {
+    let lines = params.size(1000);
+    let shared = params.density(0.3);
+
+    let left: Vec<u64> = (0..lines).map(|_| rng.range(10000, 99999)).collect();
+    let mut input = String::new();
+    for &id in &left {
+        let right = if rng.chance(shared) {
+            rng.pick(&left)
+        } else {
+            rng.range(10000, 99999)
+        };
+        writeln!(input, "{}   {}", id, right).unwrap();
+    }
+
+    Generated::input(input)
+}
+
+fn safe_levels(rng: &mut Rng) -> Vec<u64> {
+    let increasing = rng.chance(0.5);
+    let mut level = if increasing {
+        rng.range(10, 40)
+    } else {
+        rng.range(40, 70)
+    };
+
+    let mut levels = vec![level];
+    for _ in 1..rng.range(5, 8) {
+        let step = rng.range(1, 3);
+        level = if increasing {
+            level + step
+        } else {
+            level - step
+        };
+        levels.push(level);
+    }
+
+    levels
+}
+
+// Density: share of safe reports, the rest is split between reports that the
+// dampener fixes (one repeated level) and ones it can't (two repeated levels)
+fn reports(rng: &mut Rng, params: &Params) -> Generated {
+    let count = params.size(1000);
+    let safe_share = params.density(0.5);
+
+    let mut input = String::new();
+    let (mut safe, mut dampened) = (0, 0);
+    for _ in 0..count {
+        let mut levels = safe_levels(rng);
+        let repeats = if rng.chance(safe_share) {
+            safe += 1;
+            0
+        } else if rng.chance(0.5) {
+            dampened += 1;
+            1
+        } else {
+            2
+        };
+
+        for _ in 0..repeats {
+            let i = rng.index(levels.len());
+            levels.insert(i, levels[i]);
+        }
+
+        let levels: Vec<String> = levels.iter().map(|l| l.to_string()).collect();
+        writeln!(input, "{}", levels.join(" ")).unwrap();
+    }
+
+    Generated {
+        input,
+        part1: Some(safe.to_string()),
+        part2: Some((safe + dampened).to_string()),
+    }
+}
+
+// Symbols only, so the filler can never complete an instruction
+const JUNK: &[u8] = b"!@#$%^&*()[]{}<>?;:,' +-/";
+
+// Density: share of corrupted mul instructions
+fn corrupted_memory(rng: &mut Rng, params: &Params) -> Generated {
+    let count = params.size(700);
+    let corrupted = params.density(0.5);
+
+    let mut memory = String::new();
+    let mut enabled = true;
+    let (mut all, mut active) = (0, 0);
+    for _ in 0..count {
+        match rng.index(8) {
+            0 => {
+                memory.push_str("do()");
+                enabled = true;
+            }
+            1 => {
+                memory.push_str("don't()");
+                enabled = false;
+            }
+            _ => {}
+        }
+
+        for _ in 0..rng.range(0, 6) {
+            memory.push(rng.pick(JUNK) as char);
+        }
+
+        let (a, b) = (rng.range(1, 999), rng.range(1, 999));
+        if rng.chance(corrupted) {
+            let instruction = match rng.index(4) {
+                0 => format!("mul[{},{}]", a, b),
+                1 => format!("mul({},{}!", a, b),
+                2 => format!("mul ( {},{} )", a, b),
+                _ => format!("mul({}{})", a, b),
+            };
+            memory.push_str(&instruction);
+        } else {
+            write!(memory, "mul({},{})", a, b).unwrap();
+            all += a * b;
+            if enabled {
+                active += a * b;
+            }
+        }
+
+        if rng.chance(0.01) {
+            memory.push('\n');
+        }
+    }
+    memory.push('\n');
+
+    Generated {
+        input: memory,
+        part1: Some(all.to_string()),
+        part2: Some(active.to_string()),
+    }
+}
+
+// Density: share of letters drawn from XMAS rather than the whole alphabet
+fn word_search(rng: &mut Rng, params: &Params) -> Generated {
+    let side = params.size(140);
+    let xmas = params.density(1.0);
+
+    let mut map = Grid::new(side, side, b'X');
+    for p in map.points().collect::<Vec<Point>>() {
+        map[p] = if rng.chance(xmas) {
+            rng.pick(b"XMAS")
+        } else {
+            b'A' + rng.index(26) as u8
+        };
+    }
+
+    Generated::input(map_input(&map))
+}
+
+// Density: share of updates that are already in the right order
+fn print_queue(rng: &mut Rng, params: &Params) -> Generated {
+    let updates = params.size(200);
+    let ordered = params.density(0.5);
+
+    // The order of this list is the order of the pages, every pair gets a rule
+    let mut pages: Vec<usize> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    let mut rules: Vec<(usize, usize)> = Vec::new();
+    for i in 0..pages.len() {
+        for j in i + 1..pages.len() {
+            rules.push((pages[i], pages[j]));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let mut input = String::new();
+    for (before, after) in rules {
+        writeln!(input, "{}|{}", before, after).unwrap();
+    }
+    input.push('\n');
+
+    let (mut correct_sum, mut fixed_sum) = (0, 0);
+    for _ in 0..updates {
+        let len = 2 * rng.range(2, 11) as usize + 1;
+        let mut picked: Vec<usize> = (0..pages.len()).collect();
+        rng.shuffle(&mut picked);
+        picked.truncate(len);
+        picked.sort();
+
+        let sorted: Vec<usize> = picked.iter().map(|&i| pages[i]).collect();
+        let middle = sorted[len / 2];
+
+        let mut update = sorted.clone();
+        if rng.chance(ordered) {
+            correct_sum += middle;
+        } else {
+            while update == sorted {
+                rng.shuffle(&mut update);
+            }
+            fixed_sum += middle;
+        }
+
+        let update: Vec<String> = update.iter().map(|p| p.to_string()).collect();
+        writeln!(input, "{}", update.join(",")).unwrap();
+    }
+
+    Generated {
+        input,
+        part1: Some(correct_sum.to_string()),
+        part2: Some(fixed_sum.to_string()),
+    }
+}
+
+// Density: share of obstructions, capped so the guard can still find a way out
+fn guard_map(rng: &mut Rng, params: &Params) -> Generated {
+    let side = params.size(130).max(2);
+    let obstructions = params.density(0.05).min(0.3);
+
+    // The puzzle needs a guard that leaves the map, try again until this one does
+    loop {
+        let mut map = Grid::new(side, side, b'.');
+        for p in map.points().collect::<Vec<Point>>() {
+            if rng.chance(obstructions) {
+                map[p] = b'#';
+            }
+        }
+
+        let guard = Point::new(rng.index(side), rng.index(side));
+        map[guard] = b'^';
+
+        if day6::walk(&mut map.clone()).is_some() {
+            return Generated::input(map_input(&map));
+        }
+    }
+}
+
+// Targets stay below this so every sum and product fits in a u64
+const MAX_CALIBRATION: u64 = 100_000_000_000_000;
+
+// Density: share of equations whose test value comes from some operators,
+// the others get a random test value that most likely can't be reached
+fn calibrations(rng: &mut Rng, params: &Params) -> Generated {
+    let count = params.size(850);
+    let reachable = params.density(0.7);
+
+    let mut input = String::new();
+    let mut written = 0;
+    while written < count {
+        let operands: Vec<u64> = (0..rng.range(3, 12)).map(|_| rng.range(1, 99)).collect();
+
+        // The solver may try concatenating every operand, that must fit in a u64 too
+        let digits: usize = operands.iter().map(|o| o.to_string().len()).sum();
+        if digits > 19 {
+            continue;
+        }
+
+        let mut value = Some(operands[0]);
+        for &operand in &operands[1..] {
+            value = value.and_then(|v| match rng.index(3) {
+                0 => v.checked_add(operand),
+                1 => v.checked_mul(operand),
+                _ => format!("{}{}", v, operand).parse::<u64>().ok(),
+            });
+        }
+
+        let Some(value) = value.filter(|&v| v < MAX_CALIBRATION) else {
+            continue;
+        };
+        let target = if rng.chance(reachable) {
+            value
+        } else {
+            rng.range(1, value)
+        };
+
+        let operands: Vec<String> = operands.iter().map(|o| o.to_string()).collect();
+        writeln!(input, "{}: {}", target, operands.join(" ")).unwrap();
+        written += 1;
+    }
+
+    Generated::input(input)
+}
+
+// Density: share of cells with an antenna
+fn antenna_map(rng: &mut Rng, params: &Params) -> Generated {
+    const FREQUENCIES: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+    let side = params.size(50);
+    let antennas = params.density(0.08);
+
+    let mut map = Grid::new(side, side, b'.');
+    for p in map.points().collect::<Vec<Point>>() {
+        if rng.chance(antennas) {
+            map[p] = rng.pick(FREQUENCIES);
+        }
+    }
+
+    Generated::input(map_input(&map))
+}
+
+// Size: number of digits, density: share of free spaces that are not empty
+fn disk_map(rng: &mut Rng, params: &Params) -> Generated {
+    let digits = params.size(19999);
+    let free = params.density(0.9);
+
+    let mut input = String::with_capacity(digits + 1);
+    for i in 0..digits {
+        // Even positions are files, which are never empty
+        let digit = if i % 2 == 0 || rng.chance(free) {
+            rng.range(1, 9)
+        } else {
+            0
+        };
+        input.push((b'0' + digit as u8) as char);
+    }
+    input.push('\n');
+
+    Generated::input(input)
+}
+
+// Density: share of cells that are a peak, the land slopes down around each
+// peak so that trails actually exist
+fn topographic_map(rng: &mut Rng, params: &Params) -> Generated {
+    let side = params.size(50);
+    let peaks = params.density(0.01);
+
+    let mut distance: Grid<Option<u8>> = Grid::new(side, side, None);
+    let mut frontier: Vec<Point> = distance.points().filter(|_| rng.chance(peaks)).collect();
+    for &p in &frontier {
+        distance[p] = Some(0);
+    }
+
+    // Breadth-first from all peaks at once, up to the trailheads
+    for d in 1..=9 {
+        let mut next: Vec<Point> = Vec::new();
+        for p in frontier {
+            for (_, q) in distance.neighbours(p).collect::<Vec<(Dir, Point)>>() {
+                if distance[q].is_none() {
+                    distance[q] = Some(d);
+                    next.push(q);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    let map = distance.map(|d| b'9' - d.unwrap_or(9));
+    Generated::input(map_input(&map))
+}
+
+fn stones(rng: &mut Rng, params: &Params) -> Generated {
+    let count = params.size(8);
+
+    let stones: Vec<String> = (0..count)
+        .map(|_| rng.range(0, 999_999).to_string())
+        .collect();
+
+    Generated::input(format!("{}\n", stones.join(" ")))
+}
+
+// Splits 0..len into runs of 1 to max
+fn runs(rng: &mut Rng, len: usize, max: usize) -> Vec<usize> {
+    let mut runs: Vec<usize> = Vec::new();
+    let mut left = len;
+    while left > 0 {
+        let run = rng.range(1, max.min(left) as u64) as usize;
+        runs.push(run);
+        left -= run;
+    }
+
+    runs
+}
+
+// Rectangular plots laid out like a chessboard, neighbours always draw their
+// letters from different halves of the alphabet so every plot is its own region
+fn garden(rng: &mut Rng, params: &Params) -> Generated {
+    let side = params.size(140);
+
+    let widths = runs(rng, side, 8);
+    let heights = runs(rng, side, 8);
+
+    let mut input = String::with_capacity(side * (side + 1));
+    let (mut price, mut discounted) = (0, 0);
+    for (r, &height) in heights.iter().enumerate() {
+        let mut row = String::with_capacity(side + 1);
+        for (c, &width) in widths.iter().enumerate() {
+            let plant = if (r + c) % 2 == 0 {
+                rng.pick(b"ABCDEFGHIJKLM")
+            } else {
+                rng.pick(b"NOPQRSTUVWXYZ")
+            };
+            row.extend(std::iter::repeat_n(plant as char, width));
+
+            let area = width * height;
+            price += area * 2 * (width + height);
+            discounted += area * 4;
+        }
+        row.push('\n');
+
+        for _ in 0..height {
+            input.push_str(&row);
+        }
+    }
+
+    Generated {
+        input,
+        part1: Some(price.to_string()),
+        part2: Some(discounted.to_string()),
+    }
+}
+
+// Density: share of machines whose prize can be won, the others are moved
+// off the lattice of reachable points
+fn claw_machines(rng: &mut Rng, params: &Params) -> Generated {
+    let count = params.size(320);
+    let winnable = params.density(0.6);
+
+    let mut machines: Vec<String> = Vec::new();
+    let mut tokens = 0;
+    while machines.len() < count {
+        let a: (i64, i64) = (rng.range(10, 99) as i64, rng.range(10, 99) as i64);
+        let b: (i64, i64) = (rng.range(10, 99) as i64, rng.range(10, 99) as i64);
+        let det = a.0 * b.1 - a.1 * b.0;
+        if det.abs() < 2 {
+            continue;
+        }
+
+        let (presses_a, presses_b) = (rng.range(1, 100) as i64, rng.range(1, 100) as i64);
+        let mut prize = (
+            presses_a * a.0 + presses_b * b.0,
+            presses_a * a.1 + presses_b * b.1,
+        );
+
+        if rng.chance(winnable) {
+            tokens += 3 * presses_a + presses_b;
+        } else {
+            // Cramer's rule gives a fractional number of A presses
+            let shift = (1..det.abs()).find(|s| (s * b.1) % det != 0);
+            let Some(shift) = shift else {
+                continue;
+            };
+            prize.0 += shift;
+        }
+
+        machines.push(format!(
+            "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+            a.0, a.1, b.0, b.1, prize.0, prize.1
+        ));
+    }
+
+    Generated {
+        input: machines.join("\n"),
+        part1: Some(tokens.to_string()),
+        part2: None,
+    }
+}
+
+// The robots are placed backwards from a second at which they draw a tree,
+// whether an earlier second also looks like one is left to chance
+fn robots(rng: &mut Rng, params: &Params) -> Generated {
+    let (width, height) = day14::BOUNDARY;
+    let count = params.size(500).min((width * height) as usize);
+
+    let mut cells: Vec<(i64, i64)> = Vec::new();
+    let tree = ((count as f64).sqrt() as i64).min(10);
+    let (top_x, top_y) = (width / 2, height / 2 - tree / 2);
+    for k in 0..tree {
+        for x in top_x - k..=top_x + k {
+            cells.push((x, top_y + k));
+        }
+    }
+
+    let taken: HashSet<(i64, i64)> = cells.iter().copied().collect();
+    let mut others: Vec<(i64, i64)> = (0..width)
+        .flat_map(|x| (0..height).map(move |y| (x, y)))
+        .filter(|cell| !taken.contains(cell))
+        .collect();
+    rng.shuffle(&mut others);
+    cells.extend(others.into_iter().take(count - cells.len()));
+
+    let second = rng.range(1000, (width * height - 1) as u64) as i64;
+    let mut input = String::new();
+    for (x, y) in cells {
+        let (vx, vy) = (rng.range(0, 198) as i64 - 99, rng.range(0, 198) as i64 - 99);
+        let start = (
+            (x - vx * second).rem_euclid(width),
+            (y - vy * second).rem_euclid(height),
+        );
+        writeln!(input, "p={},{} v={},{}", start.0, start.1, vx, vy).unwrap();
+    }
+
+    Generated::input(input)
+}
+
+// Density: share of cells with a box, a few more are walls
+fn warehouse(rng: &mut Rng, params: &Params) -> Generated {
+    let side = params.size(50).max(3);
+    let boxes = params.density(0.3);
+
+    let mut map = Grid::new(side, side, b'#');
+    for i in 1..side - 1 {
+        for j in 1..side - 1 {
+            map[Point::new(i, j)] = if rng.chance(0.05) {
+                b'#'
+            } else if rng.chance(boxes) {
+                b'O'
+            } else {
+                b'.'
+            };
+        }
+    }
+    map[Point::new(
+        rng.range(1, side as u64 - 2) as usize,
+        rng.range(1, side as u64 - 2) as usize,
+    )] = b'@';
+
+    let mut input = map_input(&map);
+    input.push('\n');
+    for _ in 0..side * 400 / 1000 + 1 {
+        let moves: String = (0..1000).map(|_| rng.pick(b"<>^v") as char).collect();
+        writeln!(input, "{}", moves).unwrap();
+    }
+
+    Generated::input(input)
+}
+
+// Perfect maze on the odd cells of a side x side map, then a share of the
+// inner walls is knocked down to make loops
+fn maze(rng: &mut Rng, side: usize, loops: f64) -> Grid<u8> {
+    let side = side.max(5) | 1;
+    let mut map = Grid::new(side, side, b'#');
+
+    let start = Point::new(1, 1);
+    map[start] = b'.';
+    let mut stack = vec![start];
+    while let Some(&p) = stack.last() {
+        let mut dirs = Dir::ALL;
+        rng.shuffle(&mut dirs);
+
+        let next = dirs.into_iter().find_map(|dir| {
+            let (di, dj) = dir.offset();
+            let cell = map.offset(p, (2 * di, 2 * dj))?;
+            (cell.i < side - 1 && cell.j < side - 1 && map[cell] == b'#').then_some((dir, cell))
+        });
+
+        match next {
+            Some((dir, cell)) => {
+                map[p.step(dir).unwrap()] = b'.';
+                map[cell] = b'.';
+                stack.push(cell);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+
+    for i in 1..side - 1 {
+        for j in 1..side - 1 {
+            let p = Point::new(i, j);
+            if (i + j) % 2 == 1 && map[p] == b'#' && rng.chance(loops) {
+                map[p] = b'.';
+            }
+        }
+    }
+
+    map
+}
+
+// Density: share of inner walls removed, so there are several best paths
+fn reindeer_maze(rng: &mut Rng, params: &Params) -> Generated {
+    let mut map = maze(rng, params.size(141), params.density(0.05));
+    let side = map.height();
+    map[Point::new(side - 2, 1)] = b'S';
+    map[Point::new(1, side - 2)] = b'E';
+
+    Generated::input(map_input(&map))
+}
+
+// The part 2 search is written for this program, so only register A changes
+fn device(rng: &mut Rng) -> Generated {
+    let reg_a = rng.range(1 << 30, 1 << 45);
+
+    Generated::input(format!(
+        "Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: 2,4,1,1,7,5,4,6,0,3,1,4,5,5,3,0\n",
+        reg_a
+    ))
+}
+
+// Size: side of the memory space, density: share of the cells with both
+// coordinates odd that get corrupted. Those never block the even rows and
+// columns, then a full even row falls and its last byte is the answer
+fn falling_bytes(rng: &mut Rng, params: &Params) -> Generated {
+    let side = params.size(71).max(5) | 1;
+    let noise = params.density(0.9);
+
+    let mut bytes: Vec<(usize, usize)> = Vec::new();
+    for x in (1..side).step_by(2) {
+        for y in (1..side).step_by(2) {
+            if rng.chance(noise) {
+                bytes.push((x, y));
+            }
+        }
+    }
+    rng.shuffle(&mut bytes);
+
+    // Odd columns of the row go first, so an even gap stays open until the end
+    let row = 2 * rng.range(1, (side as u64 - 3) / 2) as usize;
+    let mut odd: Vec<usize> = (1..side).step_by(2).collect();
+    let mut even: Vec<usize> = (0..side).step_by(2).collect();
+    rng.shuffle(&mut odd);
+    rng.shuffle(&mut even);
+    bytes.extend(odd.into_iter().chain(even).map(|x| (x, row)));
+
+    let mut input = String::new();
+    for &(x, y) in &bytes {
+        writeln!(input, "{},{}", x, y).unwrap();
+    }
+
+    let (x, y) = bytes[bytes.len() - 1];
+    Generated {
+        input,
+        part1: None,
+        part2: Some(format!("{},{}", x, y)),
+    }
+}
+
+// Density: share of possible designs. Patterns never use 'g' and are at least
+// 2 stripes long, which keeps the arrangement counts within a u64; impossible
+// designs get a 'g' stripe
+fn towels(rng: &mut Rng, params: &Params) -> Generated {
+    let count = params.size(400);
+    let possible_share = params.density(0.75);
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut patterns: Vec<String> = Vec::new();
+    while patterns.len() < 400 {
+        let len = rng.range(2, 8);
+        let pattern: String = (0..len).map(|_| rng.pick(b"wubr") as char).collect();
+        if seen.insert(pattern.clone()) {
+            patterns.push(pattern);
+        }
+    }
+
+    let mut designs: Vec<String> = Vec::new();
+    let mut possible = 0;
+    for _ in 0..count {
+        let mut design = String::new();
+        let len = rng.range(20, 60) as usize;
+        while design.len() < len {
+            design.push_str(&patterns[rng.index(patterns.len())]);
+        }
+
+        if rng.chance(possible_share) {
+            possible += 1;
+        } else {
+            let i = rng.index(design.len());
+            design.replace_range(i..=i, "g");
+        }
+        designs.push(design);
+    }
+
+    Generated {
+        input: format!("{}\n\n{}\n", patterns.join(", "), designs.join("\n")),
+        part1: Some(possible.to_string()),
+        part2: None,
+    }
+}
+
+// Density: share of inner walls removed, a perfect maze when 0
+fn racetrack(rng: &mut Rng, params: &Params) -> Generated {
+    let mut map = maze(rng, params.size(141), params.density(0.0));
+    let side = map.height();
+    map[Point::new(1, 1)] = b'S';
+    map[Point::new(side - 2, side - 2)] = b'E';
+
+    Generated::input(map_input(&map))
+}
+
+fn door_codes(rng: &mut Rng, params: &Params) -> Generated {
+    let mut input = String::new();
+    for _ in 0..params.size(5) {
+        writeln!(input, "{:03}A", rng.range(0, 999)).unwrap();
+    }
+
+    Generated::input(input)
+}
+
+fn secrets(rng: &mut Rng, params: &Params) -> Generated {
+    let mut input = String::new();
+    for _ in 0..params.size(2000) {
+        writeln!(input, "{}", rng.range(1, 16777215)).unwrap();
+    }
+
+    Generated::input(input)
+}
+
+const PLANTED_CLIQUE: usize = 13;
+
+// Density: edge probability between other computers of different groups.
+// They are split in 3 groups with no edges inside a group, so their cliques have at most 3
+// computers, and each one links to at most one clique member. That makes the
+// planted clique the unique largest one
+fn lan_party(rng: &mut Rng, params: &Params) -> Generated {
+    let count = params.size(520).max(4 * PLANTED_CLIQUE);
+    let edge_chance = params.density(0.025);
+
+    let mut width = 2;
+    while 26usize.pow(width) < count {
+        width += 1;
+    }
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut names: Vec<String> = Vec::new();
+    while names.len() < count {
+        let name: String = (0..width)
+            .map(|_| (b'a' + rng.index(26) as u8) as char)
+            .collect();
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    let mut edges: HashSet<(usize, usize)> = HashSet::new();
+    for u in 0..PLANTED_CLIQUE {
+        for v in u + 1..PLANTED_CLIQUE {
+            edges.insert((u, v));
+        }
+    }
+
+    let mut others: Vec<usize> = (PLANTED_CLIQUE..count).collect();
+    rng.shuffle(&mut others);
+    let mut others = others.into_iter();
+    for u in 0..PLANTED_CLIQUE {
+        for v in others.by_ref().take(rng.range(1, 3) as usize) {
+            edges.insert((u, v));
+        }
+    }
+
+    let pairs = (count - PLANTED_CLIQUE) * (count - PLANTED_CLIQUE - 1) / 2;
+    let wanted = edges.len() + (edge_chance * pairs as f64 * 2.0 / 3.0) as usize;
+    let mut attempts = 0;
+    while edges.len() < wanted && attempts < 20 * wanted {
+        attempts += 1;
+        let u = rng.range(PLANTED_CLIQUE as u64, count as u64 - 1) as usize;
+        let v = rng.range(PLANTED_CLIQUE as u64, count as u64 - 1) as usize;
+        if u % 3 != v % 3 {
+            edges.insert((u.min(v), u.max(v)));
+        }
+    }
+
+    let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+    edges.sort();
+    rng.shuffle(&mut edges);
+
+    let mut input = String::new();
+    for (u, v) in edges {
+        let (u, v) = if rng.chance(0.5) { (u, v) } else { (v, u) };
+        writeln!(input, "{}-{}", names[u], names[v]).unwrap();
+    }
+
+    let mut clique: Vec<&str> = names[..PLANTED_CLIQUE].iter().map(String::as_str).collect();
+    clique.sort();
+
+    Generated {
+        input,
+        part1: None,
+        part2: Some(clique.join(",")),
+    }
+}
+
+// Size: bits of x and y. A ripple-carry adder in the puzzle's layout with the
+// outputs of four pairs of gates within a bit swapped, in four bands of bits
+// far enough apart not to meet
+fn crossed_wires(rng: &mut Rng, params: &Params) -> Generated {
+    let bits = params.size(45).clamp(10, 63);
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut name = || loop {
+        let name: String = (0..3)
+            .map(|_| (b'a' + rng.index(23) as u8) as char)
+            .collect();
+        if seen.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    // The XOR and AND of x_i and y_i, the AND of that XOR and the carry in, and
+    // the carry out, which is the last z for the top bit
+    let z = |i: usize| format!("z{:02}", i);
+    let xor: Vec<String> = (0..bits).map(|_| name()).collect();
+    let and: Vec<String> = (0..bits).map(|_| name()).collect();
+    let carried: Vec<String> = (0..bits).map(|_| name()).collect();
+    let mut carry: Vec<String> = (0..bits).map(|_| name()).collect();
+    carry[bits - 1] = z(bits);
+
+    let mut gates: Vec<(String, &str, String, String)> = Vec::new();
+    for i in 0..bits {
+        let (x, y) = (format!("x{:02}", i), format!("y{:02}", i));
+        if i == 0 {
+            gates.push((x.clone(), "XOR", y.clone(), z(0)));
+            gates.push((x, "AND", y, carry[0].clone()));
+            continue;
+        }
+        gates.push((x.clone(), "XOR", y.clone(), xor[i].clone()));
+        gates.push((x, "AND", y, and[i].clone()));
+        gates.push((xor[i].clone(), "XOR", carry[i - 1].clone(), z(i)));
+        gates.push((
+            xor[i].clone(),
+            "AND",
+            carry[i - 1].clone(),
+            carried[i].clone(),
+        ));
+        gates.push((and[i].clone(), "OR", carried[i].clone(), carry[i].clone()));
+    }
+
+    let band = (bits - 2) / 4;
+    let mut swapped: Vec<String> = Vec::new();
+    for start in (0..4).map(|n| 1 + n * band) {
+        let i = start + rng.index(band - 1);
+        let (a, b) = match rng.index(4) {
+            0 => (xor[i].clone(), and[i].clone()),
+            1 => (z(i), carry[i].clone()),
+            2 => (z(i), carried[i].clone()),
+            _ => (z(i), and[i].clone()),
+        };
+        for gate in gates.iter_mut() {
+            if gate.3 == a {
+                gate.3 = b.clone();
+            } else if gate.3 == b {
+                gate.3 = a.clone();
+            }
+        }
+        swapped.extend([a, b]);
+    }
+    swapped.sort();
+
+    let mut input = String::new();
+    for prefix in ["x", "y"] {
+        for i in 0..bits {
+            writeln!(input, "{}{:02}: {}", prefix, i, rng.index(2)).unwrap();
+        }
+    }
+    input.push('\n');
+
+    rng.shuffle(&mut gates);
+    for (a, op, b, out) in gates {
+        let (a, b) = if rng.chance(0.5) { (a, b) } else { (b, a) };
+        writeln!(input, "{} {} {} -> {}", a, op, b, out).unwrap();
+    }
+
+    Generated {
+        input,
+        part1: None,
+        part2: Some(swapped.join(",")),
+    }
+}
+
+// Size: schematics. Density: share of locks. Pins are 0 to 5 high in 5
+// columns, as in the puzzle
+fn schematics(rng: &mut Rng, params: &Params) -> Generated {
+    const SPACE: usize = 5;
+
+    let mut locks: Vec<Vec<usize>> = Vec::new();
+    let mut keys: Vec<Vec<usize>> = Vec::new();
+    let mut drawn: Vec<String> = Vec::new();
+    for _ in 0..params.size(500).max(1) {
+        let pins: Vec<usize> = (0..5)
+            .map(|_| rng.range(0, SPACE as u64) as usize)
+            .collect();
+        let lock = rng.chance(params.density(0.5));
+
+        let mut rows: Vec<String> = (0..SPACE + 2)
+            .map(|row| {
+                pins.iter()
+                    .map(|&pin| if row <= pin { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        if lock {
+            locks.push(pins);
+        } else {
+            rows.reverse();
+            keys.push(pins);
+        }
+        drawn.push(rows.join("\n"));
+    }
+
+    let fits = locks
+        .iter()
+        .flat_map(|lock| keys.iter().map(move |key| (lock, key)))
+        .filter(|(lock, key)| lock.iter().zip(key.iter()).all(|(l, k)| l + k <= SPACE))
+        .count();
+
+    Generated {
+        input: drawn.join("\n\n") + "\n",
+        part1: Some(fits.to_string()),
+        part2: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Generated inputs use the official puzzle parameters
+    fn official_params(day: u32) -> crate::params::Params {
+        crate::runner(day).params(false, &[]).unwrap()
+    }
+
+    fn params(size: Option<usize>) -> Params {
+        Params {
+            seed: 7,
+            size,
+            density: None,
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in 1..=crate::LAST_DAY {
+            assert_eq!(
+                generate(day, &params(None)).input,
+                generate(day, &params(None)).input,
+                "day {}",
+                day
+            );
+        }
+    }
+
+    #[test]
+    fn inputs_parse() {
+        for day in 1..=crate::LAST_DAY {
+            let generated = generate(day, &params(None));
+            assert!(
+                crate::run_day(day, &generated.input, &official_params(day), &[], None).is_ok(),
+                "day {}",
+                day
+            );
+        }
+    }
+
+    #[test]
+    fn known_answers_match_solvers() {
+        for day in 1..=crate::LAST_DAY {
+            let generated = generate(day, &params(None));
+            for (part, known) in [(1, generated.part1), (2, generated.part2)] {
+                let Some(known) = known else {
+                    continue;
+                };
+                let results =
+                    crate::run_day(day, &generated.input, &official_params(day), &[part], None)
+                        .unwrap();
+                assert_eq!(results[0].answer, known, "day {} part {}", day, part);
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::params::Params;
+use crate::solutions::{day14_boundary, day16_costs, day18_size};
+use parser::ParseError;
+use viz::{ImageFormat, Palette, Picture};
+
+pub const DAYS: [u32; 7] = [6, 8, 10, 12, 14, 16, 18];
+// Days drawn as a graph rather than a grid, in Graphviz DOT
+pub const GRAPH_DAYS: [u32; 1] = [24];
+
+pub struct Options {
+    pub part: u8,
+    pub second: Option<i64>, // Day 14 only
+    pub scale: usize,
+    pub format: ImageFormat,
+    pub palette: Palette, // Overrides the day's colours
+}
+
+fn encode(mut picture: Picture, options: &Options) -> Vec<u8> {
+    picture.palette.extend(&options.palette);
+    picture.encode(options.format, options.scale)
+}
+
+// `part` picks what to draw when a day has more than one picture: the
+// antinodes with unlimited distance for day 8, the easter egg for day 14 and
+// the first blocking byte for day 18
+pub fn render(
+    day: u32,
+    input: &str,
+    params: &Params,
+    options: &Options,
+) -> Result<Vec<u8>, ParseError> {
+    let image = match day {
+        6 => encode(day6::picture(&day6::parse(input)?), options),
+        8 => {
+            let map = day8::parse(input)?;
+            encode(map.picture(options.part == 2), options)
+        }
+        10 => {
+            let map = day10::parse(input)?;
+            encode(day10::picture(&map), options)
+        }
+        12 => {
+            let map = day12::parse(input)?;
+            encode(day12::picture(&map), options)
+        }
+        14 => {
+            let robots = day14::parse(input)?;
+            let boundary = day14_boundary(params);
+            let second = options.second.unwrap_or_else(|| {
+                if options.part == 2 {
+                    day14::find_easter_egg(&robots, boundary).unwrap_or(0)
+                } else {
+                    params.get("seconds")
+                }
+            });
+            encode(day14::picture(&robots, boundary, second), options)
+        }
+        16 => {
+            let map = day16::parse(input)?;
+            let picture = day16::picture(&map, day16::start_position(&map), day16_costs(params));
+            encode(picture, options)
+        }
+        18 => {
+            let size = day18_size(params);
+            let coords = day18::parse(input, size)?;
+            let bytes: usize = params.get("bytes");
+            let fallen = if options.part == 2 {
+                day18::find_first_blocking_coord(&coords, size, bytes)
+                    .map_or(coords.len(), |i| i + 1)
+            } else {
+                bytes
+            };
+            encode(day18::picture(&coords, size, fallen), options)
+        }
+        _ => unreachable!("day {} has no picture", day),
+    };
+
+    Ok(image)
+}
+
+// The circuit of day 24 with the swapped wires, if any, in red. Render it with
+// `dot -Tsvg`
+pub fn graph(day: u32, input: &str) -> Result<String, ParseError> {
+    let graph = match day {
+        24 => {
+            let circuit = day24::parse(input)?;
+            let pairs = day24::swapped_pairs(&circuit).unwrap_or_default();
+            let swapped: Vec<String> = pairs.into_iter().flat_map(|(a, b)| [a, b]).collect();
+            day24::dot(&circuit, &swapped)
+        }
+        _ => unreachable!("day {} has no graph", day),
+    };
+
+    Ok(graph)
+}
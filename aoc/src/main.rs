@@ -0,0 +1,870 @@
+mod alloc;
+mod animate;
+#[cfg(all(test, feature = "real-inputs"))]
+mod answers;
+mod bench;
+mod generator;
+mod image;
+mod output;
+mod params;
+mod site;
+mod solutions;
+
+use bench::{Baseline, StageStats};
+use output::{Format, PartResult};
+use params::Params;
+use parser::{ParseError, Source};
+use solutions::*;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime};
+use std::{env, fs};
+
+const LAST_DAY: u32 = 25;
+
+const USAGE: &str = "Usage: aoc run <day|all> [--part 1|2] [--input PATH|-] [--example N]
+               [--param NAME=VALUE]... [--format text|json] [--explain]
+               [--threads N]
+       aoc bench <day|all> [--part 1|2] [--input PATH|-] [--example N] [--param NAME=VALUE]...
+                 [--iterations N] [--baseline PATH] [--save-baseline PATH]
+                 [--threshold PERCENT] [--threads N]
+       aoc animate <day> [--part 1|2] [--input PATH|-] [--example N] [--param NAME=VALUE]...
+                   [--cast PATH] [--delay MS]
+       aoc image <day> --output PATH [--part 1|2] [--input PATH|-] [--example N]
+                 [--param NAME=VALUE]... [--scale N] [--palette KEY=rrggbb,...] [--second N]
+       aoc gen <day> [--seed N] [--size N] [--density F]
+       aoc fetch <day|all>
+       aoc submit <day> --part 1|2 [--answer ANSWER]
+       aoc session < COOKIE";
+
+const DEFAULT_ITERATIONS: usize = 5;
+const DEFAULT_DELAY_MS: u64 = 50;
+const DEFAULT_SCALE: usize = 8;
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[global_allocator]
+static ALLOC: alloc::PeakAlloc = alloc::PeakAlloc;
+
+// Usage errors are followed by the usage line, failures while solving are not
+enum Error {
+    Usage(String),
+    Failed(String),
+}
+
+impl From<&str> for Error {
+    fn from(msg: &str) -> Error {
+        Error::Usage(msg.to_string())
+    }
+}
+
+// Facts explaining the answers go to stderr, so stdout stays the answers
+type Explain = trace::Writer<io::Stderr>;
+
+fn solve<S: Solution>(
+    day: u32,
+    input: &str,
+    params: &Params,
+    parts: &[u8],
+    mut explain: Option<&mut Explain>,
+) -> Result<Vec<PartResult>, ParseError> {
+    let parsed = S::parse(input, params)?;
+
+    Ok(parts
+        .iter()
+        .map(|&part| {
+            let now = Instant::now();
+            let answer = match (part, explain.as_deref_mut()) {
+                (1, None) => S::part1_detailed(&parsed),
+                (_, None) => S::part2_detailed(&parsed),
+                (1, Some(writer)) => {
+                    writer.begin(day, part);
+                    S::part1_explained(&parsed, writer)
+                }
+                (_, Some(writer)) => {
+                    writer.begin(day, part);
+                    S::part2_explained(&parsed, writer)
+                }
+            };
+
+            PartResult::new(day, part, answer, now.elapsed())
+        })
+        .collect())
+}
+
+// Object-safe view of a Solution, so a day can be picked at runtime
+trait Runner {
+    fn params(&self, example: bool, given: &[(String, String)]) -> Result<Params, String>;
+    fn solve(
+        &self,
+        day: u32,
+        input: &str,
+        params: &Params,
+        parts: &[u8],
+        explain: Option<&mut Explain>,
+    ) -> Result<Vec<PartResult>, ParseError>;
+    fn bench(
+        &self,
+        day: u32,
+        input: &str,
+        params: &Params,
+        parts: &[u8],
+        iterations: usize,
+    ) -> Result<Vec<StageStats>, ParseError>;
+}
+
+impl<S: Solution> Runner for S {
+    fn params(&self, example: bool, given: &[(String, String)]) -> Result<Params, String> {
+        let example_params = if example { S::EXAMPLE_PARAMS } else { &[] };
+        let params = Params::resolve(S::PARAMS, example_params, given)?;
+        S::check_params(&params)?;
+        Ok(params)
+    }
+
+    fn solve(
+        &self,
+        day: u32,
+        input: &str,
+        params: &Params,
+        parts: &[u8],
+        explain: Option<&mut Explain>,
+    ) -> Result<Vec<PartResult>, ParseError> {
+        solve::<S>(day, input, params, parts, explain)
+    }
+
+    fn bench(
+        &self,
+        day: u32,
+        input: &str,
+        params: &Params,
+        parts: &[u8],
+        iterations: usize,
+    ) -> Result<Vec<StageStats>, ParseError> {
+        bench::bench::<S>(day, input, params, parts, iterations)
+    }
+}
+
+fn runner(day: u32) -> &'static dyn Runner {
+    match day {
+        1 => &Day1,
+        2 => &Day2,
+        3 => &Day3,
+        4 => &Day4,
+        5 => &Day5,
+        6 => &Day6,
+        7 => &Day7,
+        8 => &Day8,
+        9 => &Day9,
+        10 => &Day10,
+        11 => &Day11,
+        12 => &Day12,
+        13 => &Day13,
+        14 => &Day14,
+        15 => &Day15,
+        16 => &Day16,
+        17 => &Day17,
+        18 => &Day18,
+        19 => &Day19,
+        20 => &Day20,
+        21 => &Day21,
+        22 => &Day22,
+        23 => &Day23,
+        24 => &Day24,
+        25 => &Day25,
+        _ => unreachable!("day {} is not solved", day),
+    }
+}
+
+// Official values, the example's when solving one, then the ones given with --param
+fn day_params(day: u32, source: &Source, given: &[(String, String)]) -> Result<Params, Error> {
+    let example = matches!(source, Source::Example(_));
+    runner(day).params(example, given).map_err(Error::Usage)
+}
+
+fn run_day(
+    day: u32,
+    input: &str,
+    params: &Params,
+    parts: &[u8],
+    explain: Option<&mut Explain>,
+) -> Result<Vec<PartResult>, ParseError> {
+    runner(day).solve(day, input, params, parts, explain)
+}
+
+fn workspace() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+// Examples are kept next to each day's crate, e.g. day12/test.txt, inputs are
+// fetched into inputs/
+fn day_dir(day: u32) -> PathBuf {
+    workspace().join(format!("day{}", day))
+}
+
+fn read_input(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path)
+        .map_err(|e| Error::Failed(format!("Unable to read {}: {}", path.display(), e)))
+}
+
+// Returns the name to show in diagnostics and the input
+fn read_source(day: u32, source: &Source) -> Result<(String, String), Error> {
+    let dir = day_dir(day);
+    let input = source.read(&dir).map_err(Error::Failed)?;
+
+    Ok((source.name(&dir), input))
+}
+
+fn run_day_on(
+    day: u32,
+    source: &Source,
+    params: &Params,
+    parts: &[u8],
+    explain: Option<&mut Explain>,
+) -> Result<Vec<PartResult>, Error> {
+    let (name, input) = read_source(day, source)?;
+
+    run_day(day, &input, params, parts, explain).map_err(|e| Error::Failed(e.render(&name, &input)))
+}
+
+fn bench_day_on(
+    day: u32,
+    source: &Source,
+    params: &Params,
+    parts: &[u8],
+    iterations: usize,
+) -> Result<Vec<StageStats>, Error> {
+    let (name, input) = read_source(day, source)?;
+
+    runner(day)
+        .bench(day, &input, params, parts, iterations)
+        .map_err(|e| Error::Failed(e.render(&name, &input)))
+}
+
+fn run_one(
+    day: u32,
+    parts: &[u8],
+    source: &Source,
+    params: &Params,
+    format: Format,
+    explain: Option<&mut Explain>,
+) -> Result<(), Error> {
+    let results = run_day_on(day, source, params, parts, explain)?;
+
+    match format {
+        Format::Text => output::print_text(&results),
+        Format::Json => output::print_json(&results),
+    }
+
+    Ok(())
+}
+
+fn run_all(parts: &[u8], format: Format, mut explain: Option<&mut Explain>) -> Result<(), Error> {
+    if format == Format::Text {
+        output::print_table_header();
+    }
+
+    let mut all_results: Vec<PartResult> = Vec::new();
+    let mut total = Duration::ZERO;
+    for day in 1..=LAST_DAY {
+        let params = day_params(day, &Source::Default, &[])?;
+        let results = run_day_on(
+            day,
+            &Source::Default,
+            &params,
+            parts,
+            explain.as_deref_mut(),
+        )?;
+        total += results.iter().map(|r| r.elapsed).sum::<Duration>();
+
+        if format == Format::Text {
+            output::print_table_row(day, &results);
+        }
+        all_results.extend(results);
+    }
+
+    match format {
+        Format::Text => println!("Total: {:.2?}", total),
+        Format::Json => output::print_json(&all_results),
+    }
+
+    Ok(())
+}
+
+fn parse_day(arg: &str) -> Result<u32, Error> {
+    match arg.parse::<u32>() {
+        Ok(day) if (1..=LAST_DAY).contains(&day) => Ok(day),
+        _ => Err(Error::Usage(format!(
+            "Invalid day '{}', expected 1-{}",
+            arg, LAST_DAY
+        ))),
+    }
+}
+
+struct BenchOptions {
+    iterations: usize,
+    baseline: Option<PathBuf>,
+    save_baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+// Returns whether any stage got slower than the baseline by more than the threshold
+fn bench(
+    days: &[u32],
+    source: &Source,
+    given: &[(String, String)],
+    parts: &[u8],
+    options: &BenchOptions,
+) -> Result<bool, Error> {
+    let baseline: Option<Baseline> = match &options.baseline {
+        Some(path) => Some(bench::read_baseline(&read_input(path)?).map_err(Error::Failed)?),
+        None => None,
+    };
+
+    println!(
+        "{:>3} | {:<5} | {:>10} | {:>10} | {:>10} | {:>10} | {:>10}",
+        "Day", "Stage", "Min", "Median", "Max", "Peak alloc", "vs base"
+    );
+    println!(
+        "{:-<3}-+-{:-<5}-+-{:-<10}-+-{:-<10}-+-{:-<10}-+-{:-<10}-+-{:-<10}",
+        "", "", "", "", "", "", ""
+    );
+
+    let mut all_stats: Vec<StageStats> = Vec::new();
+    let mut regressed = false;
+
+    for &day in days {
+        let params = day_params(day, source, given)?;
+        for stats in bench_day_on(day, source, &params, parts, options.iterations)? {
+            let change = baseline
+                .as_ref()
+                .and_then(|baseline| bench::change_percent(&stats, baseline));
+            let flag = match change {
+                Some(change) if change > options.threshold => {
+                    regressed = true;
+                    " REGRESSION"
+                }
+                _ => "",
+            };
+
+            println!(
+                "{:>3} | {:<5} | {:>10} | {:>10} | {:>10} | {:>10} | {:>10}{}",
+                stats.day,
+                stats.stage.name(),
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max),
+                bench::format_bytes(stats.peak_bytes),
+                change.map_or("-".to_string(), |c| format!("{:+.1}%", c)),
+                flag
+            );
+
+            all_stats.push(stats);
+        }
+    }
+
+    if let Some(path) = &options.save_baseline {
+        fs::write(path, bench::write_baseline(&all_stats))
+            .map_err(|e| Error::Failed(format!("Unable to write {}: {}", path.display(), e)))?;
+    }
+
+    Ok(regressed)
+}
+
+// Plays in the terminal, or records an asciinema cast with --cast
+fn animate(
+    day: u32,
+    source: &Source,
+    params: &Params,
+    part: u8,
+    cast: Option<&Path>,
+    delay: Duration,
+) -> Result<(), Error> {
+    if !animate::DAYS.contains(&day) {
+        return Err(Error::Usage(format!(
+            "Day {} has no animation, try one of {:?}",
+            day,
+            animate::DAYS
+        )));
+    }
+
+    let (name, input) = read_source(day, source)?;
+    let failed = |e: ParseError| Error::Failed(e.render(&name, &input));
+
+    match cast {
+        Some(path) => {
+            let write_error =
+                |e: io::Error| Error::Failed(format!("Unable to write {}: {}", path.display(), e));
+            let file = fs::File::create(path).map_err(write_error)?;
+
+            let mut cast = viz::Cast::new(io::BufWriter::new(file), delay.as_secs_f64());
+            animate::animate(day, &input, params, part, &mut cast).map_err(failed)?;
+            cast.finish().map_err(write_error)?;
+        }
+        None => {
+            let terminal_error = |e: io::Error| Error::Failed(format!("Terminal error: {}", e));
+
+            let mut terminal = viz::Terminal::new(delay).map_err(terminal_error)?;
+            animate::animate(day, &input, params, part, &mut terminal).map_err(failed)?;
+            terminal.finish().map_err(terminal_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Writes a PPM, PNG or SVG picture, picked by the extension of `output`
+fn image(
+    day: u32,
+    source: &Source,
+    params: &Params,
+    output: &Path,
+    options: image::Options,
+) -> Result<(), Error> {
+    if !image::DAYS.contains(&day) {
+        return Err(Error::Usage(format!(
+            "Day {} has no picture, try one of {:?}",
+            day,
+            image::DAYS
+        )));
+    }
+
+    let (name, input) = read_source(day, source)?;
+    let image = image::render(day, &input, params, &options)
+        .map_err(|e| Error::Failed(e.render(&name, &input)))?;
+
+    fs::write(output, image)
+        .map_err(|e| Error::Failed(format!("Unable to write {}: {}", output.display(), e)))
+}
+
+// Writes a Graphviz DOT graph, the days with one have no other options
+fn graph(day: u32, source: &Source, output: &Path) -> Result<(), Error> {
+    let is_dot = output
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("dot"));
+    if !is_dot {
+        return Err(Error::Usage(format!(
+            "Day {} draws a graph, --output must end in .dot",
+            day
+        )));
+    }
+
+    let (name, input) = read_source(day, source)?;
+    let graph = image::graph(day, &input).map_err(|e| Error::Failed(e.render(&name, &input)))?;
+
+    fs::write(output, graph)
+        .map_err(|e| Error::Failed(format!("Unable to write {}: {}", output.display(), e)))
+}
+
+// The input goes to stdout so it can be piped into run, known answers to stderr
+fn print_generated(day: u32, params: &generator::Params) -> Result<(), Error> {
+    let generated = generator::generate(day, params);
+
+    io::stdout()
+        .lock()
+        .write_all(generated.input.as_bytes())
+        .map_err(|e| Error::Failed(format!("Unable to write the input: {}", e)))?;
+
+    for (part, answer) in [(1, generated.part1), (2, generated.part2)] {
+        if let Some(answer) = answer {
+            eprintln!("part{} = {:?}", part, answer);
+        }
+    }
+
+    Ok(())
+}
+
+fn gen_command<'a>(target: &str, mut args: impl Iterator<Item = &'a String>) -> Result<(), Error> {
+    let day = parse_day(target)?;
+    let mut params = generator::Params {
+        seed: 1,
+        size: None,
+        density: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => params.seed = parse_number(args.next(), arg)?,
+            "--size" => params.size = Some(parse_number(args.next(), arg)?),
+            "--density" => {
+                let density: f64 = parse_number(args.next(), arg)?;
+                if !(0.0..=1.0).contains(&density) {
+                    return Err("--density must be between 0 and 1".into());
+                }
+                params.density = Some(density);
+            }
+            _ => return Err(Error::Usage(format!("Unknown option '{}'", arg))),
+        }
+    }
+
+    print_generated(day, &params)
+}
+
+// AOC_URL points at another site, such as a local stand-in
+fn site() -> Result<site::Site, Error> {
+    let root = workspace();
+    let session = site::session(&root).map_err(Error::Failed)?;
+    let url = env::var("AOC_URL").unwrap_or_else(|_| site::DEFAULT_URL.to_string());
+
+    Ok(site::Site::new(&url, &session, &root))
+}
+
+fn relative(path: &Path) -> String {
+    let root = workspace();
+    path.strip_prefix(&root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn fetch(target: &str) -> Result<(), Error> {
+    let days: Vec<u32> = if target == "all" {
+        (1..=LAST_DAY).collect()
+    } else {
+        vec![parse_day(target)?]
+    };
+
+    let site = site()?;
+    for day in days {
+        let (path, downloaded) = site.fetch(day).map_err(Error::Failed)?;
+        let how = if downloaded { "downloaded" } else { "cached" };
+        println!("Day {}: {} {}", day, how, relative(&path));
+    }
+
+    Ok(())
+}
+
+// Without --answer, solves the part from the fetched input. Succeeds when the
+// answer is right
+fn submit<'a>(target: &str, mut args: impl Iterator<Item = &'a String>) -> Result<bool, Error> {
+    let day = parse_day(target)?;
+    let mut part: Option<u8> = None;
+    let mut answer: Option<String> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                part = match args.next().map(String::as_str) {
+                    Some("1") => Some(1),
+                    Some("2") => Some(2),
+                    _ => return Err("--part expects 1 or 2".into()),
+                };
+            }
+            "--answer" => answer = Some(args.next().ok_or("--answer expects a value")?.clone()),
+            _ => return Err(Error::Usage(format!("Unknown option '{}'", arg))),
+        }
+    }
+
+    let part = part.ok_or("submit needs --part")?;
+    let site = site()?;
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            site.fetch(day).map_err(Error::Failed)?;
+            let params = day_params(day, &Source::Default, &[])?;
+            let result = run_day_on(day, &Source::Default, &params, &[part], None)?.remove(0);
+            if let Some(error) = result.diagnostics.get("error") {
+                return Err(Error::Failed(format!("No answer to submit: {}", error)));
+            }
+            result.answer
+        }
+    };
+
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let outcome = site
+        .submit(day, part, &answer, now)
+        .map_err(Error::Failed)?;
+
+    let verdict = match &outcome.verdict {
+        site::Verdict::Right => "is right".to_string(),
+        site::Verdict::Wrong(Some(site::Hint::High)) => "is wrong, too high".to_string(),
+        site::Verdict::Wrong(Some(site::Hint::Low)) => "is wrong, too low".to_string(),
+        site::Verdict::Wrong(None) => "is wrong".to_string(),
+        site::Verdict::Solved(right) => format!("was not sent, {} was accepted already", right),
+        site::Verdict::WrongLevel => {
+            "was not accepted, the part is solved already or part 1 is still open".to_string()
+        }
+        site::Verdict::Wait(seconds) => format!("was not sent, wait {}s", seconds),
+    };
+    let known = if outcome.submitted {
+        ""
+    } else {
+        " (from earlier answers)"
+    };
+    println!("Day {} part {}: {} {}{}", day, part, answer, verdict, known);
+
+    Ok(outcome.verdict == site::Verdict::Right)
+}
+
+// Reads the cookie from stdin so it stays out of the shell history
+fn store_session() -> Result<(), Error> {
+    let mut session = String::new();
+    io::stdin()
+        .read_line(&mut session)
+        .map_err(|e| Error::Failed(format!("Unable to read stdin: {}", e)))?;
+    if session.trim().is_empty() {
+        return Err("session expects the cookie on stdin".into());
+    }
+
+    let path = site::store_session(&workspace(), &session).map_err(Error::Failed)?;
+    println!("Stored in {}", relative(&path));
+    Ok(())
+}
+
+// Threads of the pool used by the parallel feature, rayon starts one per core
+// by default
+#[cfg(feature = "parallel")]
+fn set_threads(threads: usize) -> Result<(), Error> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|e| Error::Failed(format!("Unable to start {} threads: {}", threads, e)))
+}
+
+#[cfg(not(feature = "parallel"))]
+fn set_threads(_: usize) -> Result<(), Error> {
+    Err("--threads needs aoc built with --features parallel".into())
+}
+
+fn parse_number<T: std::str::FromStr>(value: Option<&String>, option: &str) -> Result<T, Error> {
+    value
+        .and_then(|v| v.parse::<T>().ok())
+        .ok_or_else(|| Error::Usage(format!("{} expects a number", option)))
+}
+
+fn run(args: &[String]) -> Result<ExitCode, Error> {
+    let mut args = args.iter();
+
+    let command = match args.next().map(String::as_str) {
+        Some("session") => {
+            store_session()?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(cmd @ ("run" | "bench" | "animate" | "image" | "gen" | "fetch" | "submit")) => cmd,
+        Some(cmd) => return Err(Error::Usage(format!("Unknown command '{}'", cmd))),
+        None => return Err("Missing command".into()),
+    };
+
+    let target = args.next().ok_or("Missing day")?;
+
+    if command == "gen" {
+        gen_command(target, args)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if command == "fetch" {
+        if let Some(arg) = args.next() {
+            return Err(Error::Usage(format!("Unknown option '{}'", arg)));
+        }
+        fetch(target)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if command == "submit" {
+        return if submit(target, args)? {
+            Ok(ExitCode::SUCCESS)
+        } else {
+            Ok(ExitCode::FAILURE)
+        };
+    }
+
+    let mut parts: Vec<u8> = vec![1, 2];
+    let mut source = Source::Default;
+    let mut given: Vec<(String, String)> = Vec::new();
+    let mut format = Format::Text;
+    let mut explain = false;
+    let mut cast: Option<PathBuf> = None;
+    let mut delay = Duration::from_millis(DEFAULT_DELAY_MS);
+    let mut output: Option<PathBuf> = None;
+    let mut image_options = image::Options {
+        part: 1,
+        second: None,
+        scale: DEFAULT_SCALE,
+        format: viz::ImageFormat::Png,
+        palette: viz::Palette::default(),
+    };
+    let mut options = BenchOptions {
+        iterations: DEFAULT_ITERATIONS,
+        baseline: None,
+        save_baseline: None,
+        threshold: DEFAULT_THRESHOLD,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threads" => {
+                let threads: usize = parse_number(args.next(), arg)?;
+                if threads == 0 {
+                    return Err("--threads must be at least 1".into());
+                }
+                set_threads(threads)?;
+            }
+            "--part" => {
+                parts = match args.next().map(String::as_str) {
+                    Some("1") => vec![1],
+                    Some("2") => vec![2],
+                    _ => return Err("--part expects 1 or 2".into()),
+                };
+            }
+            "--input" => {
+                let path = args.next().ok_or("--input expects a path or -")?;
+                source = Source::from_arg(path);
+            }
+            "--example" => {
+                let n = args.next().ok_or("--example expects a number")?;
+                source = Source::example(n).map_err(Error::Usage)?;
+            }
+            "--param" => {
+                let assignment = args.next().ok_or("--param expects NAME=VALUE")?;
+                given.push(
+                    params::parse_assignment(assignment).ok_or("--param expects NAME=VALUE")?,
+                );
+            }
+            "--format" | "--explain" if command != "run" => {
+                return Err(Error::Usage(format!("{} is only used by run", arg)));
+            }
+            "--explain" => explain = true,
+            "--format" => {
+                format = args
+                    .next()
+                    .and_then(|name| Format::from_name(name))
+                    .ok_or("--format expects json or text")?;
+            }
+            "--iterations" | "--baseline" | "--save-baseline" | "--threshold"
+                if command != "bench" =>
+            {
+                return Err(Error::Usage(format!("{} is only used by bench", arg)));
+            }
+            "--iterations" => {
+                options.iterations = parse_number(args.next(), arg)?;
+                if options.iterations == 0 {
+                    return Err("--iterations must be at least 1".into());
+                }
+            }
+            "--threshold" => options.threshold = parse_number(args.next(), arg)?,
+            "--baseline" => {
+                let path = args.next().ok_or("--baseline expects a path")?;
+                options.baseline = Some(PathBuf::from(path));
+            }
+            "--save-baseline" => {
+                let path = args.next().ok_or("--save-baseline expects a path")?;
+                options.save_baseline = Some(PathBuf::from(path));
+            }
+            "--cast" | "--delay" if command != "animate" => {
+                return Err(Error::Usage(format!("{} is only used by animate", arg)));
+            }
+            "--cast" => {
+                let path = args.next().ok_or("--cast expects a path")?;
+                cast = Some(PathBuf::from(path));
+            }
+            "--delay" => delay = Duration::from_millis(parse_number(args.next(), arg)?),
+            "--output" | "--scale" | "--palette" | "--second" if command != "image" => {
+                return Err(Error::Usage(format!("{} is only used by image", arg)));
+            }
+            "--output" => {
+                let path = args.next().ok_or("--output expects a path")?;
+                output = Some(PathBuf::from(path));
+            }
+            "--scale" => {
+                image_options.scale = parse_number(args.next(), arg)?;
+                if image_options.scale == 0 {
+                    return Err("--scale must be at least 1".into());
+                }
+            }
+            "--palette" => {
+                let spec = args.next().ok_or("--palette expects KEY=rrggbb entries")?;
+                image_options.palette = viz::Palette::parse(spec).map_err(Error::Usage)?;
+            }
+            "--second" => image_options.second = Some(parse_number(args.next(), arg)?),
+            _ => return Err(Error::Usage(format!("Unknown option '{}'", arg))),
+        }
+    }
+
+    if command == "animate" {
+        let day = parse_day(target)?;
+        let params = day_params(day, &source, &given)?;
+        let part = if parts == [2] { 2 } else { 1 };
+        animate(day, &source, &params, part, cast.as_deref(), delay)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if command == "image" {
+        let output = output.ok_or("image needs --output")?;
+        let day = parse_day(target)?;
+        if image::GRAPH_DAYS.contains(&day) {
+            graph(day, &source, &output)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        image_options.format = viz::ImageFormat::from_path(&output)
+            .ok_or("--output must end in .ppm, .png or .svg")?;
+        image_options.part = if parts == [2] { 2 } else { 1 };
+        let params = day_params(day, &source, &given)?;
+        image(day, &source, &params, &output, image_options)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if target == "all" && source != Source::Default {
+        return Err("--input and --example cannot be used with 'all'".into());
+    }
+    // Every day has its own parameters
+    if target == "all" && !given.is_empty() {
+        return Err("--param cannot be used with 'all'".into());
+    }
+
+    if command == "bench" {
+        let days: Vec<u32> = if target == "all" {
+            (1..=LAST_DAY).collect()
+        } else {
+            vec![parse_day(target)?]
+        };
+
+        return if bench(&days, &source, &given, &parts, &options)? {
+            Ok(ExitCode::FAILURE)
+        } else {
+            Ok(ExitCode::SUCCESS)
+        };
+    }
+
+    let style = match format {
+        Format::Text => trace::Style::Text,
+        Format::Json => trace::Style::Json,
+    };
+    let mut writer = explain.then(|| trace::Writer::new(io::stderr(), style));
+
+    if target == "all" {
+        run_all(&parts, format, writer.as_mut())?;
+    } else {
+        let day = parse_day(target)?;
+        let params = day_params(day, &source, &given)?;
+        run_one(day, &parts, &source, &params, format, writer.as_mut())?;
+    }
+
+    if let Some(writer) = writer {
+        writer
+            .finish()
+            .map_err(|e| Error::Failed(format!("Unable to write the explanation: {}", e)))?;
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(code) => code,
+        Err(Error::Usage(msg)) => {
+            eprintln!("{}", msg);
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+        Err(Error::Failed(msg)) => {
+            eprintln!("{}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::solutions::Diagnostics;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+// The outcome of solving one part of one day, the same for every day
+#[derive(Debug, Serialize)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    #[serde(rename = "elapsed_ns", serialize_with = "as_nanos")]
+    pub elapsed: Duration,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub diagnostics: BTreeMap<&'static str, String>,
+}
+
+impl PartResult {
+    pub fn new(
+        day: u32,
+        part: u8,
+        (answer, diagnostics): (String, Diagnostics),
+        elapsed: Duration,
+    ) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer,
+            elapsed,
+            diagnostics: diagnostics.into_iter().collect(),
+        }
+    }
+}
+
+fn as_nanos<S: Serializer>(elapsed: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(elapsed.as_nanos() as u64)
+}
+
+pub fn print_json(results: &[PartResult]) {
+    println!("{}", serde_json::to_string_pretty(results).unwrap());
+}
+
+pub fn print_text(results: &[PartResult]) {
+    for result in results {
+        println!(
+            "Day {} Part {}: {} ({:.2?})",
+            result.day, result.part, result.answer, result.elapsed
+        );
+
+        for (name, value) in &result.diagnostics {
+            println!("  {}: {}", name, value);
+        }
+    }
+}
+
+pub fn print_table_header() {
+    println!(
+        "{:>3} | {:<20} | {:<20} | {:>10}",
+        "Day", "Part 1", "Part 2", "Time"
+    );
+    println!("{:-<3}-+-{:-<20}-+-{:-<20}-+-{:-<10}", "", "", "", "");
+}
+
+// One row with both parts of a day
+pub fn print_table_row(day: u32, results: &[PartResult]) {
+    let answer = |part: u8| {
+        results
+            .iter()
+            .find(|r| r.part == part)
+            .map_or("-".to_string(), |r| r.answer.clone())
+    };
+    let elapsed: Duration = results.iter().map(|r| r.elapsed).sum();
+
+    println!(
+        "{:>3} | {:<20} | {:<20} | {:>10}",
+        day,
+        answer(1),
+        answer(2),
+        format!("{:.2?}", elapsed)
+    );
+}
@@ -0,0 +1,144 @@
+use std::ops::RangeInclusive;
+
+// A puzzle parameter of a day, such as the size of a grid, set with --param NAME=VALUE
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64, // The official value
+    pub range: RangeInclusive<i64>,
+}
+
+// The values of the parameters of one day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(&'static str, i64)>,
+}
+
+impl Params {
+    // Official values, overridden by the example's when solving one and then by
+    // the given NAME=VALUE pairs
+    pub fn resolve(
+        spec: &[Param],
+        example: &[(&str, i64)],
+        given: &[(String, String)],
+    ) -> Result<Params, String> {
+        let mut values: Vec<(&'static str, i64)> = spec
+            .iter()
+            .map(|param| (param.name, param.default))
+            .collect();
+
+        for &(name, value) in example {
+            let (_, current) = values.iter_mut().find(|(n, _)| *n == name).unwrap();
+            *current = value;
+        }
+
+        for (name, value) in given {
+            let Some(param) = spec.iter().find(|param| param.name == name) else {
+                return Err(if spec.is_empty() {
+                    format!("Unknown parameter '{}', this day has none", name)
+                } else {
+                    let names: Vec<&str> = spec.iter().map(|param| param.name).collect();
+                    format!(
+                        "Unknown parameter '{}', expected one of {}",
+                        name,
+                        names.join(", ")
+                    )
+                });
+            };
+
+            let value = value
+                .parse::<i64>()
+                .ok()
+                .filter(|value| param.range.contains(value))
+                .ok_or_else(|| {
+                    format!(
+                        "Parameter '{}' expects a number from {} to {}",
+                        name,
+                        param.range.start(),
+                        param.range.end()
+                    )
+                })?;
+
+            let (_, current) = values.iter_mut().find(|(n, _)| n == name).unwrap();
+            *current = value;
+        }
+
+        Ok(Params { values })
+    }
+
+    // The ranges of the parameters keep their values within any type the days use
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> T {
+        let &(_, value) = self
+            .values
+            .iter()
+            .find(|(n, _)| *n == name)
+            .unwrap_or_else(|| panic!("no parameter '{}'", name));
+
+        T::try_from(value)
+            .ok()
+            .unwrap_or_else(|| panic!("parameter '{}' = {} is out of range", name, value))
+    }
+}
+
+// The NAME=VALUE argument of --param
+pub fn parse_assignment(arg: &str) -> Option<(String, String)> {
+    let (name, value) = arg.split_once('=')?;
+    (!name.is_empty()).then(|| (name.to_string(), value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &[Param] = &[
+        Param {
+            name: "width",
+            default: 101,
+            range: 1..=1000,
+        },
+        Param {
+            name: "height",
+            default: 103,
+            range: 1..=1000,
+        },
+    ];
+
+    fn given(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|&(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn given_values_win_over_example_and_official_ones() {
+        let params = Params::resolve(SPEC, &[], &[]).unwrap();
+        assert_eq!(
+            (params.get::<i64>("width"), params.get::<i64>("height")),
+            (101, 103)
+        );
+
+        let example: &[(&str, i64)] = &[("width", 11), ("height", 7)];
+        let params = Params::resolve(SPEC, example, &given(&[("height", "9")])).unwrap();
+        assert_eq!(
+            (params.get::<usize>("width"), params.get::<usize>("height")),
+            (11, 9)
+        );
+    }
+
+    #[test]
+    fn unknown_names_and_bad_values_are_rejected() {
+        let e = Params::resolve(SPEC, &[], &given(&[("depth", "3")])).unwrap_err();
+        assert_eq!(
+            e,
+            "Unknown parameter 'depth', expected one of width, height"
+        );
+
+        let e = Params::resolve(&[], &[], &given(&[("depth", "3")])).unwrap_err();
+        assert_eq!(e, "Unknown parameter 'depth', this day has none");
+
+        for value in ["0", "1001", "-3", "wide"] {
+            let e = Params::resolve(SPEC, &[], &given(&[("width", value)])).unwrap_err();
+            assert_eq!(e, "Parameter 'width' expects a number from 1 to 1000");
+        }
+    }
+}
@@ -0,0 +1,562 @@
+// Downloads puzzle inputs and submits answers. Both are cached under
+// inputs/<year> so the site is only asked what it has not answered yet
+use parser::YEAR;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fs, io};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+// The site asks automated tools to say what they are
+const USER_AGENT: &str = "aoc-2024-runner (ureq)";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+// Where `aoc session` keeps the cookie, inputs/ is not committed
+pub fn session_path(workspace: &Path) -> PathBuf {
+    workspace.join("inputs").join(".session")
+}
+
+// The session cookie from AOC_SESSION, or the one stored with `aoc session`
+pub fn session(workspace: &Path) -> Result<String, String> {
+    let session = match std::env::var("AOC_SESSION") {
+        Ok(session) => session,
+        Err(_) => fs::read_to_string(session_path(workspace)).map_err(|_| {
+            "No session cookie, set AOC_SESSION or store it with `aoc session`".to_string()
+        })?,
+    };
+
+    let session = session.trim();
+    if session.is_empty() {
+        return Err("The session cookie is empty".to_string());
+    }
+    Ok(session.to_string())
+}
+
+pub fn store_session(workspace: &Path, session: &str) -> Result<PathBuf, String> {
+    let path = session_path(workspace);
+    let write_error = |e: io::Error| format!("Unable to write {}: {}", path.display(), e);
+
+    fs::create_dir_all(path.parent().unwrap()).map_err(write_error)?;
+    fs::write(&path, format!("{}\n", session.trim())).map_err(write_error)?;
+
+    // The cookie logs into the account, keep it to ourselves
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).map_err(write_error)?;
+    }
+
+    Ok(path)
+}
+
+// Which way a wrong answer was off, when the site says so
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Hint {
+    High,
+    Low,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong(Option<Hint>),
+    // Another answer was accepted for this part already
+    Solved(String),
+    // Not the open part: solved without us knowing the answer, or part 1 is still open
+    WrongLevel,
+    // Rate limited, answers are refused for that many more seconds
+    Wait(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub verdict: Verdict,
+    // False when the verdict follows from earlier ones and nothing was sent
+    pub submitted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Wrong {
+    answer: String,
+    hint: Option<Hint>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PartHistory {
+    #[serde(default)]
+    right: Option<String>,
+    #[serde(default)]
+    wrong: Vec<Wrong>,
+}
+
+impl PartHistory {
+    // What earlier verdicts already say about `answer`
+    fn verdict(&self, answer: &str) -> Option<Verdict> {
+        if let Some(right) = &self.right {
+            return Some(if right == answer {
+                Verdict::Right
+            } else {
+                Verdict::Solved(right.clone())
+            });
+        }
+
+        if let Some(wrong) = self.wrong.iter().find(|wrong| wrong.answer == answer) {
+            return Some(Verdict::Wrong(wrong.hint));
+        }
+
+        // An answer past one that was too high is too high as well
+        let answer: i128 = answer.parse().ok()?;
+        self.wrong.iter().find_map(|wrong| {
+            let bound: i128 = wrong.answer.parse().ok()?;
+            match wrong.hint? {
+                Hint::High if answer >= bound => Some(Verdict::Wrong(Some(Hint::High))),
+                Hint::Low if answer <= bound => Some(Verdict::Wrong(Some(Hint::Low))),
+                _ => None,
+            }
+        })
+    }
+}
+
+// Everything the site told us about our answers, in inputs/<year>/submissions.json
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct History {
+    // Unix time until which the site refuses answers
+    #[serde(default)]
+    wait_until: u64,
+    // Keyed by "<day>-<part>"
+    #[serde(default)]
+    parts: BTreeMap<String, PartHistory>,
+}
+
+pub struct Site {
+    url: String,
+    session: String,
+    workspace: PathBuf,
+    agent: ureq::Agent,
+}
+
+impl Site {
+    // `url` is the site's root, a local stand-in in tests
+    pub fn new(url: &str, session: &str, workspace: &Path) -> Site {
+        Site {
+            url: url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            workspace: workspace.to_path_buf(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(TIMEOUT)
+                .build(),
+        }
+    }
+
+    fn day_url(&self, day: u32, page: &str) -> String {
+        format!("{}/{}/day/{}/{}", self.url, YEAR, day, page)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    // Returns the path of the input and whether it was downloaded, a cached
+    // input is never downloaded again
+    pub fn fetch(&self, day: u32) -> Result<(PathBuf, bool), String> {
+        let path = parser::input_path(&self.workspace, day);
+        if path.exists() {
+            return Ok((path, false));
+        }
+
+        let input = self
+            .agent
+            .get(&self.day_url(day, "input"))
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| http_error(day, e))?
+            .into_string()
+            .map_err(|e| format!("Unable to read the input of day {}: {}", day, e))?;
+
+        let write_error = |e: io::Error| format!("Unable to write {}: {}", path.display(), e);
+        fs::create_dir_all(path.parent().unwrap()).map_err(write_error)?;
+        fs::write(&path, input).map_err(write_error)?;
+
+        Ok((path, true))
+    }
+
+    fn history_path(&self) -> PathBuf {
+        self.workspace
+            .join("inputs")
+            .join(YEAR.to_string())
+            .join("submissions.json")
+    }
+
+    fn read_history(&self) -> Result<History, String> {
+        let path = self.history_path();
+        match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| format!("Unable to read {}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(format!("Unable to read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn write_history(&self, history: &History) -> Result<(), String> {
+        let path = self.history_path();
+        let write_error = |e: io::Error| format!("Unable to write {}: {}", path.display(), e);
+
+        fs::create_dir_all(path.parent().unwrap()).map_err(write_error)?;
+        fs::write(&path, serde_json::to_string_pretty(history).unwrap()).map_err(write_error)
+    }
+
+    // `now` is the Unix time, answers the earlier verdicts settle are not sent
+    pub fn submit(&self, day: u32, part: u8, answer: &str, now: u64) -> Result<Outcome, String> {
+        let mut history = self.read_history()?;
+        let key = format!("{}-{}", day, part);
+
+        if let Some(verdict) = history.parts.get(&key).and_then(|p| p.verdict(answer)) {
+            return Ok(Outcome {
+                verdict,
+                submitted: false,
+            });
+        }
+        if now < history.wait_until {
+            return Ok(Outcome {
+                verdict: Verdict::Wait(history.wait_until - now),
+                submitted: false,
+            });
+        }
+
+        let page = self
+            .agent
+            .post(&self.day_url(day, "answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| http_error(day, e))?
+            .into_string()
+            .map_err(|e| format!("Unable to read the verdict: {}", e))?;
+
+        let (verdict, wait) = read_verdict(&article_text(&page))?;
+
+        let entry = history.parts.entry(key).or_default();
+        match &verdict {
+            Verdict::Right => entry.right = Some(answer.to_string()),
+            &Verdict::Wrong(hint) => entry.wrong.push(Wrong {
+                answer: answer.to_string(),
+                hint,
+            }),
+            Verdict::Solved(_) | Verdict::WrongLevel | Verdict::Wait(_) => {}
+        }
+        history.wait_until = now + wait;
+        self.write_history(&history)?;
+
+        Ok(Outcome {
+            verdict,
+            submitted: true,
+        })
+    }
+}
+
+fn http_error(day: u32, e: ureq::Error) -> String {
+    match e {
+        ureq::Error::Status(404, _) => format!("Day {} is not unlocked yet", day),
+        ureq::Error::Status(400 | 500, _) => {
+            "The site rejected the session cookie, log in again and store the new one".to_string()
+        }
+        ureq::Error::Status(code, _) => format!("The site answered with status {}", code),
+        ureq::Error::Transport(e) => format!("Unable to reach the site: {}", e),
+    }
+}
+
+// The text of the page's <article>, where the site puts the verdict
+fn article_text(page: &str) -> String {
+    let start = page.find("<article").unwrap_or(0);
+    let end = page[start..]
+        .find("</article>")
+        .map_or(page.len(), |end| start + end);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// The verdict and how many seconds to wait before the next answer
+fn read_verdict(text: &str) -> Result<(Verdict, u64), String> {
+    if text.contains("That's the right answer") {
+        Ok((Verdict::Right, 0))
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::High)
+        } else if text.contains("too low") {
+            Some(Hint::Low)
+        } else {
+            None
+        };
+        // "Please wait one minute before trying again", longer after repeated mistakes
+        let wait = match between(text, "wait ", " before trying again") {
+            Some("one minute") => 60,
+            Some(delay) => delay_seconds(delay.trim_end_matches(" minutes"))
+                .map(|minutes| minutes * 60)
+                .unwrap_or(60),
+            None => 60,
+        };
+        Ok((Verdict::Wrong(hint), wait))
+    } else if text.contains("You gave an answer too recently") {
+        // "You have 1m 5s left to wait"
+        let wait = between(text, "You have ", " left to wait")
+            .and_then(delay_seconds)
+            .unwrap_or(60);
+        Ok((Verdict::Wait(wait), wait))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Ok((Verdict::WrongLevel, 0))
+    } else {
+        Err(format!("Unexpected answer from the site: {}", text))
+    }
+}
+
+fn between<'a>(text: &'a str, before: &str, after: &str) -> Option<&'a str> {
+    let start = text.find(before)? + before.len();
+    let len = text[start..].find(after)?;
+    Some(&text[start..start + len])
+}
+
+// "1m 5s", "42s" or a bare number of the unit the caller expects
+fn delay_seconds(delay: &str) -> Option<u64> {
+    delay.split_whitespace().try_fold(0, |total, part| {
+        let (n, unit) = match part.strip_suffix(['h', 'm', 's']) {
+            Some(n) => (n, part.chars().last().unwrap()),
+            None => (part, 's'),
+        };
+        let n: u64 = n.parse().ok()?;
+        Some(
+            total
+                + match unit {
+                    'h' => n * 3600,
+                    'm' => n * 60,
+                    _ => n,
+                },
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    const SESSION: &str = "53616c7465645f5f";
+    const START: u64 = 1_733_000_000;
+
+    #[derive(Debug, Clone)]
+    struct Request {
+        method: String,
+        path: String,
+        cookie: String,
+        body: String,
+    }
+
+    // Stands in for the site on a local port, answering each request with the
+    // status and page `respond` gives. Returns the root URL and the requests so far
+    fn stand_in(
+        respond: impl Fn(&Request) -> (u16, String) + Send + 'static,
+    ) -> (String, Arc<Mutex<Vec<Request>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut words = line.split_whitespace();
+                let (method, path) = (words.next().unwrap(), words.next().unwrap());
+
+                let (mut cookie, mut length) = (String::new(), 0);
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let Some((name, value)) = header.trim_end().split_once(": ") else {
+                        break;
+                    };
+                    match name.to_ascii_lowercase().as_str() {
+                        "cookie" => cookie = value.to_string(),
+                        "content-length" => length = value.parse().unwrap(),
+                        _ => {}
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                let request = Request {
+                    method: method.to_string(),
+                    path: path.to_string(),
+                    cookie,
+                    body: String::from_utf8(body).unwrap(),
+                };
+                let (status, page) = respond(&request);
+                seen.lock().unwrap().push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    page.len(),
+                    page
+                )
+                .unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn page(text: &str) -> String {
+        format!(
+            "<html><main><article><p>{}</p></article></main></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn inputs_are_downloaded_once() {
+        let (url, requests) = stand_in(|request| {
+            if request.cookie == format!("session={}", SESSION) {
+                (200, "1 2\n3 4\n".to_string())
+            } else {
+                (
+                    400,
+                    "Puzzle inputs differ by user. Please log in to get your puzzle input."
+                        .to_string(),
+                )
+            }
+        });
+        let workspace = tempfile::tempdir().unwrap();
+
+        let site = Site::new(&url, SESSION, workspace.path());
+        let path = workspace.path().join("inputs/2024/3.txt");
+        assert_eq!(site.fetch(3), Ok((path.clone(), true)));
+        assert_eq!(site.fetch(3), Ok((path.clone(), false)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            (requests[0].method.as_str(), requests[0].path.as_str()),
+            ("GET", "/2024/day/3/input")
+        );
+
+        let site = Site::new(&url, "expired", workspace.path());
+        assert_eq!(
+            site.fetch(4),
+            Err("The site rejected the session cookie, log in again and store the new one".into())
+        );
+        assert!(!workspace.path().join("inputs/2024/4.txt").exists());
+    }
+
+    #[test]
+    fn verdicts_are_remembered() {
+        let (url, requests) = stand_in(|request| {
+            let text = match request.body.as_str() {
+                "level=1&answer=42" => "That's the right answer! You are one gold star closer.",
+                _ => {
+                    "That's not the right answer; your answer is too high. \
+                     Please wait one minute before trying again."
+                }
+            };
+            (200, page(text))
+        });
+        let workspace = tempfile::tempdir().unwrap();
+        let site = Site::new(&url, SESSION, workspace.path());
+
+        let submit = |answer: &str, at: u64| {
+            let outcome = site.submit(7, 1, answer, START + at).unwrap();
+            (outcome.verdict, outcome.submitted)
+        };
+
+        let too_high = Verdict::Wrong(Some(Hint::High));
+        assert_eq!(submit("100", 0), (too_high.clone(), true));
+        // Known wrong, or past an answer that was too high
+        assert_eq!(submit("100", 10), (too_high.clone(), false));
+        assert_eq!(submit("150", 10), (too_high.clone(), false));
+        assert_eq!(submit("50", 10), (Verdict::Wait(50), false));
+        assert_eq!(submit("42", 60), (Verdict::Right, true));
+        assert_eq!(submit("42", 61), (Verdict::Right, false));
+        assert_eq!(submit("43", 61), (Verdict::Solved("42".into()), false));
+
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].path, "/2024/day/7/answer");
+        assert_eq!(requests[1].cookie, format!("session={}", SESSION));
+
+        // Another run picks up the same history
+        let site = Site::new(&url, SESSION, workspace.path());
+        assert!(!site.submit(7, 1, "42", START).unwrap().submitted);
+    }
+
+    #[test]
+    fn rate_limits_are_respected() {
+        let (url, requests) = stand_in(|_| {
+            let text = "You gave an answer too recently; you have to wait after submitting an \
+                        answer before trying again. You have 1m 5s left to wait.";
+            (200, page(text))
+        });
+        let workspace = tempfile::tempdir().unwrap();
+        let site = Site::new(&url, SESSION, workspace.path());
+
+        let outcome = site.submit(2, 2, "7", START).unwrap();
+        assert_eq!(
+            (outcome.verdict, outcome.submitted),
+            (Verdict::Wait(65), true)
+        );
+        let outcome = site.submit(2, 2, "8", START + 30).unwrap();
+        assert_eq!(
+            (outcome.verdict, outcome.submitted),
+            (Verdict::Wait(35), false)
+        );
+        site.submit(2, 2, "8", START + 65).unwrap();
+
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn verdict_pages_are_read() {
+        let read = |html: &str| read_verdict(&article_text(html));
+
+        assert_eq!(
+            read(&page("That's not the right answer. If you're stuck, ... Please wait <em>5 minutes</em> before trying again.")),
+            Ok((Verdict::Wrong(None), 300))
+        );
+        assert_eq!(
+            read(&page("That's not the right answer; your answer is too low. Please wait one minute before trying again.")),
+            Ok((Verdict::Wrong(Some(Hint::Low)), 60))
+        );
+        // A delay that cannot be read waits the usual minute
+        assert_eq!(
+            read(&page("That's not the right answer. Please wait <em>a few minutes</em> before trying again.")),
+            Ok((Verdict::Wrong(None), 60))
+        );
+        assert_eq!(
+            read(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Ok((Verdict::WrongLevel, 0))
+        );
+        assert!(read("<html>Log in</html>").is_err());
+    }
+}
@@ -0,0 +1,892 @@
+use crate::params::{Param, Params};
+use parser::ParseError;
+use std::collections::HashSet;
+use std::fmt::Display;
+use trace::Trace;
+
+// Extra named values about an answer, e.g. the sequence behind a best price
+pub type Diagnostics = Vec<(&'static str, String)>;
+
+pub trait Solution {
+    type Input;
+
+    // Puzzle parameters the day takes with --param, and the values its
+    // examples need where they differ from the official ones
+    const PARAMS: &'static [Param] = &[];
+    const EXAMPLE_PARAMS: &'static [(&'static str, i64)] = &[];
+
+    // Days whose parameters constrain each other check them here
+    fn check_params(_: &Params) -> Result<(), String> {
+        Ok(())
+    }
+
+    // Days with parameters keep them alongside the parsed input
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> String;
+    fn part2(input: &Self::Input) -> String;
+
+    // Days with more to report than the answer override these
+    fn part1_detailed(input: &Self::Input) -> (String, Diagnostics) {
+        (Self::part1(input), Vec::new())
+    }
+
+    fn part2_detailed(input: &Self::Input) -> (String, Diagnostics) {
+        (Self::part2(input), Vec::new())
+    }
+
+    // Days that can tell how they reached the answer override these, for --explain
+    fn part1_explained(input: &Self::Input, _: &mut dyn Trace) -> (String, Diagnostics) {
+        Self::part1_detailed(input)
+    }
+
+    fn part2_explained(input: &Self::Input, _: &mut dyn Trace) -> (String, Diagnostics) {
+        Self::part2_detailed(input)
+    }
+}
+
+// Days with checked arithmetic answer "overflow" instead of a wrong number,
+// with what overflowed as a diagnostic
+fn checked<T: Display, E: Display>(result: Result<T, E>) -> (String, Diagnostics) {
+    match result {
+        Ok(answer) => (answer.to_string(), Vec::new()),
+        Err(e) => ("overflow".to_string(), vec![("error", e.to_string())]),
+    }
+}
+
+// Lengths of the button sequences of day 21 grow about 2.5 times with every
+// robot, this many keeps five codes well within 64 bits
+const ROBOTS_MAX: i64 = 35;
+
+pub struct Day1;
+pub struct Day2;
+pub struct Day3;
+pub struct Day4;
+pub struct Day5;
+pub struct Day6;
+pub struct Day7;
+pub struct Day8;
+pub struct Day9;
+pub struct Day10;
+pub struct Day11;
+pub struct Day12;
+pub struct Day13;
+pub struct Day14;
+pub struct Day15;
+pub struct Day16;
+pub struct Day17;
+pub struct Day18;
+pub struct Day19;
+pub struct Day20;
+pub struct Day21;
+pub struct Day22;
+pub struct Day23;
+pub struct Day24;
+pub struct Day25;
+
+impl Solution for Day1 {
+    type Input = (Vec<u64>, Vec<u64>);
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day1::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        Self::part1_detailed(input).0
+    }
+
+    fn part2((first_arr, second_arr): &Self::Input) -> String {
+        day1::solve_part2(first_arr, second_arr).to_string()
+    }
+
+    fn part1_detailed((first_arr, second_arr): &Self::Input) -> (String, Diagnostics) {
+        match day1::solve_part1(first_arr, second_arr) {
+            Ok(distance) => (distance.to_string(), Vec::new()),
+            Err(e) => ("none".to_string(), vec![("error", e.to_string())]),
+        }
+    }
+}
+
+// The safety rules of day 2 parts 1 and 2 from its parameters
+fn safety_rules(params: &Params) -> (day2::rule::SafetyRule, day2::rule::SafetyRule) {
+    day2::rules(|name| params.get(name)).expect("the parameters were checked")
+}
+
+impl Solution for Day2 {
+    type Input = (Vec<Vec<u64>>, Params);
+
+    // Named and set as the day binary takes them
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: day2::PARAMS[0].0,
+            default: day2::PARAMS[0].1,
+            range: 0..=1000000,
+        },
+        Param {
+            name: day2::PARAMS[1].0,
+            default: day2::PARAMS[1].1,
+            range: 0..=1000000,
+        },
+        Param {
+            name: day2::PARAMS[2].0,
+            default: day2::PARAMS[2].1,
+            range: -1..=1,
+        },
+        Param {
+            name: day2::PARAMS[3].0,
+            default: day2::PARAMS[3].1,
+            range: 0..=1000000,
+        },
+        Param {
+            name: day2::PARAMS[4].0,
+            default: day2::PARAMS[4].1,
+            range: 0..=1000000,
+        },
+    ];
+
+    fn check_params(params: &Params) -> Result<(), String> {
+        day2::rules(|name| params.get(name)).map(|_| ())
+    }
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((day2::parse(input)?, params.clone()))
+    }
+
+    fn part1((reports, params): &Self::Input) -> String {
+        day2::count_safe_by(reports, &safety_rules(params).0).to_string()
+    }
+
+    fn part2((reports, params): &Self::Input) -> String {
+        day2::count_safe_by(reports, &safety_rules(params).1).to_string()
+    }
+
+    fn part2_explained(
+        (reports, params): &Self::Input,
+        trace: &mut dyn Trace,
+    ) -> (String, Diagnostics) {
+        let (_, rule) = safety_rules(params);
+        let safe = day2::count_safe_traced(reports, &rule, trace);
+        (safe.to_string(), Vec::new())
+    }
+}
+
+impl Solution for Day3 {
+    type Input = String;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day3::parse(input)
+    }
+
+    fn part1(instruction: &Self::Input) -> String {
+        day3::solve(instruction, false).to_string()
+    }
+
+    fn part2(instruction: &Self::Input) -> String {
+        day3::solve(instruction, true).to_string()
+    }
+}
+
+impl Solution for Day4 {
+    type Input = day4::Matrix;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day4::parse(input)
+    }
+
+    fn part1(matrix: &Self::Input) -> String {
+        matrix.find_all(day4::AlignmentType::Xmas).to_string()
+    }
+
+    fn part2(matrix: &Self::Input) -> String {
+        matrix.find_all(day4::AlignmentType::CrossMas).to_string()
+    }
+}
+
+impl Solution for Day5 {
+    type Input = (Box<day5::Prerequisites>, Vec<Vec<usize>>);
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day5::parse(input)
+    }
+
+    fn part1((prereq, updates): &Self::Input) -> String {
+        day5::solve(prereq, updates).0.to_string()
+    }
+
+    fn part2((prereq, updates): &Self::Input) -> String {
+        day5::solve(prereq, updates).1.to_string()
+    }
+
+    fn part1_explained(
+        (prereq, updates): &Self::Input,
+        trace: &mut dyn Trace,
+    ) -> (String, Diagnostics) {
+        let sums = day5::solve_traced(prereq, updates, trace);
+        (sums.0.to_string(), Vec::new())
+    }
+}
+
+impl Solution for Day6 {
+    type Input = grid::Grid<u8>;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day6::parse(input)
+    }
+
+    fn part1(map: &Self::Input) -> String {
+        day6::walk(&mut map.clone()).unwrap().to_string()
+    }
+
+    fn part2(map: &Self::Input) -> String {
+        day6::find_all_obstacles(map).to_string()
+    }
+}
+
+impl Solution for Day7 {
+    type Input = Vec<(u64, Vec<u64>)>;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day7::parse(input)
+    }
+
+    fn part1(equations: &Self::Input) -> String {
+        Self::part1_detailed(equations).0
+    }
+
+    fn part2(equations: &Self::Input) -> String {
+        Self::part2_detailed(equations).0
+    }
+
+    fn part1_detailed(equations: &Self::Input) -> (String, Diagnostics) {
+        checked(day7::calibration_sum(equations, false))
+    }
+
+    fn part2_detailed(equations: &Self::Input) -> (String, Diagnostics) {
+        checked(day7::calibration_sum(equations, true))
+    }
+
+    fn part1_explained(equations: &Self::Input, trace: &mut dyn Trace) -> (String, Diagnostics) {
+        checked(day7::calibration_sum_traced(equations, false, trace))
+    }
+
+    fn part2_explained(equations: &Self::Input, trace: &mut dyn Trace) -> (String, Diagnostics) {
+        checked(day7::calibration_sum_traced(equations, true, trace))
+    }
+}
+
+impl Solution for Day8 {
+    type Input = day8::AntennaMap;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day8::parse(input)
+    }
+
+    fn part1(map: &Self::Input) -> String {
+        map.find_all_antinodes(false).to_string()
+    }
+
+    fn part2(map: &Self::Input) -> String {
+        map.find_all_antinodes(true).to_string()
+    }
+}
+
+impl Solution for Day9 {
+    type Input = String;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day9::parse(input)
+    }
+
+    fn part1(disk_map: &Self::Input) -> String {
+        Self::part1_detailed(disk_map).0
+    }
+
+    fn part2(disk_map: &Self::Input) -> String {
+        Self::part2_detailed(disk_map).0
+    }
+
+    fn part1_detailed(disk_map: &Self::Input) -> (String, Diagnostics) {
+        checked(day9::rearranged_checksum(
+            &mut day9::expand(disk_map),
+            false,
+        ))
+    }
+
+    fn part2_detailed(disk_map: &Self::Input) -> (String, Diagnostics) {
+        checked(day9::rearranged_checksum(&mut day9::expand(disk_map), true))
+    }
+}
+
+impl Solution for Day10 {
+    type Input = grid::Grid<u8>;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day10::parse(input)
+    }
+
+    fn part1(map: &Self::Input) -> String {
+        day10::calculate_score(map).0.to_string()
+    }
+
+    fn part2(map: &Self::Input) -> String {
+        day10::calculate_score(map).1.to_string()
+    }
+}
+
+impl Solution for Day11 {
+    type Input = (Vec<u64>, Params);
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "blinks1",
+            default: day11::PART1_BLINKS as i64,
+            range: 0..=1000,
+        },
+        Param {
+            name: "blinks2",
+            default: day11::PART2_BLINKS as i64,
+            range: 0..=1000,
+        },
+    ];
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((day11::parse(input)?, params.clone()))
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        Self::part1_detailed(input).0
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        Self::part2_detailed(input).0
+    }
+
+    fn part1_detailed((stones, params): &Self::Input) -> (String, Diagnostics) {
+        checked(day11::solve(stones, params.get("blinks1")))
+    }
+
+    fn part2_detailed((stones, params): &Self::Input) -> (String, Diagnostics) {
+        checked(day11::solve(stones, params.get("blinks2")))
+    }
+}
+
+impl Solution for Day12 {
+    type Input = day12::GardenMap<u8>;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day12::parse(input)
+    }
+
+    fn part1(map: &Self::Input) -> String {
+        day12::solve(map, false).to_string()
+    }
+
+    fn part2(map: &Self::Input) -> String {
+        day12::solve(map, true).to_string()
+    }
+}
+
+impl Solution for Day13 {
+    type Input = (Vec<day13::Query>, Params);
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "offset",
+            default: day13::PRIZE_OFFSET,
+            range: 0..=i64::MAX,
+        },
+        Param {
+            name: "a_tokens",
+            default: day13::BUTTON_TOKENS.0,
+            range: 0..=i64::MAX,
+        },
+        Param {
+            name: "b_tokens",
+            default: day13::BUTTON_TOKENS.1,
+            range: 0..=i64::MAX,
+        },
+    ];
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((day13::parse(input)?, params.clone()))
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        Self::part1_detailed(input).0
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        Self::part2_detailed(input).0
+    }
+
+    fn part1_detailed(input: &Self::Input) -> (String, Diagnostics) {
+        Self::part1_explained(input, &mut trace::NoTrace)
+    }
+
+    fn part2_detailed(input: &Self::Input) -> (String, Diagnostics) {
+        Self::part2_explained(input, &mut trace::NoTrace)
+    }
+
+    fn part1_explained(
+        (queries, params): &Self::Input,
+        trace: &mut dyn Trace,
+    ) -> (String, Diagnostics) {
+        day13_detailed(queries, 0, params, trace)
+    }
+
+    fn part2_explained(
+        (queries, params): &Self::Input,
+        trace: &mut dyn Trace,
+    ) -> (String, Diagnostics) {
+        day13_detailed(queries, params.get("offset"), params, trace)
+    }
+}
+
+fn day13_detailed(
+    queries: &[day13::Query],
+    prize_offset: i64,
+    params: &Params,
+    trace: &mut dyn Trace,
+) -> (String, Diagnostics) {
+    let tokens = (params.get("a_tokens"), params.get("b_tokens"));
+    let (answer, mut diagnostics) = checked(day13::min_tokens_traced(
+        queries,
+        prize_offset,
+        tokens,
+        trace,
+    ));
+
+    let won = queries
+        .iter()
+        .filter(|q| matches!(day13::solve(q, prize_offset, tokens), Ok(Some(_))))
+        .count();
+    diagnostics.push(("prizes_won", won.to_string()));
+
+    (answer, diagnostics)
+}
+
+impl Solution for Day14 {
+    type Input = (Vec<day14::Robot>, Params);
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "width",
+            default: day14::BOUNDARY.0,
+            range: 1..=10000,
+        },
+        Param {
+            name: "height",
+            default: day14::BOUNDARY.1,
+            range: 1..=10000,
+        },
+        Param {
+            name: "seconds",
+            default: day14::SECONDS,
+            range: 0..=1000000000,
+        },
+    ];
+    const EXAMPLE_PARAMS: &'static [(&'static str, i64)] = &[("width", 11), ("height", 7)];
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((day14::parse(input)?, params.clone()))
+    }
+
+    fn part1((robots, params): &Self::Input) -> String {
+        let boundary = day14_boundary(params);
+        let mut robots = robots.clone();
+        for robot in &mut robots {
+            robot.run(params.get("seconds"), boundary);
+        }
+
+        day14::calculate_safety_factor(&robots, boundary).to_string()
+    }
+
+    fn part2((robots, params): &Self::Input) -> String {
+        day14::find_easter_egg(robots, day14_boundary(params))
+            .map_or("none".to_string(), |t| t.to_string())
+    }
+}
+
+pub fn day14_boundary(params: &Params) -> (i64, i64) {
+    (params.get("width"), params.get("height"))
+}
+
+impl Solution for Day15 {
+    type Input = (day15::NormalMap, Vec<day15::Move>);
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day15::parse(input)
+    }
+
+    fn part1((map, moves): &Self::Input) -> String {
+        day15::simulate_all(&mut map.clone(), moves).to_string()
+    }
+
+    fn part2((map, moves): &Self::Input) -> String {
+        let mut dmap = day15::DoubleWideMap::from_normal_map(map);
+        day15::simulate_all(&mut dmap, moves).to_string()
+    }
+}
+
+impl Solution for Day16 {
+    type Input = (grid::Grid<u8>, Params);
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "step_cost",
+            default: day16::COSTS.step as i64,
+            range: 1..=1000000,
+        },
+        Param {
+            name: "turn_cost",
+            default: day16::COSTS.turn as i64,
+            range: 0..=1000000,
+        },
+    ];
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((day16::parse(input)?, params.clone()))
+    }
+
+    fn part1((map, params): &Self::Input) -> String {
+        day16::bfs(map, day16::start_position(map), day16_costs(params))
+            .map_or("none".to_string(), |(score, _)| score.to_string())
+    }
+
+    fn part2((map, params): &Self::Input) -> String {
+        day16::bfs(map, day16::start_position(map), day16_costs(params))
+            .map_or("none".to_string(), |(_, n_tiles)| n_tiles.to_string())
+    }
+}
+
+pub fn day16_costs(params: &Params) -> day16::Costs {
+    day16::Costs {
+        step: params.get("step_cost"),
+        turn: params.get("turn_cost"),
+    }
+}
+
+impl Solution for Day17 {
+    type Input = day17::Device;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day17::parse(input)
+    }
+
+    fn part1(device: &Self::Input) -> String {
+        let mut computer = day17::Computer::new(
+            device.reg_a,
+            device.reg_b,
+            device.reg_c,
+            device.program.clone(),
+        );
+        let outputs: Vec<String> = computer
+            .execute()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect();
+
+        outputs.join(",")
+    }
+
+    fn part2(device: &Self::Input) -> String {
+        day17::find_valid_reg_a(&device.program, 0, 0).map_or("none".to_string(), |a| a.to_string())
+    }
+}
+
+impl Solution for Day18 {
+    type Input = (Vec<(usize, usize)>, Params);
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "width",
+            default: day18::MAP_SIZE.1 as i64,
+            range: 1..=10000,
+        },
+        Param {
+            name: "height",
+            default: day18::MAP_SIZE.0 as i64,
+            range: 1..=10000,
+        },
+        Param {
+            name: "bytes",
+            default: day18::FIRST_KILOBYTE as i64,
+            range: 0..=i64::MAX,
+        },
+    ];
+    const EXAMPLE_PARAMS: &'static [(&'static str, i64)] =
+        &[("width", 7), ("height", 7), ("bytes", 12)];
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((day18::parse(input, day18_size(params))?, params.clone()))
+    }
+
+    fn part1((coords, params): &Self::Input) -> String {
+        let bytes: usize = params.get("bytes");
+        day18::simulate(&coords[..bytes.min(coords.len())], day18_size(params))
+            .map_or("none".to_string(), |d| d.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        Self::part2_detailed(input).0
+    }
+
+    fn part2_detailed((coords, params): &Self::Input) -> (String, Diagnostics) {
+        let size = day18_size(params);
+        match day18::find_first_blocking_coord(coords, size, params.get("bytes")) {
+            Some(i) => (
+                format!("{},{}", coords[i].0, coords[i].1),
+                vec![("byte_index", i.to_string())],
+            ),
+            None => ("none".to_string(), Vec::new()),
+        }
+    }
+}
+
+// (rows, columns) as the day18 functions take it
+pub fn day18_size(params: &Params) -> (usize, usize) {
+    (params.get("height"), params.get("width"))
+}
+
+impl Solution for Day19 {
+    type Input = (Vec<String>, Vec<String>);
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day19::parse(input)
+    }
+
+    fn part1((patterns, queries): &Self::Input) -> String {
+        day19::count_all(patterns, queries)
+            .into_iter()
+            .filter(|&ways| ways > 0)
+            .count()
+            .to_string()
+    }
+
+    fn part2((patterns, queries): &Self::Input) -> String {
+        day19::count_all(patterns, queries)
+            .into_iter()
+            .sum::<u64>()
+            .to_string()
+    }
+}
+
+impl Solution for Day20 {
+    type Input = (grid::Grid<u8>, Params);
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "threshold",
+            default: day20::PICOSEC_THRESHOLD as i64,
+            range: 0..=1000000,
+        },
+        Param {
+            name: "cheat1",
+            default: day20::PART1_LIMIT as i64,
+            range: 0..=1000000,
+        },
+        Param {
+            name: "cheat2",
+            default: day20::PART2_LIMIT as i64,
+            range: 0..=1000000,
+        },
+    ];
+    // The example track saves 64 picoseconds at most, gives 1 and 285
+    const EXAMPLE_PARAMS: &'static [(&'static str, i64)] = &[("threshold", 50)];
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((day20::parse(input)?, params.clone()))
+    }
+
+    fn part1((map, params): &Self::Input) -> String {
+        day20::solve(map, params.get("threshold"), params.get("cheat1")).to_string()
+    }
+
+    fn part2((map, params): &Self::Input) -> String {
+        day20::solve(map, params.get("threshold"), params.get("cheat2")).to_string()
+    }
+}
+
+impl Solution for Day21 {
+    type Input = (Vec<String>, Params);
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "robots1",
+            default: day21::PART1_DEPTH as i64,
+            range: 0..=ROBOTS_MAX,
+        },
+        Param {
+            name: "robots2",
+            default: day21::PART2_DEPTH as i64,
+            range: 0..=ROBOTS_MAX,
+        },
+    ];
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((day21::parse(input)?, params.clone()))
+    }
+
+    fn part1((codes, params): &Self::Input) -> String {
+        day21::solve(codes, params.get("robots1")).to_string()
+    }
+
+    fn part2((codes, params): &Self::Input) -> String {
+        day21::solve(codes, params.get("robots2")).to_string()
+    }
+}
+
+impl Solution for Day22 {
+    type Input = (Vec<u64>, Params);
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "secrets",
+        default: day22::SECRETS as i64,
+        range: 0..=1000000,
+    }];
+
+    fn parse(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((day22::parse(input)?, params.clone()))
+    }
+
+    fn part1((initial_seeds, params): &Self::Input) -> String {
+        day22::simulate(initial_seeds, params.get("secrets"))
+            .0
+            .to_string()
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        Self::part2_detailed(input).0
+    }
+
+    fn part2_detailed((initial_seeds, params): &Self::Input) -> (String, Diagnostics) {
+        let (_, price_map) = day22::simulate(initial_seeds, params.get("secrets"));
+        let (best_price, best_sequence) = day22::solve(initial_seeds, &price_map);
+
+        let diagnostics = best_sequence
+            .first()
+            .map(|(a, b, c, d)| vec![("sequence", format!("{},{},{},{}", a, b, c, d))])
+            .unwrap_or_default();
+
+        (best_price.to_string(), diagnostics)
+    }
+}
+
+impl Solution for Day23 {
+    type Input = day23::Network;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day23::parse(input)
+    }
+
+    fn part1(network: &Self::Input) -> String {
+        let computer_vec: Vec<String> = network.computers.iter().cloned().collect();
+        day23::part1(&computer_vec, &network.computer_map).to_string()
+    }
+
+    fn part2(network: &Self::Input) -> String {
+        let max_clique: HashSet<String> = day23::max_clique(&network.adj_list, &network.computers);
+        day23::password(max_clique)
+    }
+
+    fn part2_explained(network: &Self::Input, trace: &mut dyn Trace) -> (String, Diagnostics) {
+        let max_clique = day23::max_clique_traced(&network.adj_list, &network.computers, trace);
+        (day23::password(max_clique), Vec::new())
+    }
+}
+
+impl Solution for Day24 {
+    type Input = day24::Circuit;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day24::parse(input)
+    }
+
+    fn part1(circuit: &Self::Input) -> String {
+        circuit.output().to_string()
+    }
+
+    fn part2(circuit: &Self::Input) -> String {
+        Self::part2_detailed(circuit).0
+    }
+
+    fn part2_detailed(circuit: &Self::Input) -> (String, Diagnostics) {
+        match day24::swapped_pairs(circuit) {
+            Some(pairs) => {
+                let swaps: Vec<String> =
+                    pairs.iter().map(|(a, b)| format!("{}-{}", a, b)).collect();
+                (day24::answer(&pairs), vec![("pairs", swaps.join(","))])
+            }
+            None => (
+                "none".to_string(),
+                vec![(
+                    "error",
+                    "swapping outputs does not make the circuit an adder".to_string(),
+                )],
+            ),
+        }
+    }
+}
+
+impl Solution for Day25 {
+    type Input = day25::Schematics;
+
+    fn parse(input: &str, _: &Params) -> Result<Self::Input, ParseError> {
+        day25::parse(input)
+    }
+
+    fn part1(schematics: &Self::Input) -> String {
+        day25::count_fits(schematics).to_string()
+    }
+
+    // The last day has a single puzzle
+    fn part2(_: &Self::Input) -> String {
+        "-".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example<S: Solution>(day: u32) -> S::Input {
+        let path = format!("{}/../day{}/test.txt", env!("CARGO_MANIFEST_DIR"), day);
+        let input = std::fs::read_to_string(path).unwrap();
+        let params = Params::resolve(S::PARAMS, S::EXAMPLE_PARAMS, &[]).unwrap();
+        S::parse(&input, &params).unwrap()
+    }
+
+    #[test]
+    fn day2_rule_comes_from_params() {
+        let path = format!("{}/../day2/test.txt", env!("CARGO_MANIFEST_DIR"));
+        let input = std::fs::read_to_string(path).unwrap();
+        let given = [("direction", "1"), ("removals2", "2")]
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+        let params = Params::resolve(Day2::PARAMS, &[], &given).unwrap();
+        let reports = Day2::parse(&input, &params).unwrap();
+
+        // Only increasing reports, up to two levels removed in part 2
+        assert_eq!(
+            (Day2::part1(&reports), Day2::part2(&reports)),
+            ("1".into(), "3".into())
+        );
+
+        let given = [("min_step", "5"), ("max_step", "2")]
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+        let params = Params::resolve(Day2::PARAMS, &[], &given).unwrap();
+        assert!(Day2::check_params(&params).is_err());
+    }
+
+    #[test]
+    fn example_params_give_the_example_answers() {
+        let input = example::<Day18>(18);
+        assert_eq!(
+            (Day18::part1(&input), Day18::part2(&input)),
+            ("22".into(), "6,1".into())
+        );
+        let input = example::<Day20>(20);
+        assert_eq!(
+            (Day20::part1(&input), Day20::part2(&input)),
+            ("1".into(), "285".into())
+        );
+    }
+}
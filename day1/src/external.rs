@@ -0,0 +1,416 @@
+//! Both answers for lists too large to hold in memory. The input is read line
+//! by line, each column is sorted in runs of a bounded length that are written
+//! to temporary files, and the runs are merged back in order, at most
+//! [`FAN_IN`] at a time. Both answers come from one pass joining the sorted
+//! columns on equal IDs, so memory stays bounded whatever the input size.
+//! The lists must have the same length: pairing lists of different lengths
+//! leaves out the IDs that cost least, which takes them all in memory
+
+use crate::{line_ids, two_lists};
+use parser::{Line, ParseError};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::Path;
+
+/// IDs of each column sorted in memory at a time, 8 MiB per column
+pub const RUN_LEN: usize = 1 << 20;
+
+/// Runs merged at once, more take passes merging them into longer runs
+pub const FAN_IN: usize = 64;
+
+// IDs read back from disk at a time, for each run
+const READ_LEN: usize = 8 * 1024;
+
+/// Why the lists could not be streamed
+#[derive(Debug)]
+pub enum Error {
+    /// Reading the input or the temporary files failed
+    Io(io::Error),
+    /// A line is not location IDs like [`parse`](crate::parse) takes
+    Parse(ParseError),
+    /// The lists have different lengths, this many IDs on the left and right
+    Unequal(u64, u64),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Parse(e) => write!(f, "line {}, column {}: {}", e.line, e.column, e),
+            Error::Unequal(left, right) => write!(
+                f,
+                "the left list has {} IDs and the right one {}, only lists of the same length can be streamed",
+                left, right
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error {
+        Error::Parse(e)
+    }
+}
+
+/// The answers of both parts, as `solve_part1` and `solve_part2` give them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    /// Total distance between the sorted lists
+    pub distance: u128,
+    /// Similarity score of the left list to the right one
+    pub similarity: u128,
+}
+
+/// Both answers for the lists read from `reader`, sorting `run_len` IDs of
+/// each column at a time into temporary files created in `dir`. The files
+/// are removed once done with, even on errors. Lines are read like
+/// [`parse`](crate::parse) reads them, but the lists must have the same length
+pub fn stream(reader: impl BufRead, run_len: usize, dir: &Path) -> Result<Totals, Error> {
+    let (left, right) = split_into_runs(reader, run_len.max(1), dir)?;
+    let (left, right) = (left.reduce(dir)?, right.reduce(dir)?);
+
+    // Paired in sorted order, the distance is the area between the counts of
+    // IDs at or below each value on both sides, as long as the lists have the
+    // same length, which reading them made sure of.
+    // Equal IDs are next to each other once sorted, so counting them on both
+    // sides is the histogram of the similarity score
+    let mut distance: u128 = 0;
+    let mut similarity: u128 = 0;
+    let (mut below_left, mut below_right): (u64, u64) = (0, 0);
+    let mut last: Option<u64> = None;
+
+    let (mut l, mut r) = (
+        Counts(left.merge(0..left.lens.len())?),
+        Counts(right.merge(0..right.lens.len())?),
+    );
+    let (mut a, mut b) = (l.next()?, r.next()?);
+    loop {
+        let id = match (a, b) {
+            (Some((x, _)), Some((y, _))) => x.min(y),
+            (Some((x, _)), None) | (None, Some((x, _))) => x,
+            (None, None) => break,
+        };
+        if let Some(last) = last {
+            distance += below_left.abs_diff(below_right) as u128 * (id - last) as u128;
+        }
+        last = Some(id);
+
+        let left_count = a.filter(|&(x, _)| x == id).map_or(0, |(_, count)| count);
+        let right_count = b.filter(|&(y, _)| y == id).map_or(0, |(_, count)| count);
+        let score = id as u128 * left_count as u128 * right_count as u128;
+        similarity = similarity.saturating_add(score);
+
+        if left_count > 0 {
+            below_left += left_count;
+            a = l.next()?;
+        }
+        if right_count > 0 {
+            below_right += right_count;
+            b = r.next()?;
+        }
+    }
+
+    Ok(Totals {
+        distance,
+        similarity,
+    })
+}
+
+// Reads the lines into sorted runs of both columns, which must end up the
+// same length. Blank lines may only end the input, like `parse` allows
+fn split_into_runs(
+    mut reader: impl BufRead,
+    run_len: usize,
+    dir: &Path,
+) -> Result<(Runs, Runs), Error> {
+    let mut left = Runs::new(dir)?;
+    let mut right = Runs::new(dir)?;
+    let mut left_run: Vec<u64> = Vec::with_capacity(run_len);
+    let mut right_run: Vec<u64> = Vec::with_capacity(run_len);
+
+    let mut text = String::new();
+    let mut number = 0;
+    let mut first_blank: Option<usize> = None;
+    let mut lens: (u64, u64) = (0, 0);
+    loop {
+        text.clear();
+        if reader.read_line(&mut text)? == 0 {
+            break;
+        }
+        number += 1;
+        let line = Line {
+            number,
+            text: text.trim_end_matches(['\n', '\r']),
+        };
+
+        if line.is_empty() {
+            first_blank.get_or_insert(number);
+            continue;
+        }
+        if let Some(number) = first_blank {
+            return Err(line_ids(&Line { number, text: "" }, 2).unwrap_err().into());
+        }
+        if number == 1 {
+            two_lists(&line)?;
+        }
+
+        let ids = line_ids(&line, 2)?;
+        if let Some(Some(id)) = ids.first() {
+            left_run.push(*id);
+            lens.0 += 1;
+        }
+        if let Some(Some(id)) = ids.get(1) {
+            right_run.push(*id);
+            lens.1 += 1;
+        }
+        if left_run.len() == run_len {
+            left.write(&mut left_run)?;
+        }
+        if right_run.len() == run_len {
+            right.write(&mut right_run)?;
+        }
+    }
+    if lens.0 != lens.1 {
+        return Err(Error::Unequal(lens.0, lens.1));
+    }
+    left.write(&mut left_run)?;
+    right.write(&mut right_run)?;
+
+    Ok((left, right))
+}
+
+// Sorted runs of one column, one after the other in a temporary file
+struct Runs {
+    file: File,
+    lens: Vec<u64>,
+}
+
+impl Runs {
+    fn new(dir: &Path) -> io::Result<Runs> {
+        Ok(Runs {
+            file: tempfile::tempfile_in(dir)?,
+            lens: Vec::new(),
+        })
+    }
+
+    // Sorts the run and appends it to the file, emptying it
+    fn write(&mut self, run: &mut Vec<u64>) -> io::Result<()> {
+        if run.is_empty() {
+            return Ok(());
+        }
+        run.sort_unstable();
+
+        let mut writer = BufWriter::new(&self.file);
+        for id in run.iter() {
+            writer.write_all(&id.to_le_bytes())?;
+        }
+        writer.flush()?;
+
+        self.lens.push(run.len() as u64);
+        run.clear();
+        Ok(())
+    }
+
+    // Merges the runs FAN_IN at a time into runs of a new file, until there
+    // are no more than FAN_IN left
+    fn reduce(mut self, dir: &Path) -> io::Result<Runs> {
+        while self.lens.len() > FAN_IN {
+            let mut merged = Runs::new(dir)?;
+            for start in (0..self.lens.len()).step_by(FAN_IN) {
+                let mut merge = self.merge(start..(start + FAN_IN).min(self.lens.len()))?;
+                let mut writer = BufWriter::new(&merged.file);
+                let mut len = 0;
+                while let Some(id) = merge.next()? {
+                    writer.write_all(&id.to_le_bytes())?;
+                    len += 1;
+                }
+                writer.flush()?;
+                merged.lens.push(len);
+            }
+            self = merged;
+        }
+
+        Ok(self)
+    }
+
+    fn merge(&self, runs: Range<usize>) -> io::Result<Merge<'_>> {
+        let mut readers: Vec<RunReader> = Vec::with_capacity(runs.len());
+        let mut start: u64 = self.lens[..runs.start].iter().sum();
+        for &len in &self.lens[runs] {
+            readers.push(RunReader {
+                file: &self.file,
+                offset: start * 8,
+                left: len,
+                buffer: Vec::new(),
+            });
+            start += len;
+        }
+
+        let mut heap: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
+        for (i, reader) in readers.iter_mut().enumerate() {
+            if let Some(id) = reader.next()? {
+                heap.push(Reverse((id, i)));
+            }
+        }
+
+        Ok(Merge { readers, heap })
+    }
+}
+
+// One run read back in order, a buffer at a time. Runs share the file, so
+// each seeks to where it left off before reading
+struct RunReader<'a> {
+    file: &'a File,
+    offset: u64,
+    left: u64,
+    buffer: Vec<u64>, // Reversed, the next ID last
+}
+
+impl RunReader<'_> {
+    fn next(&mut self) -> io::Result<Option<u64>> {
+        if self.buffer.is_empty() && self.left > 0 {
+            let count = self.left.min(READ_LEN as u64);
+            let mut bytes = vec![0; count as usize * 8];
+            let mut file = self.file;
+            file.seek(SeekFrom::Start(self.offset))?;
+            file.read_exact(&mut bytes)?;
+
+            self.buffer = bytes
+                .chunks_exact(8)
+                .rev()
+                .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+                .collect();
+            self.offset += count * 8;
+            self.left -= count;
+        }
+
+        Ok(self.buffer.pop())
+    }
+}
+
+// Runs of a column merged into one sorted stream
+struct Merge<'a> {
+    readers: Vec<RunReader<'a>>,
+    heap: BinaryHeap<Reverse<(u64, usize)>>,
+}
+
+impl Merge<'_> {
+    fn next(&mut self) -> io::Result<Option<u64>> {
+        let Some(Reverse((id, i))) = self.heap.pop() else {
+            return Ok(None);
+        };
+        if let Some(next) = self.readers[i].next()? {
+            self.heap.push(Reverse((next, i)));
+        }
+
+        Ok(Some(id))
+    }
+}
+
+// Each distinct ID of a sorted stream with the number of times it appears
+struct Counts<'a>(Merge<'a>);
+
+impl Counts<'_> {
+    fn next(&mut self) -> io::Result<Option<(u64, u64)>> {
+        let Some(id) = self.0.next()? else {
+            return Ok(None);
+        };
+
+        let mut count = 1;
+        while let Some(Reverse((next, _))) = self.0.heap.peek() {
+            if *next != id {
+                break;
+            }
+            self.0.next()?;
+            count += 1;
+        }
+
+        Ok(Some((id, count)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, solve_part1, solve_part2};
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    fn totals(input: &str, run_len: usize) -> Result<Totals, Error> {
+        stream(input.as_bytes(), run_len, &std::env::temp_dir())
+    }
+
+    #[test]
+    fn runs_agree_with_sorting_in_memory() {
+        // Plenty of repeated IDs, spread over many runs
+        let input: String = (0..1000u64)
+            .map(|i| format!("{}   {}\n", i * 7919 % 211, i * 104729 % 173))
+            .collect();
+        let (first_arr, second_arr) = parse(&input).unwrap();
+
+        // One ID per run takes two passes of merging before the last one
+        for run_len in [1, 3, 64, 1000, RUN_LEN] {
+            let totals = totals(&input, run_len).unwrap();
+            assert_eq!(
+                totals.distance,
+                solve_part1(&first_arr, &second_arr).unwrap() as u128
+            );
+            assert_eq!(
+                totals.similarity,
+                solve_part2(&first_arr, &second_arr) as u128
+            );
+        }
+
+        let example = totals(EXAMPLE, 2).unwrap();
+        assert_eq!((example.distance, example.similarity), (11, 31));
+    }
+
+    #[test]
+    fn bad_lines_are_reported() {
+        let Err(Error::Parse(e)) = totals("1 2\n\n3 4\n", 1) else {
+            panic!("a blank line in the middle is an error");
+        };
+        assert_eq!((e.line, e.column), (2, 1));
+        let Err(Error::Parse(e)) = totals("1 2\n3 x\n", 1) else {
+            panic!("IDs are numbers");
+        };
+        assert_eq!((e.line, e.column), (2, 3));
+        assert!(totals("1 2\n3 4\n\n\n", 1).is_ok());
+        let Err(Error::Parse(e)) = totals("1\n", 1) else {
+            panic!("the first line has both lists");
+        };
+        assert_eq!((e.line, e.column), (1, 2));
+    }
+
+    #[test]
+    fn lists_read_like_parse_does() {
+        let even = "1 -\n3 4\n- 2\n5 6\n\n";
+        let (first_arr, second_arr) = parse(even).unwrap();
+        let streamed = totals(even, 1).unwrap();
+        assert_eq!(
+            (streamed.distance, streamed.similarity),
+            (
+                solve_part1(&first_arr, &second_arr).unwrap() as u128,
+                solve_part2(&first_arr, &second_arr) as u128
+            )
+        );
+
+        let Err(e) = totals("1 -\n3 4\n- 2\n5\n", 1) else {
+            panic!("streamed lists have the same length");
+        };
+        assert!(matches!(e, Error::Unequal(3, 2)));
+        assert!(e.to_string().contains("only lists of the same length"));
+    }
+}
@@ -0,0 +1,204 @@
+//! Day 1: Historian Hysteria, comparing two lists of location IDs
+#![warn(missing_docs)]
+
+/// Both answers for lists too large for memory, sorted on disk
+pub mod external;
+/// Pairing lists of different lengths, by several metrics
+pub mod reconcile;
+
+use parser::{Line, ParseError};
+use reconcile::{reconcile, Metric, Unmatched};
+use std::collections::HashMap;
+
+/// Columns of location IDs separated by whitespace, as many lists as IDs on
+/// the first line. Lists may have different lengths: a `-` stands for no ID,
+/// and IDs missing at the end of a line belong to lists that already ran out
+pub fn parse_columns(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    let mut columns: Vec<Vec<u64>> = Vec::new();
+
+    for line in parser::lines(input) {
+        if columns.is_empty() {
+            columns = vec![Vec::new(); line.text.split_whitespace().count()];
+        }
+        let ids = line_ids(&line, columns.len())?;
+        for (column, id) in columns.iter_mut().zip(ids) {
+            column.extend(id);
+        }
+    }
+
+    Ok(columns)
+}
+
+// The IDs of a line of `lists` lists at most, None for a `-`
+pub(crate) fn line_ids(line: &Line, lists: usize) -> Result<Vec<Option<u64>>, ParseError> {
+    let ids: Vec<&str> = line.text.split_whitespace().collect();
+    if ids.is_empty() {
+        return Err(line.error_at_end("location IDs separated by spaces"));
+    }
+    if let Some(extra) = ids.get(lists) {
+        let expected = format!("at most {} location IDs like the first line", lists);
+        return Err(line.error(extra, &expected));
+    }
+
+    ids.into_iter()
+        .map(|id| match id {
+            "-" => Ok(None),
+            id => line.parse::<u64>(id, "a location ID or -").map(Some),
+        })
+        .collect()
+}
+
+// The first line sets the number of lists, the puzzle has two
+pub(crate) fn two_lists(first: &Line) -> Result<(), ParseError> {
+    let ids: Vec<&str> = first.text.split_whitespace().collect();
+    if let Some(extra) = ids.get(2) {
+        return Err(first.error(extra, "two location IDs per line"));
+    }
+    if ids.len() < 2 {
+        return Err(first.error_at_end("two location IDs separated by spaces"));
+    }
+    Ok(())
+}
+
+/// The left and right columns of the input, which may differ in length like
+/// [`parse_columns`] allows
+pub fn parse(input: &str) -> Result<(Vec<u64>, Vec<u64>), ParseError> {
+    if let Some(first) = parser::lines(input).next() {
+        two_lists(&first)?;
+    }
+
+    let mut columns = parse_columns(input)?;
+    let second_arr = columns.pop().unwrap_or_default();
+    let first_arr = columns.pop().unwrap_or_default();
+
+    Ok((first_arr, second_arr))
+}
+
+/// Total distance between the lists once both are sorted. The extra IDs of a
+/// longer list are left out where that gives the lowest distance, an error
+/// for lists too uneven to pair, see [`reconcile::MAX_CELLS`]. Panics if the
+/// distance does not fit in a u64, [`reconcile`] gives it as a u128
+pub fn solve_part1(first_arr: &[u64], second_arr: &[u64]) -> Result<u64, reconcile::Error> {
+    let distance = reconcile(first_arr, second_arr, Metric::L1, Unmatched::Skip)?.score;
+    Ok(u64::try_from(distance).expect("the total distance fits in a u64"))
+}
+
+/// Similarity score: each left ID times the number of times it appears on the right
+pub fn solve_part2(first_arr: &[u64], second_arr: &[u64]) -> u64 {
+    let mut b_map: HashMap<u64, u64> = HashMap::new();
+
+    for &e in second_arr {
+        let count = b_map.entry(e).or_insert(0);
+        *count += 1;
+    }
+
+    let mut sim_score = 0;
+
+    for &e in first_arr {
+        sim_score += e * (b_map.get(&e).unwrap_or(&0));
+    }
+
+    sim_score
+}
+
+/// `metric` between every pair of columns, the left list picking the row and
+/// the right one the column. Similarity is not symmetric, the distances are
+pub fn matrix(
+    columns: &[Vec<u64>],
+    metric: Metric,
+    unmatched: Unmatched,
+) -> Result<Vec<Vec<u128>>, reconcile::Error> {
+    columns
+        .iter()
+        .map(|left| {
+            columns
+                .iter()
+                .map(|right| Ok(reconcile(left, right, metric, unmatched)?.score))
+                .collect()
+        })
+        .collect()
+}
+
+/// The matrix as a right-aligned table headed by the column numbers, from 1
+pub fn render_matrix(matrix: &[Vec<u128>]) -> String {
+    let cells: Vec<Vec<String>> = matrix
+        .iter()
+        .map(|row| row.iter().map(u128::to_string).collect())
+        .collect();
+    let width = cells
+        .iter()
+        .flatten()
+        .map(String::len)
+        .chain([matrix.len().to_string().len()])
+        .max()
+        .unwrap_or(1);
+    let label = matrix.len().to_string().len();
+
+    let mut table = format!("{:label$}", "");
+    for column in 1..=matrix.len() {
+        table += &format!("  {:>width$}", column);
+    }
+    for (row, cells) in cells.iter().enumerate() {
+        table += &format!("\n{:>label$}", row + 1);
+        for cell in cells {
+            table += &format!("  {:>width$}", cell);
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let (first_arr, second_arr) = parse(EXAMPLE).unwrap();
+        assert_eq!(solve_part1(&first_arr, &second_arr), Ok(11));
+    }
+
+    #[test]
+    fn part2_example() {
+        let (first_arr, second_arr) = parse(EXAMPLE).unwrap();
+        assert_eq!(solve_part2(&first_arr, &second_arr), 31);
+    }
+
+    #[test]
+    fn every_pair_of_columns() {
+        let columns = parse_columns("3 4\t3\n4  3 9\n2 5 3\n1 3 4\n3 9 1\n3 3 3\n").unwrap();
+        assert_eq!(columns.len(), 3);
+
+        let distances = matrix(&columns, Metric::L1, Unmatched::Skip).unwrap();
+        assert_eq!(distances[0][1], 11);
+        assert_eq!(distances[1][0], 11);
+        assert_eq!(distances[0][0], 0);
+        let similarity = matrix(&columns, Metric::Similarity, Unmatched::Skip).unwrap();
+        assert_eq!(similarity[0][1], 31);
+        assert_eq!(similarity[2][0], 3 * 3 * 3 + 4 + 1);
+
+        let table = render_matrix(&[vec![0, 11], vec![11, 0]]);
+        assert_eq!(table, "    1   2\n1   0  11\n2  11   0");
+    }
+
+    #[test]
+    fn lists_may_run_out() {
+        let columns = parse_columns("1 2 3\n4 - 5\n6\n").unwrap();
+        assert_eq!(columns, [vec![1, 4, 6], vec![2], vec![3, 5]]);
+        let (first_arr, second_arr) = parse("3 4\n4\n2\n").unwrap();
+        assert_eq!(solve_part1(&first_arr, &second_arr), Ok(0));
+        assert_eq!(
+            matrix(&[first_arr, second_arr], Metric::L1, Unmatched::Error),
+            Err(reconcile::Error::LengthMismatch(3, 1))
+        );
+
+        let e = parse_columns("1 2\n4 5 6\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 5));
+        let e = parse_columns("1 2\n\n4 5\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        let e = parse("1 2 3\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 5));
+    }
+}
@@ -0,0 +1,101 @@
+use day1::external::{stream, RUN_LEN};
+use day1::reconcile::{reconcile, Metric, Unmatched};
+use day1::{matrix, parse_columns, render_matrix, solve_part1, solve_part2};
+use parser::{unwrap_or_exit, DayArgs};
+use std::env;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::process;
+
+fn exit_with(e: impl Display) -> ! {
+    eprintln!("error: {}", e);
+    process::exit(1);
+}
+
+// Lists too large for memory are sorted on disk, a path or - for stdin
+fn stream_or_exit(path: &str) {
+    let totals = if path == "-" {
+        stream(io::stdin().lock(), RUN_LEN, &env::temp_dir())
+    } else {
+        File::open(path)
+            .map_err(Into::into)
+            .and_then(|file| stream(BufReader::new(file), RUN_LEN, &env::temp_dir()))
+    };
+    let totals = totals.unwrap_or_else(|e| exit_with(format!("{}: {}", path, e)));
+
+    println!("{}", totals.distance);
+    println!("{}", totals.similarity);
+}
+
+fn title(metric: Metric) -> &'static str {
+    match metric {
+        Metric::L1 => "Distance",
+        Metric::L2Squared => "Squared distance",
+        Metric::Max => "Largest difference",
+        Metric::Similarity => "Similarity",
+    }
+}
+
+fn main() {
+    let mut args = DayArgs::from_env(
+        "[--stream PATH|-] [--metric l1|l2|max|similarity] [--unmatched skip|error|PENALTY] [--pairs] ",
+    );
+    if let Some(path) = args.value("--stream") {
+        stream_or_exit(&path);
+        return;
+    }
+    let metric = args.value("--metric").map(|name| {
+        Metric::from_name(&name)
+            .unwrap_or_else(|| args.exit("--metric expects l1, l2, max or similarity"))
+    });
+    let unmatched = args.value("--unmatched").map(|name| {
+        Unmatched::from_name(&name)
+            .unwrap_or_else(|| args.exit("--unmatched expects skip, error or a penalty"))
+    });
+    let pairs = args.flag("--pairs");
+
+    let (filename, input) = args.read_input_or_exit(env!("CARGO_MANIFEST_DIR"));
+    let columns = unwrap_or_exit(parse_columns(&input), &filename, &input);
+
+    match columns.as_slice() {
+        // The puzzle's two lists get its two answers
+        [first_arr, second_arr] if metric.is_none() && unmatched.is_none() && !pairs => {
+            let distance = solve_part1(first_arr, second_arr).unwrap_or_else(|e| exit_with(e));
+            println!("{}", distance);
+            println!("{}", solve_part2(first_arr, second_arr));
+        }
+        // Or the pairs they reconcile into, in the input format with - for no partner
+        [left, right] => {
+            let metric = metric.unwrap_or(Metric::L1);
+            let reconciliation =
+                reconcile(left, right, metric, unmatched.unwrap_or(Unmatched::Skip))
+                    .unwrap_or_else(|e| exit_with(e));
+            if pairs {
+                for (a, b) in &reconciliation.pairs {
+                    println!("{} {}", a, b);
+                }
+                for id in &reconciliation.unmatched_left {
+                    println!("{} -", id);
+                }
+                for id in &reconciliation.unmatched_right {
+                    println!("- {}", id);
+                }
+            }
+            println!("{}: {}", title(metric), reconciliation.score);
+        }
+        _ if pairs => exit_with("--pairs needs exactly two lists"),
+        // More lists get every pair of them
+        _ => {
+            let metrics = match metric {
+                Some(metric) => vec![metric],
+                None => vec![Metric::L1, Metric::Similarity],
+            };
+            for metric in metrics {
+                let matrix = matrix(&columns, metric, unmatched.unwrap_or(Unmatched::Skip))
+                    .unwrap_or_else(|e| exit_with(e));
+                println!("{}\n{}", title(metric), render_matrix(&matrix));
+            }
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Pairing up two lists of IDs that need not be the same length
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// How far apart two reconciled lists are
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Sum of the differences of the pairs, the distance of part 1
+    L1,
+    /// Sum of the squared differences of the pairs
+    L2Squared,
+    /// Largest difference of any pair
+    Max,
+    /// Each left ID times the number of times it appears on the right, the
+    /// similarity score of part 2. Pairs are equal IDs, one left to one right
+    Similarity,
+}
+
+impl Metric {
+    /// The metric called `l1`, `l2`, `max` or `similarity`
+    pub fn from_name(name: &str) -> Option<Metric> {
+        match name {
+            "l1" => Some(Metric::L1),
+            "l2" => Some(Metric::L2Squared),
+            "max" => Some(Metric::Max),
+            "similarity" => Some(Metric::Similarity),
+            _ => None,
+        }
+    }
+}
+
+/// What becomes of IDs left without a partner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unmatched {
+    /// Each unmatched ID costs this much, on the scale of the metric: added to
+    /// the sums, raising the largest difference, taken off the similarity.
+    /// For the distance metrics only the surplus of the longer list goes
+    /// unmatched, a costly pair is never dropped for two penalties instead
+    Penalty(u64),
+    /// Unmatched IDs are left out without a cost
+    Skip,
+    /// Lists of different lengths are an error
+    Error,
+}
+
+impl Unmatched {
+    /// `skip`, `error`, or a number for the penalty
+    pub fn from_name(name: &str) -> Option<Unmatched> {
+        match name {
+            "skip" => Some(Unmatched::Skip),
+            "error" => Some(Unmatched::Error),
+            _ => name.parse().ok().map(Unmatched::Penalty),
+        }
+    }
+}
+
+/// Most steps pairing lists of different lengths may take, n·(k + 1) for n
+/// IDs in the shorter list and k more in the longer one. Each step also keeps
+/// a bit to walk back the pairs, 16 MiB at most
+pub const MAX_CELLS: u128 = 1 << 27;
+
+/// Why the lists could not be reconciled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The lists have different lengths, this many IDs on the left and right,
+    /// and unmatched IDs are an error
+    LengthMismatch(usize, usize),
+    /// The lists are too uneven to pair within [`MAX_CELLS`] steps, this many
+    /// IDs on the left and right
+    TooUneven(usize, usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::LengthMismatch(left, right) => write!(
+                f,
+                "the left list has {} IDs and the right one {}",
+                left, right
+            ),
+            Error::TooUneven(left, right) => write!(
+                f,
+                "the left list has {} IDs and the right one {}, pairing lists this uneven takes more than {} steps",
+                left, right, MAX_CELLS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The pairs the lists were matched into and what was left over
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// (left, right) pairs, in increasing order
+    pub pairs: Vec<(u64, u64)>,
+    /// Left IDs without a partner, in increasing order
+    pub unmatched_left: Vec<u64>,
+    /// Right IDs without a partner, in increasing order
+    pub unmatched_right: Vec<u64>,
+    /// The metric over the pairs, with the penalties of the unmatched IDs
+    pub score: u128,
+}
+
+/// Matches the sorted lists. For the distance metrics every ID of the shorter
+/// list gets a partner, chosen to give the lowest score. With n IDs in the
+/// shorter list and k more in the longer one that takes O(n·k) time and
+/// O(n·k) bits of memory to walk back the pairs, so lists more uneven than
+/// [`MAX_CELLS`] allows are an error. Lists of the same length are simply
+/// paired in sorted order, and the similarity pairs equal IDs whatever the
+/// lengths, both in O(n log n)
+pub fn reconcile(
+    left: &[u64],
+    right: &[u64],
+    metric: Metric,
+    unmatched: Unmatched,
+) -> Result<Reconciliation, Error> {
+    if unmatched == Unmatched::Error && left.len() != right.len() {
+        return Err(Error::LengthMismatch(left.len(), right.len()));
+    }
+    let (n, k) = (
+        left.len().min(right.len()),
+        left.len().abs_diff(right.len()),
+    );
+    if metric != Metric::Similarity && k > 0 && n as u128 * (k as u128 + 1) > MAX_CELLS {
+        return Err(Error::TooUneven(left.len(), right.len()));
+    }
+    let penalty = match unmatched {
+        Unmatched::Penalty(penalty) => penalty as u128,
+        Unmatched::Skip | Unmatched::Error => 0,
+    };
+
+    let mut left = left.to_vec();
+    let mut right = right.to_vec();
+    left.sort();
+    right.sort();
+
+    if metric == Metric::Similarity {
+        return Ok(similarity(&left, &right, penalty));
+    }
+
+    // Pair the shorter list into the longer one
+    let swapped = left.len() > right.len();
+    let (short, long) = if swapped {
+        (&right, &left)
+    } else {
+        (&left, &right)
+    };
+    let (pairs, leftover, score) = closest_pairs(short, long, metric, penalty);
+
+    Ok(if swapped {
+        Reconciliation {
+            pairs: pairs.into_iter().map(|(r, l)| (l, r)).collect(),
+            unmatched_left: leftover,
+            unmatched_right: Vec::new(),
+            score,
+        }
+    } else {
+        Reconciliation {
+            pairs,
+            unmatched_left: Vec::new(),
+            unmatched_right: leftover,
+            score,
+        }
+    })
+}
+
+// Best order-keeping matching of every `short` ID to a `long` one, and the
+// `long` IDs left over. Matchings that cross are never better for these metrics
+fn closest_pairs(
+    short: &[u64],
+    long: &[u64],
+    metric: Metric,
+    penalty: u128,
+) -> (Vec<(u64, u64)>, Vec<u64>, u128) {
+    let combine = |score: u128, cost: u128| match metric {
+        Metric::Max => score.max(cost),
+        _ => score.saturating_add(cost),
+    };
+    let pair_cost = |a: u64, b: u64| {
+        let diff = a.abs_diff(b) as u128;
+        match metric {
+            Metric::L2Squared => diff * diff,
+            _ => diff,
+        }
+    };
+
+    let (n, k) = (short.len(), long.len() - short.len());
+    if k == 0 {
+        let pairs: Vec<(u64, u64)> = short.iter().copied().zip(long.iter().copied()).collect();
+        let score = pairs.iter().map(|&(a, b)| pair_cost(a, b)).fold(0, combine);
+        return (pairs, Vec::new(), score);
+    }
+
+    // row[d]: best for short[..i] matched into long[..i + d], d skipped, with
+    // prev the same for i - 1. The bit of (i, d) in matched is set when that
+    // best ends by matching short[i - 1]
+    let width = k + 1;
+    let mut prev: Vec<u128> = vec![0; width];
+    let mut row: Vec<u128> = vec![0; width];
+    let mut matched: Vec<u64> = vec![0; ((n + 1) * width).div_ceil(64)];
+    let is_matched = |matched: &[u64], cell: usize| matched[cell / 64] >> (cell % 64) & 1 == 1;
+
+    for i in 0..=n {
+        for d in 0..=k {
+            let by_match =
+                (i > 0).then(|| combine(prev[d], pair_cost(short[i - 1], long[i - 1 + d])));
+            let by_skip = (d > 0).then(|| combine(row[d - 1], penalty));
+
+            let cell = i * width + d;
+            row[d] = match (by_match, by_skip) {
+                (Some(m), Some(s)) if s < m => s,
+                (Some(m), _) => {
+                    matched[cell / 64] |= 1 << (cell % 64);
+                    m
+                }
+                (None, Some(s)) => s,
+                (None, None) => 0,
+            };
+        }
+        std::mem::swap(&mut prev, &mut row);
+    }
+
+    let mut pairs: Vec<(u64, u64)> = Vec::with_capacity(n);
+    let mut leftover: Vec<u64> = Vec::with_capacity(k);
+    let (mut i, mut d) = (n, k);
+    while i + d > 0 {
+        if is_matched(&matched, i * width + d) {
+            pairs.push((short[i - 1], long[i + d - 1]));
+            i -= 1;
+        } else {
+            leftover.push(long[i + d - 1]);
+            d -= 1;
+        }
+    }
+    pairs.reverse();
+    leftover.reverse();
+
+    (pairs, leftover, prev[k])
+}
+
+// Equal IDs paired one to one, the rest unmatched
+fn similarity(left: &[u64], right: &[u64], penalty: u128) -> Reconciliation {
+    let mut counts: HashMap<u64, u128> = HashMap::new();
+    for &id in right {
+        *counts.entry(id).or_insert(0) += 1;
+    }
+    let score: u128 = left
+        .iter()
+        .map(|&id| id as u128 * counts.get(&id).copied().unwrap_or(0))
+        .fold(0, u128::saturating_add);
+
+    let mut reconciliation = Reconciliation {
+        pairs: Vec::new(),
+        unmatched_left: Vec::new(),
+        unmatched_right: Vec::new(),
+        score,
+    };
+    let (mut l, mut r) = (0, 0);
+    while l < left.len() && r < right.len() {
+        if left[l] == right[r] {
+            reconciliation.pairs.push((left[l], right[r]));
+            (l, r) = (l + 1, r + 1);
+        } else if left[l] < right[r] {
+            reconciliation.unmatched_left.push(left[l]);
+            l += 1;
+        } else {
+            reconciliation.unmatched_right.push(right[r]);
+            r += 1;
+        }
+    }
+    reconciliation.unmatched_left.extend(&left[l..]);
+    reconciliation.unmatched_right.extend(&right[r..]);
+
+    let unmatched = reconciliation.unmatched_left.len() + reconciliation.unmatched_right.len();
+    reconciliation.score = score.saturating_sub(penalty.saturating_mul(unmatched as u128));
+    reconciliation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: [u64; 6] = [3, 4, 2, 1, 3, 3];
+    const RIGHT: [u64; 6] = [4, 3, 5, 3, 9, 3];
+
+    #[test]
+    fn equal_lengths_pair_in_sorted_order() {
+        let r = reconcile(&LEFT, &RIGHT, Metric::L1, Unmatched::Error).unwrap();
+        assert_eq!(r.score, 11);
+        assert_eq!(r.pairs, [(1, 3), (2, 3), (3, 3), (3, 4), (3, 5), (4, 9)]);
+
+        let score = |metric| {
+            reconcile(&LEFT, &RIGHT, metric, Unmatched::Error)
+                .unwrap()
+                .score
+        };
+        assert_eq!(score(Metric::L2Squared), 35);
+        assert_eq!(score(Metric::Max), 5);
+        assert_eq!(score(Metric::Similarity), 31);
+    }
+
+    #[test]
+    fn extra_ids_are_left_where_they_cost_least() {
+        let right = [10, 1, 5, 6];
+        let r = reconcile(&[5, 2], &right, Metric::L1, Unmatched::Skip).unwrap();
+        assert_eq!(r.pairs, [(2, 1), (5, 5)]);
+        assert_eq!(r.unmatched_right, [6, 10]);
+        assert_eq!(r.score, 1);
+
+        let r = reconcile(&right, &[5, 2], Metric::L1, Unmatched::Penalty(100)).unwrap();
+        assert_eq!(r.pairs, [(1, 2), (5, 5)]);
+        assert_eq!(r.unmatched_left, [6, 10]);
+        assert_eq!(r.score, 201);
+
+        let r = reconcile(&right, &[5, 2], Metric::Max, Unmatched::Penalty(3)).unwrap();
+        assert_eq!(r.score, 3);
+
+        assert_eq!(
+            reconcile(&[1, 2], &[1], Metric::L1, Unmatched::Error),
+            Err(Error::LengthMismatch(2, 1))
+        );
+    }
+
+    #[test]
+    fn names_of_metrics_and_policies() {
+        assert_eq!(Metric::from_name("l2"), Some(Metric::L2Squared));
+        assert_eq!(Metric::from_name("L1"), None);
+        assert_eq!(Unmatched::from_name("skip"), Some(Unmatched::Skip));
+        assert_eq!(Unmatched::from_name("25"), Some(Unmatched::Penalty(25)));
+        assert_eq!(Unmatched::from_name("-1"), None);
+    }
+
+    #[test]
+    fn similarity_pairs_equal_ids() {
+        let r = reconcile(
+            &LEFT,
+            &RIGHT[..4],
+            Metric::Similarity,
+            Unmatched::Penalty(1),
+        )
+        .unwrap();
+        assert_eq!(r.pairs, [(3, 3), (3, 3), (4, 4)]);
+        assert_eq!(r.unmatched_left, [1, 2, 3]);
+        assert_eq!(r.unmatched_right, [5]);
+        assert_eq!(r.score, 3 * 2 * 3 + 4 - 4);
+    }
+
+    #[test]
+    fn lists_too_uneven_are_refused() {
+        let (short, long) = (vec![1; 1 << 14], vec![2; 1 << 15]);
+        assert_eq!(
+            reconcile(&short, &long, Metric::L1, Unmatched::Skip),
+            Err(Error::TooUneven(1 << 14, 1 << 15))
+        );
+        let r = reconcile(&long, &short, Metric::Similarity, Unmatched::Skip).unwrap();
+        assert_eq!(r.score, 0);
+    }
+}
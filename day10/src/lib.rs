@@ -0,0 +1,78 @@
+//! Day 10: Hoof It, scoring hiking trails on a topographic map
+#![warn(missing_docs)]
+
+use grid::{Grid, Point};
+use parser::ParseError;
+use std::collections::HashSet;
+use viz::{Palette, Picture, Rgb};
+
+fn find_top_positions(map: &Grid<u8>, curr_node: Point) -> (HashSet<Point>, usize) {
+    if map[curr_node] == 9 {
+        return (HashSet::from_iter([curr_node]), 1);
+    }
+
+    let mut top_positions: HashSet<Point> = HashSet::new();
+    let mut trail_count: usize = 0;
+
+    for (_, next) in map.neighbours(curr_node) {
+        if map[curr_node] + 1 == map[next] {
+            let (tp, tc) = find_top_positions(map, next);
+            top_positions.extend(tp);
+            trail_count += tc;
+        }
+    }
+
+    (top_positions, trail_count)
+}
+
+/// Sums over the trailheads of the number of 9-height positions they reach,
+/// and of the number of distinct trails to them
+pub fn calculate_score(map: &Grid<u8>) -> (usize, usize) {
+    let mut score = 0;
+    let mut rating = 0;
+
+    for trailhead in map.find_all(0) {
+        let (tp, tc) = find_top_positions(map, trailhead);
+        score += tp.len();
+        rating += tc;
+    }
+
+    (score, rating)
+}
+
+/// Heights from dark green valleys to white peaks, keys are the heights themselves
+pub fn picture(map: &Grid<u8>) -> Picture<'_> {
+    let shades: Vec<(u8, Rgb)> = (0..=9)
+        .map(|h| (h, Rgb(20 + h * 25, 60 + h * 21, 30 + h * 25)))
+        .collect();
+
+    let mut picture = Picture::new(map.height(), map.width(), Palette::new(&shades));
+    picture.add(map);
+    picture
+}
+
+/// A grid of heights from 0 to 9
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let map = Grid::parse(input, |c| c.is_ascii_digit(), "a height digit")?;
+
+    Ok(map.map(|c| c - b'0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(calculate_score(&map).0, 36);
+    }
+
+    #[test]
+    fn part2_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(calculate_score(&map).1, 81);
+    }
+}
@@ -0,0 +1,147 @@
+//! Day 11: Plutonian Pebbles, counting stones that split as you blink
+#![warn(missing_docs)]
+
+use algo::Memo;
+use num_traits::CheckedAdd;
+use parser::ParseError;
+use std::fmt;
+
+/// Blinks of part 1
+pub const PART1_BLINKS: u64 = 25;
+/// Blinks of part 2
+pub const PART2_BLINKS: u64 = 75;
+
+/// Stone counts grow exponentially with the blinks, u64 lasts until about 100
+#[cfg(not(feature = "bigint"))]
+pub type Count = u64;
+/// Stone counts without an upper bound
+#[cfg(feature = "bigint")]
+pub type Count = num_bigint::BigUint;
+
+/// What no longer fits in 64 bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// The number engraved on a stone
+    Stone,
+    /// The number of stones
+    Count,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Overflow::Stone => write!(f, "a stone number does not fit in 64 bits"),
+            Overflow::Count => write!(
+                f,
+                "the stone count does not fit in 64 bits, try the bigint feature"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+fn add(a: &Count, b: &Count) -> Result<Count, Overflow> {
+    CheckedAdd::checked_add(a, b).ok_or(Overflow::Count)
+}
+
+// (num, depth) -> # Stones
+type Blinks = Memo<(u64, u64), Count>;
+
+fn stones_after_n_blinks(
+    max_depth: u64,
+    stone: u64,
+    current_depth: u64,
+    mem: &mut Blinks,
+) -> Result<Count, Overflow> {
+    if max_depth == current_depth {
+        // Base Case
+        return Ok(Count::from(1u8));
+    }
+
+    mem.try_get_or_insert_with((stone, current_depth), |mem| {
+        if stone == 0 {
+            return stones_after_n_blinks(max_depth, 1, current_depth + 1, mem);
+        }
+
+        let stone_str = stone.to_string();
+        let len = stone_str.len();
+
+        if len.is_multiple_of(2) {
+            let left = stones_after_n_blinks(
+                max_depth,
+                stone_str[..(len / 2)].parse::<u64>().unwrap(),
+                current_depth + 1,
+                mem,
+            )?;
+            let right = stones_after_n_blinks(
+                max_depth,
+                stone_str[(len / 2)..].parse::<u64>().unwrap(),
+                current_depth + 1,
+                mem,
+            )?;
+            add(&left, &right)
+        } else {
+            let stone = stone.checked_mul(2024).ok_or(Overflow::Stone)?;
+            stones_after_n_blinks(max_depth, stone, current_depth + 1, mem)
+        }
+    })
+}
+
+/// Number of stones after `max_depth` blinks
+pub fn solve(stones: &[u64], max_depth: u64) -> Result<Count, Overflow> {
+    let mut mem = Blinks::new();
+    let mut total_stones = Count::from(0u8);
+
+    for &stone in stones {
+        let count = stones_after_n_blinks(max_depth, stone, 0, &mut mem)?;
+        total_stones = add(&total_stones, &count)?;
+    }
+
+    Ok(total_stones)
+}
+
+/// The numbers on the stones, on one line
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    let line = parser::lines(input)
+        .next()
+        .ok_or_else(|| parser::end_of_input(input, "a line of stones"))?;
+
+    line.parse_list::<u64>(line.text, " ", "a stone number")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let stones = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&stones, 6), Ok(Count::from(22u8)));
+        assert_eq!(solve(&stones, 25), Ok(Count::from(55312u32)));
+    }
+
+    // The puzzle gives no part 2 answer for the example
+    #[test]
+    fn part2_example() {
+        let stones = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&stones, 75), Ok(Count::from(65601038650482u64)));
+    }
+
+    #[cfg(not(feature = "bigint"))]
+    #[test]
+    fn long_blinks_overflow() {
+        let stones = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&stones, 200), Err(Overflow::Count));
+        assert_eq!(solve(&[10_u64.pow(18)], 1), Err(Overflow::Stone));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn long_blinks_fit_in_a_bigint() {
+        let stones = parse(EXAMPLE).unwrap();
+        assert!(solve(&stones, 200).unwrap() > Count::from(u64::MAX));
+    }
+}
@@ -0,0 +1,179 @@
+//! Day 12: Garden Groups, pricing the fences around garden regions
+#![warn(missing_docs)]
+
+use algo::GridGraph;
+use grid::{Dir, Grid, Point};
+use parser::ParseError;
+use viz::{Edge, Layer, Palette, Picture, Rgb};
+
+#[derive(Debug)]
+struct Fence {
+    side: Dir,
+    pos: Point, // The plot the fence belongs to
+}
+
+#[derive(Debug)]
+struct PlotRequirements {
+    area: usize,
+    fences: Vec<Fence>,
+}
+
+type GardenRequirements = Vec<PlotRequirements>;
+/// One plant letter per plot
+pub type GardenMap<T> = Grid<T>;
+
+fn get_fences(map: &GardenMap<u8>, pos: Point) -> Vec<Fence> {
+    let mut fences: Vec<Fence> = Vec::new();
+
+    for side in Dir::ALL {
+        let adjacent = map.step(pos, side);
+        if adjacent.is_none_or(|p| map[p] != map[pos]) {
+            // Adjacent plant is not the same type, then add a fence
+            fences.push(Fence { side, pos });
+        }
+    }
+
+    fences
+}
+
+fn get_full_price(garden: GardenRequirements) -> u64 {
+    garden.into_iter().fold(0, |price, req| {
+        price + req.area as u64 * req.fences.len() as u64
+    })
+}
+
+fn get_discounted_price(garden: GardenRequirements) -> u64 {
+    let mut price: u64 = 0;
+
+    for req in garden {
+        let mut side_fences: [Vec<(usize, usize)>; 4] = Default::default();
+
+        for fence in req.fences {
+            // Fences on the same side line up along a row or along a column
+            let pos = if fence.side.is_horizontal() {
+                (fence.pos.j, fence.pos.i)
+            } else {
+                (fence.pos.i, fence.pos.j)
+            };
+            side_fences[fence.side as usize].push(pos);
+        }
+
+        let mut all_sides = 0;
+
+        for fences in side_fences.iter_mut() {
+            fences.sort();
+
+            let mut sides = 0;
+
+            if !fences.is_empty() {
+                sides += 1;
+                for j in 0..(fences.len() - 1) {
+                    if fences[j].0 != fences[j + 1].0 || fences[j].1 + 1 != fences[j + 1].1 {
+                        // Next fence is not adjacent, count the next one as another separated side
+                        sides += 1;
+                    }
+                }
+            }
+
+            all_sides += sides;
+        }
+
+        price += req.area as u64 * all_sides;
+    }
+
+    price
+}
+
+/// Total price of fencing every region, area times perimeter or, with
+/// `side_mode`, area times number of sides
+pub fn solve(map: &GardenMap<u8>, side_mode: bool) -> u64 {
+    // Plots of a region are joined to the neighbours growing the same plant
+    let garden = GridGraph::new(map, |from, to| map[from] == map[to]);
+    let mut visited = vec![false; map.height() * map.width()];
+    let mut req: GardenRequirements = Vec::new();
+
+    for p in map.points() {
+        let region = algo::flood_fill(&garden, p, &mut visited);
+        if !region.is_empty() {
+            req.push(PlotRequirements {
+                area: region.len(),
+                fences: region
+                    .iter()
+                    .flat_map(|&pos| get_fences(map, pos))
+                    .collect(),
+            });
+        }
+    }
+
+    if side_mode {
+        get_discounted_price(req)
+    } else {
+        get_full_price(req)
+    }
+}
+
+// Key of the fences in pictures
+const FENCE: u8 = b'#';
+
+// Plots coloured by plant, fenced wherever the neighbour grows something else
+struct Garden<'a>(&'a GardenMap<u8>);
+
+impl Layer for Garden<'_> {
+    fn cell(&self, p: Point) -> Option<u8> {
+        self.0.get(p).copied()
+    }
+
+    fn edges(&self) -> Vec<Edge> {
+        self.0
+            .points()
+            .flat_map(|p| get_fences(self.0, p))
+            .map(|fence| Edge {
+                cell: fence.pos,
+                side: fence.side,
+                key: FENCE,
+            })
+            .collect()
+    }
+}
+
+/// Plots coloured by plant, with the fences between regions
+pub fn picture(map: &GardenMap<u8>) -> Picture<'_> {
+    let mut picture = Picture::new(
+        map.height(),
+        map.width(),
+        Palette::new(&[(FENCE, Rgb(40, 40, 40))]),
+    );
+    picture.add(Garden(map));
+    picture
+}
+
+/// A grid of uppercase plant letters
+pub fn parse(input: &str) -> Result<GardenMap<u8>, ParseError> {
+    Grid::parse(input, |c| c.is_ascii_uppercase(), "a plant letter")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&map, false), 1930);
+    }
+
+    #[test]
+    fn part2_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&map, true), 1206);
+    }
+
+    #[test]
+    fn every_plot_side_facing_another_plant_is_fenced() {
+        let map = parse("AAAA\nBBCD\nBBCC\nEEEC\n").unwrap();
+        // Perimeters of A, B, C, D and E
+        assert_eq!(Garden(&map).edges().len(), 10 + 8 + 10 + 4 + 8);
+    }
+}
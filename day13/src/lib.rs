@@ -0,0 +1,446 @@
+//! Day 13: Claw Contraption, the fewest tokens to win prizes from claw machines
+#![warn(missing_docs)]
+
+use num_traits::{CheckedAdd, CheckedEuclid, CheckedMul, CheckedSub, One, Zero};
+use parser::{Line, ParseError};
+use regex::Regex;
+use std::fmt;
+use trace::{Fact, NoTrace, Trace, Value};
+
+/// Tokens a press of button A and of button B cost
+pub const BUTTON_TOKENS: (i64, i64) = (3, 1);
+/// Added to both prize coordinates in part 2
+pub const PRIZE_OFFSET: i64 = 10000000000000;
+
+/// Presses and tokens, the offset prizes of part 2 leave i64 little room
+#[cfg(not(feature = "bigint"))]
+pub type Int = i64;
+/// Presses and tokens without bounds
+#[cfg(feature = "bigint")]
+pub type Int = num_bigint::BigInt;
+
+/// The presses or tokens do not fit in 64 bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the claw machine arithmetic does not fit in 64 bits, try the bigint feature"
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// A claw machine, whose buttons move the claw forward along X and Y
+#[derive(Debug)]
+pub struct Query {
+    /// How far button A moves the claw along X and Y
+    pub button_a: (i64, i64),
+    /// How far button B moves the claw along X and Y
+    pub button_b: (i64, i64),
+    /// Where the prize is
+    pub prize: (i64, i64),
+}
+
+fn add(a: &Int, b: &Int) -> Result<Int, Overflow> {
+    CheckedAdd::checked_add(a, b).ok_or(Overflow)
+}
+
+fn sub(a: &Int, b: &Int) -> Result<Int, Overflow> {
+    CheckedSub::checked_sub(a, b).ok_or(Overflow)
+}
+
+fn mul(a: &Int, b: &Int) -> Result<Int, Overflow> {
+    CheckedMul::checked_mul(a, b).ok_or(Overflow)
+}
+
+// Quotient rounded down and remainder, for b > 0
+fn div_rem(a: &Int, b: &Int) -> Result<(Int, Int), Overflow> {
+    a.checked_div_rem_euclid(b).ok_or(Overflow)
+}
+
+// The gcd g of u and v, with x such that u·x ≡ g (mod v), for u, v > 0
+// (Int is only Copy without the bigint feature)
+#[allow(clippy::clone_on_copy)]
+fn gcd_inverse(u: &Int, v: &Int) -> Result<(Int, Int), Overflow> {
+    let (mut r0, mut r1) = (u.clone(), v.clone());
+    let (mut x0, mut x1) = (Int::one(), Int::zero());
+    while !r1.is_zero() {
+        let (quotient, rest) = div_rem(&r0, &r1)?;
+        let x = sub(&x0, &mul(&quotient, &x1)?)?;
+        (r0, r1) = (r1, rest);
+        (x0, x1) = (x1, x);
+    }
+    Ok((r0, x0))
+}
+
+// Presses of A and of B moving the claw by w along an axis where A moves it
+// by u and B by v, at least 0 and not both 0, that cost the fewest tokens
+fn presses_along(
+    u: &Int,
+    v: &Int,
+    w: &Int,
+    tokens: (i64, i64),
+) -> Result<Option<(Int, Int)>, Overflow> {
+    let exact = |w: &Int, by: &Int| -> Result<Option<Int>, Overflow> {
+        let (quotient, rest) = div_rem(w, by)?;
+        Ok((rest.is_zero() && quotient >= Int::zero()).then_some(quotient))
+    };
+    if u.is_zero() {
+        return Ok(exact(w, v)?.map(|b| (Int::zero(), b)));
+    }
+    if v.is_zero() {
+        return Ok(exact(w, u)?.map(|a| (a, Int::zero())));
+    }
+
+    // The presses of A reaching w repeat every v / g, from the fewest a0
+    let (g, inverse) = gcd_inverse(u, v)?;
+    let (reduced, rest) = div_rem(w, &g)?;
+    if !rest.is_zero() {
+        return Ok(None);
+    }
+    let period = div_rem(v, &g)?.0;
+    let a0 = div_rem(
+        &mul(
+            &div_rem(&reduced, &period)?.1,
+            &div_rem(&inverse, &period)?.1,
+        )?,
+        &period,
+    )?
+    .1;
+
+    // Each token buys the most distance with the fewest or the most A presses
+    let a_cheaper = mul(&Int::from(tokens.0), v)? < mul(&Int::from(tokens.1), u)?;
+    let a = if a_cheaper {
+        let most = div_rem(w, u)?.0;
+        sub(&most, &div_rem(&sub(&most, &a0)?, &period)?.1)?
+    } else {
+        a0
+    };
+
+    let rest = sub(w, &mul(&a, u)?)?;
+    if a < Int::zero() || rest < Int::zero() {
+        return Ok(None);
+    }
+    Ok(Some((a, div_rem(&rest, v)?.0)))
+}
+
+/// Presses of A and of B that reach the prize moved by `prize_offset` for the
+/// fewest tokens, None when no whole numbers of presses reach it. `tokens`
+/// are the costs of the A and B presses, which only matter when the buttons
+/// move the claw along the same line
+pub fn presses(
+    q: &Query,
+    prize_offset: i64,
+    tokens: (i64, i64),
+) -> Result<Option<(Int, Int)>, Overflow> {
+    let (ax, ay) = (Int::from(q.button_a.0), Int::from(q.button_a.1));
+    let (bx, by) = (Int::from(q.button_b.0), Int::from(q.button_b.1));
+
+    // Add prize offset for part 2
+    let offset = Int::from(prize_offset);
+    let px = add(&Int::from(q.prize.0), &offset)?;
+    let py = add(&Int::from(q.prize.1), &offset)?;
+
+    // Buttons along the same line only reach prizes on it, along one axis
+    // the presses are found and then checked on the other
+    if sub(&mul(&ax, &by)?, &mul(&ay, &bx)?)?.is_zero() {
+        let found = if !(ax.is_zero() && bx.is_zero()) {
+            presses_along(&ax, &bx, &px, tokens)?
+        } else if !(ay.is_zero() && by.is_zero()) {
+            presses_along(&ay, &by, &py, tokens)?
+        } else {
+            Some((Int::zero(), Int::zero()))
+        };
+        let Some((a, b)) = found else {
+            return Ok(None);
+        };
+        let x = add(&mul(&a, &ax)?, &mul(&b, &bx)?)?;
+        let y = add(&mul(&a, &ay)?, &mul(&b, &by)?)?;
+        return Ok((x == px && y == py).then_some((a, b)));
+    }
+
+    // Basically solving 2 algebraic equations w/ 2 variables (Cramer's rules)
+    let Some([a_presses, b_presses]) =
+        algo::solve_2x2(&[[ax, bx], [ay, by]], &[px, py]).map_err(|_| Overflow)?
+    else {
+        // Not integer
+        return Ok(None);
+    };
+
+    // A button can't be pressed a negative number of times
+    if a_presses < Int::zero() || b_presses < Int::zero() {
+        return Ok(None);
+    }
+
+    Ok(Some((a_presses, b_presses)))
+}
+
+// Tokens the presses of A and of B cost
+fn cost(presses: &(Int, Int), tokens: (i64, i64)) -> Result<Int, Overflow> {
+    let a_tokens = mul(&Int::from(tokens.0), &presses.0)?;
+    let b_tokens = mul(&Int::from(tokens.1), &presses.1)?;
+    add(&a_tokens, &b_tokens)
+}
+
+/// Tokens to win the prize moved by `prize_offset`, None when no whole
+/// numbers of presses reach it. `tokens` are the costs of the A and B presses
+pub fn solve(q: &Query, prize_offset: i64, tokens: (i64, i64)) -> Result<Option<Int>, Overflow> {
+    match presses(q, prize_offset, tokens)? {
+        Some(presses) => cost(&presses, tokens).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn parse_pair(line: &Line, re: &Regex, example: &str) -> Result<(i64, i64), ParseError> {
+    let caps = re
+        .captures(line.text)
+        .ok_or_else(|| line.error(line.text, &format!("a line like {:?}", example)))?;
+    let (_, [x, y]) = caps.extract();
+
+    Ok((
+        line.parse::<i64>(x, "an integer")?,
+        line.parse::<i64>(y, "an integer")?,
+    ))
+}
+
+/// Claw machines of three lines each, separated by blank lines
+pub fn parse(input: &str) -> Result<Vec<Query>, ParseError> {
+    let lines = [
+        (
+            Regex::new(r"^Button A: X\+(\d+), Y\+(\d+)$").unwrap(),
+            "Button A: X+94, Y+34",
+        ),
+        (
+            Regex::new(r"^Button B: X\+(\d+), Y\+(\d+)$").unwrap(),
+            "Button B: X+22, Y+67",
+        ),
+        (
+            Regex::new(r"^Prize: X=(\d+), Y=(\d+)$").unwrap(),
+            "Prize: X=8400, Y=5400",
+        ),
+    ];
+
+    parser::sections(input)
+        .into_iter()
+        .map(|section| {
+            let mut pairs: Vec<(i64, i64)> = Vec::new();
+
+            for (i, (re, example)) in lines.iter().enumerate() {
+                let line = match section.get(i) {
+                    Some(line) => line,
+                    None => return Err(section[i - 1].missing_next(&format!("{:?}", example))),
+                };
+                pairs.push(parse_pair(line, re, example)?);
+            }
+
+            if let Some(extra) = section.get(lines.len()) {
+                return Err(extra.error(extra.text, "a blank line between claw machines"));
+            }
+
+            Ok(Query {
+                button_a: pairs[0],
+                button_b: pairs[1],
+                prize: pairs[2],
+            })
+        })
+        .collect()
+}
+
+/// Fewest tokens to win every prize that can be won
+pub fn min_tokens(
+    queries: &[Query],
+    prize_offset: i64,
+    tokens: (i64, i64),
+) -> Result<Int, Overflow> {
+    min_tokens_traced(queries, prize_offset, tokens, &mut NoTrace)
+}
+
+/// [`min_tokens`], reporting the presses of each machine as a "won" fact or
+/// the machine as "lost" when its prize cannot be won
+pub fn min_tokens_traced<T: Trace + ?Sized>(
+    queries: &[Query],
+    prize_offset: i64,
+    tokens: (i64, i64),
+    trace: &mut T,
+) -> Result<Int, Overflow> {
+    let mut total = Int::zero();
+    for (n, q) in queries.iter().enumerate() {
+        match presses(q, prize_offset, tokens)? {
+            Some(presses) => {
+                let cost = cost(&presses, tokens)?;
+                total = add(&total, &cost)?;
+                if trace.enabled() {
+                    trace.fact(
+                        Fact::new("won")
+                            .with("machine", n + 1)
+                            .with("a", Value::number(presses.0))
+                            .with("b", Value::number(presses.1))
+                            .with("tokens", Value::number(cost)),
+                    );
+                }
+            }
+            None if trace.enabled() => trace.fact(Fact::new("lost").with("machine", n + 1)),
+            None => {}
+        }
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let queries = parse(EXAMPLE).unwrap();
+        assert_eq!(min_tokens(&queries, 0, BUTTON_TOKENS), Ok(Int::from(480)));
+    }
+
+    // The puzzle gives no part 2 answer for the example
+    #[test]
+    fn part2_example() {
+        let queries = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            min_tokens(&queries, PRIZE_OFFSET, BUTTON_TOKENS),
+            Ok(Int::from(875318608908_i64))
+        );
+    }
+
+    #[test]
+    fn presses_are_reported() {
+        let queries = parse(EXAMPLE).unwrap();
+        let mut facts: Vec<Fact> = Vec::new();
+        min_tokens_traced(&queries, 0, BUTTON_TOKENS, &mut facts).unwrap();
+
+        let lines: Vec<String> = facts
+            .iter()
+            .map(|f| {
+                let fields: Vec<String> = f.fields.iter().map(|(_, v)| v.to_string()).collect();
+                format!("{} {}", f.name, fields.join(" "))
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            ["won 1 80 40 280", "lost 2", "won 3 38 86 200", "lost 4"]
+        );
+    }
+
+    // Tries every number of A presses, B presses follow from the X axis
+    fn exhaustive(q: &Query) -> Option<Int> {
+        (0..=q.prize.0 / q.button_a.0)
+            .filter_map(|a| {
+                let rest = (q.prize.0 - a * q.button_a.0, q.prize.1 - a * q.button_a.1);
+                let b = rest.0 / q.button_b.0;
+                (b >= 0 && rest.0 % q.button_b.0 == 0 && rest.1 == b * q.button_b.1)
+                    .then(|| Int::from(BUTTON_TOKENS.0 * a + BUTTON_TOKENS.1 * b))
+            })
+            .min()
+    }
+
+    #[test]
+    fn huge_prizes_are_checked() {
+        let q = Query {
+            button_a: (94, 34),
+            button_b: (22, 67),
+            prize: (8400, 5400),
+        };
+        assert_eq!(solve(&q, 0, BUTTON_TOKENS), Ok(Some(Int::from(280))));
+
+        let result = solve(&q, i64::MAX / 2, BUTTON_TOKENS);
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(result, Err(Overflow));
+        #[cfg(feature = "bigint")]
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn buttons_along_one_line_take_the_cheapest_presses() {
+        let q = Query {
+            button_a: (1, 1),
+            button_b: (2, 2),
+            prize: (4, 4),
+        };
+        assert_eq!(
+            presses(&q, 0, BUTTON_TOKENS),
+            Ok(Some((0.into(), 2.into())))
+        );
+        assert_eq!(presses(&q, 0, (1, 5)), Ok(Some((4.into(), 0.into()))));
+        assert_eq!(solve(&q, 0, BUTTON_TOKENS), Ok(Some(Int::from(2))));
+
+        let off_line = Query { prize: (4, 5), ..q };
+        assert_eq!(solve(&off_line, 0, BUTTON_TOKENS), Ok(None));
+        let odd = Query {
+            button_a: (2, 4),
+            button_b: (4, 8),
+            prize: (5, 10),
+        };
+        assert_eq!(solve(&odd, 0, BUTTON_TOKENS), Ok(None));
+        let still = Query {
+            button_a: (0, 0),
+            button_b: (0, 3),
+            prize: (0, 9),
+        };
+        assert_eq!(
+            presses(&still, 0, BUTTON_TOKENS),
+            Ok(Some((0.into(), 3.into())))
+        );
+    }
+
+    fn button() -> impl Strategy<Value = (i64, i64)> {
+        (1..100i64, 1..100i64)
+    }
+
+    // Buttons moving along the same line, multiples of one step
+    fn same_line_buttons() -> impl Strategy<Value = ((i64, i64), (i64, i64))> {
+        ((1..20i64, 1..20i64), 1..10i64, 1..10i64)
+            .prop_map(|(step, a, b)| ((step.0 * a, step.1 * a), (step.0 * b, step.1 * b)))
+    }
+
+    proptest! {
+        #[test]
+        fn cramer_matches_exhaustive(
+            button_a in button(),
+            button_b in button(),
+            prize in (0..5000i64, 0..5000i64),
+        ) {
+            let q = Query { button_a, button_b, prize };
+            prop_assert_eq!(solve(&q, 0, BUTTON_TOKENS), Ok(exhaustive(&q)));
+        }
+
+        // Negative presses hit the lattice too, but must not count as a win
+        #[test]
+        fn cramer_finds_planted_presses(
+            button_a in button(),
+            button_b in button(),
+            presses in (-100..=100i64, -100..=100i64),
+        ) {
+            let prize = (
+                presses.0 * button_a.0 + presses.1 * button_b.0,
+                presses.0 * button_a.1 + presses.1 * button_b.1,
+            );
+            let q = Query { button_a, button_b, prize };
+            prop_assert_eq!(solve(&q, 0, BUTTON_TOKENS), Ok(exhaustive(&q)));
+        }
+
+        // Many presses reach the prizes on the line, the cheapest must win
+        #[test]
+        fn same_line_matches_exhaustive(
+            (button_a, button_b) in same_line_buttons(),
+            steps in 0..300i64,
+            nudge in 0..2i64,
+        ) {
+            let step = (button_a.0.min(button_b.0), button_a.1.min(button_b.1));
+            let prize = (steps * step.0, steps * step.1 + nudge);
+            let q = Query { button_a, button_b, prize };
+            prop_assert_eq!(solve(&q, 0, BUTTON_TOKENS), Ok(exhaustive(&q)));
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Day 14: Restroom Redoubt, robots walking across a wrapping space
+#![warn(missing_docs)]
+
+use grid::{Grid, Point};
+use parser::ParseError;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::HashSet;
+use std::ops::Range;
+use viz::{Colour, Frame, Palette, Picture, Rgb, Sink};
+
+/// Seconds the robots move for in part 1
+pub const SECONDS: i64 = 100;
+/// Width and height of the space
+pub const BOUNDARY: (i64, i64) = (101, 103);
+
+/// A robot, X is the distance from the left wall and Y from the top
+#[derive(Debug, Clone)]
+pub struct Robot {
+    /// Current tile
+    pub position: (i64, i64),
+    /// Tiles per second
+    pub velocity: (i64, i64),
+}
+
+impl Robot {
+    /// Moves the robot `t` seconds ahead, wrapping around the edges
+    pub fn run(&mut self, t: i64, boundary: (i64, i64)) {
+        self.position.0 = (self.position.0 + self.velocity.0 * t) % boundary.0;
+        self.position.1 = (self.position.1 + self.velocity.1 * t) % boundary.1;
+
+        if self.position.0 < 0 {
+            self.position.0 += boundary.0;
+        }
+
+        if self.position.1 < 0 {
+            self.position.1 += boundary.1;
+        }
+    }
+}
+
+/// Product of the number of robots in each quadrant, robots on the middle
+/// row or column count for none
+pub fn calculate_safety_factor(robots: &[Robot], boundary: (i64, i64)) -> u64 {
+    let mut quadrants: [[u64; 2]; 2] = [[0; 2]; 2];
+    let middle = (boundary.0 / 2, boundary.1 / 2);
+
+    for robot in robots {
+        if robot.position.0 != middle.0 && robot.position.1 != middle.1 {
+            quadrants[(robot.position.0 < middle.0) as usize]
+                [(robot.position.1 < middle.1) as usize] += 1;
+        }
+    }
+
+    quadrants[0][0] * quadrants[0][1] * quadrants[1][0] * quadrants[1][1]
+}
+
+/// Render the robots if the map looks like it might contain the easter egg
+pub fn render_if_possible_easter_egg(robots: &[Robot], boundary: (i64, i64)) -> Option<Vec<u8>> {
+    let (width, height) = (boundary.0 as usize, boundary.1 as usize);
+    let mut map: Vec<Vec<bool>> = vec![vec![false; width]; height];
+    let mut is_easter_egg = false;
+
+    for robot in robots {
+        map[robot.position.1 as usize][robot.position.0 as usize] = true;
+    }
+
+    let mut out: Vec<u8> = Vec::new();
+    for i in 0..height {
+        for j in 0..width {
+            out.push(if map[i][j] { b'#' } else { b'-' });
+            if map[i][j] && i + 2 < height && j + 2 < width && i >= 2 && j >= 2 {
+                // The top of christmas tree should look like ^ shape
+                if map[i + 1][j - 1] && map[i + 1][j + 1] && map[i + 2][j - 2] && map[i + 2][j + 2]
+                {
+                    // Possible Easter Egg
+                    is_easter_egg = true;
+                }
+            }
+        }
+        out.push(b'\n');
+    }
+
+    if is_easter_egg {
+        Some(out)
+    } else {
+        None
+    }
+}
+
+/// The picture is drawn at the first second when no two robots share a tile
+pub fn find_easter_egg(robots: &[Robot], boundary: (i64, i64)) -> Option<i64> {
+    let is_easter_egg = |&t: &i64| {
+        let mut positions: HashSet<(i64, i64)> = HashSet::new();
+        let mut tmp: Vec<Robot> = robots.to_vec();
+        for robot in &mut tmp {
+            robot.run(t, boundary);
+            positions.insert(robot.position);
+        }
+
+        positions.len() == robots.len() && render_if_possible_easter_egg(&tmp, boundary).is_some()
+    };
+
+    // The positions repeat after width * height seconds
+    let seconds = 0..boundary.0 * boundary.1;
+
+    // The earliest second wins, whichever thread gets there first
+    #[cfg(feature = "parallel")]
+    let second = seconds.into_par_iter().find_first(is_easter_egg);
+    #[cfg(not(feature = "parallel"))]
+    let second = seconds.into_iter().find(is_easter_egg);
+
+    second
+}
+
+// Robots on each tile after `t` seconds
+fn robot_count(robots: &[Robot], boundary: (i64, i64), t: i64) -> Grid<u8> {
+    let mut robot_count: Grid<u8> = Grid::new(boundary.1 as usize, boundary.0 as usize, 0);
+    for robot in robots {
+        let mut robot = robot.clone();
+        robot.run(t, boundary);
+        let tile =
+            &mut robot_count[Point::new(robot.position.1 as usize, robot.position.0 as usize)];
+        *tile = tile.saturating_add(1);
+    }
+    robot_count
+}
+
+/// Shows the robots second by second, a tile gets brighter the more robots share it
+pub fn animate(robots: &[Robot], boundary: (i64, i64), seconds: Range<i64>, sink: &mut dyn Sink) {
+    for t in seconds {
+        let robot_count = robot_count(robots, boundary, t);
+        let frame = Frame::new(format!("Second {}", t), &robot_count, |&n| match n {
+            0 => (' ', Colour::Plain),
+            1 => ('#', Colour::Green),
+            _ => ('#', Colour::Yellow),
+        });
+        if !sink.show(&frame) {
+            break;
+        }
+    }
+}
+
+/// The robots after `t` seconds, tiles with several robots in yellow
+pub fn picture(robots: &[Robot], boundary: (i64, i64), t: i64) -> Picture<'static> {
+    let palette = Palette::new(&[
+        (b'.', Rgb(16, 24, 32)),
+        (b'1', Rgb(80, 200, 80)),
+        (b'+', Rgb(240, 200, 60)),
+    ]);
+    let mut picture = Picture::new(boundary.1 as usize, boundary.0 as usize, palette);
+    picture.add(robot_count(robots, boundary, t).map(|&n| match n {
+        0 => b'.',
+        1 => b'1',
+        _ => b'+',
+    }));
+    picture
+}
+
+/// One robot per line, like "p=0,4 v=3,-3"
+pub fn parse(input: &str) -> Result<Vec<Robot>, ParseError> {
+    let re = Regex::new(r"^p=(\d+),(\d+)\sv=(-?\d+),(-?\d+)$").unwrap();
+
+    parser::lines(input)
+        .map(|line| {
+            let caps = re
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "a robot like p=0,4 v=3,-3"))?;
+            let (_, extracted): (&str, [&str; 4]) = caps.extract();
+
+            let mut values = [0; 4];
+            for (value, s) in values.iter_mut().zip(extracted) {
+                *value = line.parse::<i64>(s, "an integer")?;
+            }
+            let [p_x, p_y, v_x, v_y] = values;
+
+            Ok(Robot {
+                position: (p_x, p_y),
+                velocity: (v_x, v_y),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    // The example room is only 11 tiles wide and 7 tiles tall
+    const EXAMPLE_BOUNDARY: (i64, i64) = (11, 7);
+
+    #[test]
+    fn part1_example() {
+        let mut robots = parse(EXAMPLE).unwrap();
+        for robot in &mut robots {
+            robot.run(SECONDS, EXAMPLE_BOUNDARY);
+        }
+
+        assert_eq!(calculate_safety_factor(&robots, EXAMPLE_BOUNDARY), 12);
+    }
+
+    // The puzzle shows the example robots after 100 seconds
+    #[test]
+    fn robot_count_matches_the_example_drawing() {
+        let robots = parse(EXAMPLE).unwrap();
+        let drawing = robot_count(&robots, EXAMPLE_BOUNDARY, SECONDS).render(|&n| {
+            if n == 0 {
+                '.'
+            } else {
+                (b'0' + n) as char
+            }
+        });
+
+        assert_eq!(
+            drawing,
+            "......2..1.\n...........\n1..........\n.11........\n.....1.....\n...12......\n.1....1...."
+        );
+    }
+}
@@ -0,0 +1,383 @@
+//! Day 15: Warehouse Woes, a robot pushing boxes around a warehouse
+#![warn(missing_docs)]
+
+use grid::{Dir, Grid, Point};
+use parser::ParseError;
+use viz::{Colour, Frame, Sink};
+
+/// A move of the robot
+pub type Move = Dir;
+
+/// What a tile of the warehouse holds
+#[derive(Debug, PartialEq, Clone)]
+pub enum Cell {
+    /// Wall
+    WALL,
+    /// Box of the normal warehouse
+    BOX,
+    /// Empty floor
+    BLANK,
+    /// The robot
+    PLAYER,
+    /// Left half of a box of the double wide warehouse
+    DBoxL,
+    /// Right half of a box of the double wide warehouse
+    DBoxR,
+}
+
+/// A warehouse the robot moves in
+pub trait MapSimulation {
+    /// Moves the robot, pushing boxes unless one would hit a wall, and returns
+    /// where the robot is
+    fn simulate(&mut self, direction: Move) -> Point;
+    /// Sum of the GPS coordinates of the boxes, 100 times the row plus the column
+    fn boxes_sum(&self) -> usize;
+    /// The tiles of the warehouse
+    fn map(&self) -> &Grid<Cell>;
+}
+
+/// The warehouse as given
+#[derive(Debug, Clone)]
+pub struct NormalMap {
+    /// Tiles, row by row
+    pub map: Grid<Cell>,
+    /// Where the robot is
+    pub player: Point,
+}
+
+/// The warehouse with everything but the robot twice as wide
+#[derive(Debug, Clone)]
+pub struct DoubleWideMap {
+    /// Tiles, row by row
+    pub map: Grid<Cell>,
+    /// Where the robot is
+    pub player: Point,
+}
+
+// The map is surrounded by walls, so a move never leaves it
+fn next(pos: Point, dir: Dir) -> Point {
+    pos.step(dir).expect("the map is surrounded by walls")
+}
+
+fn cell_char(cell: &Cell) -> char {
+    match cell {
+        Cell::BLANK => '.',
+        Cell::BOX => 'O',
+        Cell::PLAYER => '@',
+        Cell::WALL => '#',
+        Cell::DBoxL => '[',
+        Cell::DBoxR => ']',
+    }
+}
+
+fn cell_colour(cell: &Cell) -> Colour {
+    match cell {
+        Cell::WALL => Colour::Grey,
+        Cell::BOX | Cell::DBoxL | Cell::DBoxR => Colour::Yellow,
+        Cell::PLAYER => Colour::Red,
+        Cell::BLANK => Colour::Plain,
+    }
+}
+
+/// Shows the warehouse after every move of the robot
+pub fn animate(simulation: &mut dyn MapSimulation, moves: &[Move], sink: &mut dyn Sink) {
+    let frame = |title: String, map: &Grid<Cell>| {
+        Frame::new(title, map, |cell| (cell_char(cell), cell_colour(cell)))
+    };
+
+    if !sink.show(&frame("Start".to_string(), simulation.map())) {
+        return;
+    }
+
+    for (i, &direction) in moves.iter().enumerate() {
+        simulation.simulate(direction);
+
+        let title = format!(
+            "Move {}/{}: {}  GPS sum: {}",
+            i + 1,
+            moves.len(),
+            viz::arrow(direction),
+            simulation.boxes_sum()
+        );
+        if !sink.show(&frame(title, simulation.map())) {
+            return;
+        }
+    }
+}
+
+impl DoubleWideMap {
+    /// Widens a warehouse
+    pub fn from_normal_map(map: &NormalMap) -> DoubleWideMap {
+        let mut dmap: Vec<Vec<Cell>> = Vec::new();
+
+        for row in map.map.rows() {
+            let mut drow: Vec<Cell> = Vec::new();
+            for cell in row {
+                match &cell {
+                    Cell::BOX => {
+                        drow.push(Cell::DBoxL);
+                        drow.push(Cell::DBoxR);
+                    }
+                    Cell::PLAYER => {
+                        drow.push(Cell::PLAYER);
+                        drow.push(Cell::BLANK);
+                    }
+                    _ => {
+                        drow.push(cell.clone());
+                        drow.push(cell.clone());
+                    }
+                }
+            }
+
+            dmap.push(drow);
+        }
+
+        DoubleWideMap {
+            map: Grid::from_rows(dmap),
+            player: Point::new(map.player.i, map.player.j * 2),
+        }
+    }
+}
+
+impl MapSimulation for NormalMap {
+    fn simulate(&mut self, direction: Move) -> Point {
+        assert_eq!(self.map[self.player], Cell::PLAYER);
+
+        let mut curr_pos = self.player;
+        while self.map[curr_pos] != Cell::WALL {
+            if self.map[curr_pos] == Cell::BLANK {
+                // Not blocked by a wall, then shift
+                while curr_pos != self.player {
+                    let new_pos = next(curr_pos, direction.reverse());
+                    self.map[curr_pos] = self.map[new_pos].clone();
+                    curr_pos = new_pos;
+                }
+
+                self.map[self.player] = Cell::BLANK;
+                self.player = next(self.player, direction);
+                return self.player;
+            }
+
+            curr_pos = next(curr_pos, direction);
+        }
+
+        self.player
+    }
+
+    fn boxes_sum(&self) -> usize {
+        self.map.find_all(Cell::BOX).map(|p| p.i * 100 + p.j).sum()
+    }
+
+    fn map(&self) -> &Grid<Cell> {
+        &self.map
+    }
+}
+
+impl DoubleWideMap {
+    // Find all affected boxes in case of UP/DOWN move
+    // Return None if the move is impossible
+    fn affected_boxes(&self, lbox_pos: Point, direction: Move) -> Option<Vec<Point>> {
+        let next_lpos = next(lbox_pos, direction);
+        let next_rpos = next(next_lpos, Dir::RIGHT);
+
+        let mut all_affected_boxes: Vec<Point> = vec![lbox_pos];
+
+        // If the cells above the box are blank, then this move might be possible
+        if self.map[next_lpos] == Cell::BLANK && self.map[next_rpos] == Cell::BLANK {
+            return Some(all_affected_boxes);
+        }
+        // If a box faces a wall, then this move is impossible
+        else if self.map[next_lpos] == Cell::WALL || self.map[next_rpos] == Cell::WALL {
+            // Impossible Move
+            return None;
+        }
+        // The above box is directly above/below the current box
+        else if self.map[next_lpos] == Cell::DBoxL {
+            // Propagate New Box
+            let boxes = self.affected_boxes(next_lpos, direction)?;
+            all_affected_boxes.extend(boxes);
+        }
+        // If above/below box is a bit off
+        else {
+            // Propagate New Box on the left
+            if self.map[next_lpos] == Cell::DBoxR {
+                let boxes = self.affected_boxes(next(next_lpos, Dir::LEFT), direction)?;
+                all_affected_boxes.extend(boxes);
+            }
+
+            // Propagate New Box on the right
+            if self.map[next_rpos] == Cell::DBoxL {
+                let boxes = self.affected_boxes(next_rpos, direction)?;
+                all_affected_boxes.extend(boxes);
+            }
+        }
+
+        Some(all_affected_boxes)
+    }
+}
+
+impl MapSimulation for DoubleWideMap {
+    fn simulate(&mut self, direction: Move) -> Point {
+        assert_eq!(self.map[self.player], Cell::PLAYER);
+
+        if direction.is_horizontal() {
+            let mut curr_pos = self.player;
+            while self.map[curr_pos] != Cell::WALL {
+                if self.map[curr_pos] == Cell::BLANK {
+                    // Not blocked by a wall, then shift
+                    while curr_pos != self.player {
+                        let new_pos = next(curr_pos, direction.reverse());
+                        self.map[curr_pos] = self.map[new_pos].clone();
+                        curr_pos = new_pos;
+                    }
+
+                    self.map[self.player] = Cell::BLANK;
+                    self.player = next(self.player, direction);
+                    return self.player;
+                }
+
+                curr_pos = next(curr_pos, direction);
+            }
+        } else {
+            let next_pos = next(self.player, direction);
+
+            if self.map[next_pos] == Cell::BLANK {
+                self.map[self.player] = Cell::BLANK;
+                self.map[next_pos] = Cell::PLAYER;
+
+                self.player = next_pos;
+            } else if self.map[next_pos] != Cell::WALL {
+                // The player faces boxes
+                let lbox_pos = if self.map[next_pos] == Cell::DBoxL {
+                    next_pos
+                } else {
+                    next(next_pos, Dir::LEFT)
+                };
+
+                let boxes = self.affected_boxes(lbox_pos, direction);
+
+                if let Some(mut boxes_to_be_moved) = boxes {
+                    // If dir = UP, move the boxes from the most top first
+                    boxes_to_be_moved.sort();
+
+                    if direction == Move::DOWN {
+                        // If dir = DOWN, move the boxes from the bottom first
+                        boxes_to_be_moved.reverse();
+                    }
+
+                    for lbox in boxes_to_be_moved {
+                        let new_lbox = next(lbox, direction);
+
+                        self.map[new_lbox] = Cell::DBoxL;
+                        self.map[next(new_lbox, Dir::RIGHT)] = Cell::DBoxR;
+                        self.map[lbox] = Cell::BLANK;
+                        self.map[next(lbox, Dir::RIGHT)] = Cell::BLANK;
+                    }
+
+                    self.map[self.player] = Cell::BLANK;
+                    self.map[next_pos] = Cell::PLAYER;
+
+                    self.player = next_pos;
+                }
+            }
+        }
+
+        self.player
+    }
+
+    fn boxes_sum(&self) -> usize {
+        let mut sum = 0;
+
+        for p in self.map.find_all(Cell::DBoxL) {
+            let vertical_distance = p.i;
+            let horizontal_distance = p.j;
+
+            sum += vertical_distance * 100 + horizontal_distance;
+        }
+
+        sum
+    }
+
+    fn map(&self) -> &Grid<Cell> {
+        &self.map
+    }
+}
+
+/// The warehouse, a blank line and the moves
+pub fn parse(input: &str) -> Result<(NormalMap, Vec<Move>), ParseError> {
+    let mut moves: Vec<Move> = Vec::new();
+
+    let sections = parser::sections(input);
+    if sections.len() < 2 {
+        return Err(parser::end_of_input(
+            input,
+            "a map and a list of moves separated by a blank line",
+        ));
+    }
+
+    let cells = Grid::from_lines(
+        sections[0].iter().copied(),
+        |c| b"#O.@".contains(&c),
+        "'#', 'O', '.' or '@'",
+    )?;
+
+    let Some(player) = cells.find(b'@') else {
+        return Err(sections[0]
+            .last()
+            .unwrap()
+            .missing_next("a robot '@' on the map"));
+    };
+
+    let map = NormalMap {
+        map: cells.map(|&c| match c {
+            b'#' => Cell::WALL,
+            b'O' => Cell::BOX,
+            b'.' => Cell::BLANK,
+            _ => Cell::PLAYER,
+        }),
+        player,
+    };
+
+    for line in sections[1..].iter().flatten() {
+        for (j, c) in line.text.char_indices() {
+            moves.push(match c {
+                '^' => Move::UP,
+                'v' => Move::DOWN,
+                '<' => Move::LEFT,
+                '>' => Move::RIGHT,
+                _ => return Err(line.error(&line.text[j..j + c.len_utf8()], "a move ^, v, < or >")),
+            });
+        }
+    }
+
+    Ok((map, moves))
+}
+
+/// Runs every move and returns the sum of the GPS coordinates of the boxes
+pub fn simulate_all(map: &mut dyn MapSimulation, moves: &[Move]) -> usize {
+    for m in moves {
+        map.simulate(*m);
+    }
+
+    map.boxes_sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let (mut map, moves) = parse(EXAMPLE).unwrap();
+        assert_eq!(simulate_all(&mut map, &moves), 10092);
+    }
+
+    #[test]
+    fn part2_example() {
+        let (map, moves) = parse(EXAMPLE).unwrap();
+        let mut dmap = DoubleWideMap::from_normal_map(&map);
+        assert_eq!(simulate_all(&mut dmap, &moves), 9021);
+    }
+}
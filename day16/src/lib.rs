@@ -0,0 +1,232 @@
+//! Day 16: Reindeer Maze, the lowest score from start to end and the tiles on best paths
+#![warn(missing_docs)]
+
+use algo::{Graph, ShortestPaths};
+use grid::{Dir, Grid, Point};
+use parser::ParseError;
+use std::collections::HashSet;
+use viz::{Colour, Frame, Palette, Picture, Rgb, Sink};
+
+/// Where the reindeer faces
+pub type Rotation = Dir;
+
+/// What moving the reindeer adds to its score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Costs {
+    /// A step forward, at least 1
+    pub step: u64,
+    /// A quarter turn on the spot
+    pub turn: u64,
+}
+
+/// The costs of the puzzle
+pub const COSTS: Costs = Costs {
+    step: 1,
+    turn: 1000,
+};
+
+const DIRECTIONS: [Rotation; 4] = [Dir::UP, Dir::DOWN, Dir::LEFT, Dir::RIGHT];
+
+// Positions of the reindeer, each step forward costing a step and each quarter
+// turn taken with the step after it
+struct Maze<'a> {
+    map: &'a Grid<u8>,
+    costs: Costs,
+}
+
+impl Graph for Maze<'_> {
+    type Node = (Point, Rotation);
+
+    fn node_count(&self) -> usize {
+        self.map.height() * self.map.width() * 4
+    }
+
+    fn index(&self, (pos, rotation): (Point, Rotation)) -> usize {
+        (pos.i * self.map.width() + pos.j) * 4 + rotation as usize
+    }
+
+    fn edges(
+        &self,
+        (pos, rotation): (Point, Rotation),
+    ) -> impl Iterator<Item = ((Point, Rotation), u64)> {
+        DIRECTIONS.into_iter().filter_map(move |rot| {
+            let new_score = if rot == rotation {
+                self.costs.step
+            }
+            // 90 deg turn
+            else if rot.is_horizontal() != rotation.is_horizontal() {
+                self.costs.turn + self.costs.step
+            }
+            // 180 deg turn not allowed
+            else {
+                return None;
+            };
+
+            let new_pos = self.map.step(pos, rot)?;
+            (self.map[new_pos] == b'.' || self.map[new_pos] == b'E')
+                .then_some(((new_pos, rot), new_score))
+        })
+    }
+}
+
+/// Lowest score from `start` to the 'E' tile and the number of tiles on any
+/// path with that score. None when the end cannot be reached
+pub fn bfs(map: &Grid<u8>, start: (Point, Rotation), costs: Costs) -> Option<(u64, usize)> {
+    bfs_with(map, start, costs, |_, _| true).map(|(score, tiles)| (score, tiles.len()))
+}
+
+// Calls `observe` with the search so far and the position taken off the queue,
+// the search gives up when it returns false
+fn bfs_with(
+    map: &Grid<u8>,
+    start: (Point, Rotation),
+    costs: Costs,
+    mut observe: impl FnMut(&ShortestPaths<Maze>, Point) -> bool,
+) -> Option<(u64, HashSet<Point>)> {
+    let maze = Maze { map, costs };
+    let paths = algo::shortest_paths_with(
+        &maze,
+        [start],
+        |(pos, _)| map[pos] == b'E',
+        |paths, (pos, _)| observe(paths, pos),
+    )?;
+
+    // Tiles on any of the best paths
+    let tiles: HashSet<Point> = paths
+        .on_best_paths()
+        .into_iter()
+        .map(|(pos, _)| pos)
+        .collect();
+    paths.best().map(|score| (score, tiles))
+}
+
+/// A maze of '#' walls and '.' tiles with a start 'S' and an end 'E'
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let map = Grid::parse(input, |c| b"#.SE".contains(&c), "'#', '.', 'S' or 'E'")?;
+
+    for tile in [b'S', b'E'] {
+        if map.find(tile).is_none() {
+            return Err(parser::end_of_input(
+                input,
+                &format!("a '{}' tile on the map", tile as char),
+            ));
+        }
+    }
+
+    Ok(map)
+}
+
+// Positions taken off the queue between two frames of the animation
+const POPS_PER_FRAME: usize = 20;
+
+fn search_frame(title: String, map: &Grid<u8>, reached: impl Fn(Point) -> bool) -> Frame {
+    let mut frame = Frame::new(title, map, |&c| match c {
+        b'#' => ('#', Colour::Grey),
+        b'S' | b'E' => (c as char, Colour::Red),
+        _ => (' ', Colour::Plain),
+    });
+
+    for p in map.points() {
+        if map[p] == b'.' && reached(p) {
+            frame.set(p, '.', Colour::Blue);
+        }
+    }
+
+    frame
+}
+
+/// Shows the tiles reached by Dijkstra's search as it goes, then the best paths
+pub fn animate_search(map: &Grid<u8>, start: (Point, Rotation), costs: Costs, sink: &mut dyn Sink) {
+    let mut pops: usize = 0;
+    let mut searched: Option<Frame> = None;
+
+    let result = bfs_with(map, start, costs, |paths, pos| {
+        pops += 1;
+        if !pops.is_multiple_of(POPS_PER_FRAME) {
+            return true;
+        }
+
+        let reached = |p| DIRECTIONS.iter().any(|&rot| paths.cost((p, rot)).is_some());
+        let frame = search_frame(format!("Searched {} positions", pops), map, reached);
+        let mut current = frame.clone();
+        current.set(pos, '@', Colour::Yellow);
+        searched = Some(frame);
+
+        sink.show(&current)
+    });
+
+    let Some((score, tiles)) = result else {
+        return;
+    };
+
+    let mut frame = searched.unwrap_or_else(|| search_frame(String::new(), map, |_| false));
+    frame.title = format!("Best score {}, {} tiles on a best path", score, tiles.len());
+    for p in tiles {
+        frame.set(p, 'O', Colour::Green);
+    }
+    sink.show(&frame);
+}
+
+/// The maze with the tiles of every best path in green
+pub fn picture(map: &Grid<u8>, start: (Point, Rotation), costs: Costs) -> Picture<'_> {
+    let palette = Palette::new(&[
+        (b'#', Rgb(60, 60, 70)),
+        (b'.', Rgb(245, 245, 245)),
+        (b'S', Rgb(220, 50, 47)),
+        (b'E', Rgb(220, 50, 47)),
+        (b'O', Rgb(80, 180, 80)),
+    ]);
+    let mut picture = Picture::new(map.height(), map.width(), palette);
+
+    let mut path: Grid<Option<u8>> = Grid::new(map.height(), map.width(), None);
+    if let Some((_, tiles)) = bfs_with(map, start, costs, |_, _| true) {
+        for p in tiles.into_iter().filter(|&p| map[p] == b'.') {
+            path[p] = Some(b'O');
+        }
+    }
+
+    picture.add(map);
+    picture.add(path);
+    picture
+}
+
+/// The reindeer starts on the 'S' tile facing east
+pub fn start_position(map: &Grid<u8>) -> (Point, Rotation) {
+    (map.find(b'S').unwrap(), Rotation::RIGHT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+    const SMALL_EXAMPLE: &str = include_str!("../test2.txt");
+
+    #[test]
+    fn part1_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(bfs(&map, start_position(&map), COSTS).unwrap().0, 11048);
+
+        let map = parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(bfs(&map, start_position(&map), COSTS).unwrap().0, 7036);
+    }
+
+    #[test]
+    fn part2_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(bfs(&map, start_position(&map), COSTS).unwrap().1, 64);
+
+        let map = parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(bfs(&map, start_position(&map), COSTS).unwrap().1, 45);
+    }
+
+    #[test]
+    fn scaled_costs_scale_the_score() {
+        let map = parse(EXAMPLE).unwrap();
+        let doubled = Costs {
+            step: 2,
+            turn: 2000,
+        };
+        assert_eq!(bfs(&map, start_position(&map), doubled), Some((22096, 64)));
+    }
+}
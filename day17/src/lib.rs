@@ -0,0 +1,263 @@
+//! Day 17: Chronospatial Computer, a 3-bit computer and the program that outputs itself
+#![warn(missing_docs)]
+
+use parser::ParseError;
+
+/// The 3-bit computer, with its three registers and instruction pointer
+pub struct Computer {
+    reg_a: i64,
+    reg_b: i64,
+    reg_c: i64,
+    instructions: Vec<u8>,
+    rip: usize,
+}
+
+impl Computer {
+    /// A computer about to run `instructions`, opcodes and operands alternating
+    pub fn new(reg_a: i64, reg_b: i64, reg_c: i64, instructions: Vec<u8>) -> Computer {
+        Computer {
+            reg_a,
+            reg_b,
+            reg_c,
+            instructions,
+            rip: 0,
+        }
+    }
+
+    /// Runs the program until the instruction pointer leaves it and returns what
+    /// the `out` instructions printed
+    #[allow(clippy::collapsible_match)]
+    pub fn execute(&mut self) -> Vec<u8> {
+        let mut outputs: Vec<u8> = Vec::new();
+
+        while self.rip < self.instructions.len() {
+            let opcode = self.instructions[self.rip];
+            let operand = self.instructions[self.rip + 1];
+
+            match opcode {
+                0 => self.adv(operand),
+                1 => self.bxl(operand),
+                2 => self.bst(operand),
+                3 => {
+                    if self.jnz(operand) {
+                        continue;
+                    }
+                }
+                4 => self.bxc(operand),
+                5 => {
+                    outputs.push(self.out(operand));
+                }
+                6 => self.bdv(operand),
+                7 => self.cdv(operand),
+                _ => {
+                    // Invalid Opcode
+                }
+            }
+
+            self.rip += 2;
+        }
+
+        outputs
+    }
+
+    fn get_combo_operand(&self, op: u8) -> i64 {
+        match op {
+            4 => self.reg_a,
+            5 => self.reg_b,
+            6 => self.reg_c,
+            _ => op as i64,
+        }
+    }
+
+    fn div(&self, operand: u8) -> i64 {
+        let numerator = self.reg_a;
+        let denominator = 1 << (self.get_combo_operand(operand));
+
+        numerator / denominator
+    }
+
+    fn adv(&mut self, operand: u8) {
+        self.reg_a = self.div(operand);
+    }
+
+    fn bdv(&mut self, operand: u8) {
+        self.reg_b = self.div(operand);
+    }
+
+    fn cdv(&mut self, operand: u8) {
+        self.reg_c = self.div(operand);
+    }
+
+    fn bxl(&mut self, operand: u8) {
+        self.reg_b ^= operand as i64;
+    }
+
+    fn bst(&mut self, operand: u8) {
+        self.reg_b = self.get_combo_operand(operand) % 8;
+    }
+
+    // Ruturn whether successful jump
+    fn jnz(&mut self, operand: u8) -> bool {
+        if self.reg_a != 0 {
+            self.rip = operand as usize;
+            return true;
+        }
+
+        false
+    }
+
+    fn bxc(&mut self, _operand: u8) {
+        self.reg_b ^= self.reg_c;
+    }
+
+    fn out(&self, operand: u8) -> u8 {
+        (self.get_combo_operand(operand) % 8) as u8
+    }
+}
+
+/*
+For each value in instruction, we can brute-force to find the associating 4 * 3 bits whose result matches the target value.
+
+Instructions: [2, 4, 1, 1, 7, 5, 4, 6, 0, 3, 1, 4, 5, 5, 3, 0]
+2,4 => B = A % 8
+1,1 => B = B ^ 1
+7,5 => C = A / (1 << B) => C = A >> B
+4,6 => B = B ^ C
+
+0,3 => A = A / 8
+
+1,4 => B = B ^ 4
+5,5 => print(B % 8)
+
+3,0 => If A > 0, Start Over
+*/
+
+/// Returns minimum initial value of register A for which the program outputs
+/// itself, starting with `find_valid_reg_a(&program, 0, 0)`. The search is
+/// specialised to the program above, others are unlikely to find a value
+pub fn find_valid_reg_a(targets: &[u8], depth: usize, reg_a: u64) -> Option<u64> {
+    if depth >= 4 {
+        let target = targets[depth - 4];
+        let current_reg_a = reg_a >> (3 * (depth - 4));
+        let mut reg_b = current_reg_a % 8;
+        reg_b ^= 1;
+        assert!(reg_b < 8, "reg_b must less than 8");
+        let reg_c = current_reg_a >> reg_b;
+        reg_b ^= reg_c;
+        reg_b ^= 4;
+        reg_b %= 8;
+
+        if target != reg_b as u8 {
+            return None;
+        }
+
+        if depth - 4 + 1 >= targets.len() {
+            if target == reg_b as u8 {
+                // Check Validity
+                let mut computer = Computer::new(reg_a as i64, 0, 0, targets.to_vec());
+                let out = computer.execute();
+                if out == targets {
+                    // Valid Value
+                    return Some(reg_a);
+                }
+            }
+            // Not Valid
+            return None;
+        }
+    }
+
+    let mut min_reg_a: Option<u64> = None;
+    for i in 0..8 {
+        let new_reg_a = (i << (3 * depth)) + reg_a;
+        let result = find_valid_reg_a(targets, depth + 1, new_reg_a);
+        if let Some(result) = result {
+            // There is a possible initial reg_a, select the minimum value
+            min_reg_a = Some(min_reg_a.map_or(result, |min| min.min(result)));
+        }
+    }
+
+    min_reg_a
+}
+
+/// The registers and program of the puzzle input
+#[derive(Debug)]
+pub struct Device {
+    /// Initial value of register A
+    pub reg_a: i64,
+    /// Initial value of register B
+    pub reg_b: i64,
+    /// Initial value of register C
+    pub reg_c: i64,
+    /// Opcodes and operands, 3 bits each
+    pub program: Vec<u8>,
+}
+
+/// Three "Register X: N" lines, a blank line and the program
+pub fn parse(input: &str) -> Result<Device, ParseError> {
+    let sections = parser::sections(input);
+    if sections.len() < 2 {
+        return Err(parser::end_of_input(
+            input,
+            "registers and a program separated by a blank line",
+        ));
+    }
+    if let Some(extra) = sections[0].get(3).or(sections[1].get(1)) {
+        return Err(extra.error(extra.text, "a blank line"));
+    }
+    if let Some(extra) = sections.get(2) {
+        return Err(extra[0].error(extra[0].text, "the end of input"));
+    }
+
+    let mut registers: Vec<i64> = Vec::new();
+    for (i, name) in ["A", "B", "C"].iter().enumerate() {
+        let prefix = format!("Register {}: ", name);
+        let line = match sections[0].get(i) {
+            Some(line) => line,
+            None => return Err(sections[0][i - 1].missing_next(&format!("{:?}", prefix))),
+        };
+        let value = line
+            .text
+            .strip_prefix(&prefix)
+            .ok_or_else(|| line.error(line.text, &format!("a line starting with {:?}", prefix)))?;
+        registers.push(line.parse::<i64>(value, "an integer")?);
+    }
+
+    let line = sections[1][0];
+    let values = line
+        .text
+        .strip_prefix("Program: ")
+        .ok_or_else(|| line.error(line.text, "a line starting with \"Program: \""))?;
+    let mut program: Vec<u8> = Vec::new();
+    for value in values.split(",") {
+        match value.parse::<u8>() {
+            Ok(v) if v < 8 => program.push(v),
+            _ => return Err(line.error(value, "a 3-bit number")),
+        }
+    }
+
+    if !program.len().is_multiple_of(2) {
+        return Err(line.error_at_end("an operand after the last opcode"));
+    }
+
+    Ok(Device {
+        reg_a: registers[0],
+        reg_b: registers[1],
+        reg_c: registers[2],
+        program,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    // find_valid_reg_a is specialised to the shape of the real program, so only part 1 is checked
+    #[test]
+    fn part1_example() {
+        let device = parse(EXAMPLE).unwrap();
+        let mut computer = Computer::new(device.reg_a, device.reg_b, device.reg_c, device.program);
+        assert_eq!(computer.execute(), vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+    }
+}
@@ -0,0 +1,151 @@
+//! Day 18: RAM Run, escaping a memory space as bytes fall into it
+#![warn(missing_docs)]
+
+use algo::GridGraph;
+use grid::{Grid, Point};
+use parser::ParseError;
+use viz::{Palette, Picture, Rgb};
+
+/// Height and width of the memory space, every size is (rows, columns)
+pub const MAP_SIZE: (usize, usize) = (71, 71);
+/// Bytes fallen by the time part 1 looks for a path
+pub const FIRST_KILOBYTE: usize = 1024;
+
+/// Coords are (x, y), i.e. (column, row). Returns the fewest steps from the top
+/// left corner to the bottom right one around the corrupted `coords`, None when
+/// they cut the exit off
+pub fn simulate(coords: &[(usize, usize)], size: (usize, usize)) -> Option<usize> {
+    shortest_path(coords, size).map(|path| path.len() - 1)
+}
+
+/// Tiles from the top left corner to the exit, both included
+pub fn shortest_path(coords: &[(usize, usize)], size: (usize, usize)) -> Option<Vec<Point>> {
+    let map = corrupted(coords, size);
+    let (height, width) = size;
+    let start = Point::new(0, 0);
+    let exit = Point::new(height - 1, width - 1);
+    if map[start] == b'#' {
+        return None;
+    }
+
+    let memory = GridGraph::new(&map, |_, to| map[to] != b'#');
+    algo::bfs_until(&memory, start, |p| p == exit).path(exit)
+}
+
+fn corrupted(coords: &[(usize, usize)], size: (usize, usize)) -> Grid<u8> {
+    let mut map = Grid::new(size.0, size.1, b'.');
+    for &(x, y) in coords {
+        map[Point::new(y, x)] = b'#';
+    }
+    map
+}
+
+/// The first `fallen` bytes with the shortest path. When the last of them cuts
+/// the exit off it is drawn in red over the path it blocked
+pub fn picture(coords: &[(usize, usize)], size: (usize, usize), fallen: usize) -> Picture<'static> {
+    let palette = Palette::new(&[
+        (b'#', Rgb(60, 60, 70)),
+        (b'.', Rgb(245, 245, 245)),
+        (b'O', Rgb(80, 180, 80)),
+        (b'X', Rgb(220, 50, 47)),
+    ]);
+    let mut picture = Picture::new(size.0, size.1, palette);
+
+    let fallen = &coords[..fallen.min(coords.len())];
+    let mut marks: Grid<Option<u8>> = Grid::new(size.0, size.1, None);
+    let mut path = shortest_path(fallen, size);
+    if path.is_none() {
+        if let Some((&(x, y), before)) = fallen.split_last() {
+            marks[Point::new(y, x)] = Some(b'X');
+            path = shortest_path(before, size);
+        }
+    }
+    for p in path.into_iter().flatten() {
+        marks[p] = marks[p].or(Some(b'O'));
+    }
+
+    picture.add(corrupted(fallen, size));
+    picture.add(marks);
+    picture
+}
+
+/// One "X,Y" coordinate per line, inside a memory space of `size`
+pub fn parse(input: &str, size: (usize, usize)) -> Result<Vec<(usize, usize)>, ParseError> {
+    let (height, width) = size;
+
+    parser::lines(input)
+        .map(|line| {
+            let (x, y) = line.split_once(",", "a coordinate like 5,4")?;
+            let x = line.parse::<usize>(x, "a coordinate")?;
+            let y = line.parse::<usize>(y, "a coordinate")?;
+            if x >= width || y >= height {
+                return Err(line.error(
+                    line.text,
+                    &format!("a coordinate inside the {}x{} memory space", width, height),
+                ));
+            }
+            Ok((x, y))
+        })
+        .collect()
+}
+
+/// Returns the index of the first coord that blocks the path to the exit
+pub fn find_first_blocking_coord(
+    coords: &[(usize, usize)],
+    size: (usize, usize),
+    start: usize,
+) -> Option<usize> {
+    for i in start..=coords.len() {
+        if simulate(&coords[..i], size).is_none() {
+            return Some(i - 1);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    // The example memory space is 7x7 and only the first 12 bytes fall for part 1
+    const EXAMPLE_SIZE: (usize, usize) = (7, 7);
+    const EXAMPLE_BYTES: usize = 12;
+
+    #[test]
+    fn part1_example() {
+        let coords = parse(EXAMPLE, EXAMPLE_SIZE).unwrap();
+        assert_eq!(simulate(&coords[..EXAMPLE_BYTES], EXAMPLE_SIZE), Some(22));
+    }
+
+    #[test]
+    fn part2_example() {
+        let coords = parse(EXAMPLE, EXAMPLE_SIZE).unwrap();
+        let i = find_first_blocking_coord(&coords, EXAMPLE_SIZE, EXAMPLE_BYTES).unwrap();
+        assert_eq!(coords[i], (6, 1));
+    }
+
+    #[test]
+    fn shortest_path_steps_between_free_neighbours() {
+        let coords = parse(EXAMPLE, EXAMPLE_SIZE).unwrap();
+        let fallen = &coords[..EXAMPLE_BYTES];
+        let path = shortest_path(fallen, EXAMPLE_SIZE).unwrap();
+
+        assert_eq!(path.first(), Some(&Point::new(0, 0)));
+        assert_eq!(path.last(), Some(&Point::new(6, 6)));
+        for pair in path.windows(2) {
+            let (di, dj) = pair[0].delta(pair[1]);
+            assert_eq!(di.abs() + dj.abs(), 1);
+        }
+        assert!(path.iter().all(|p| !fallen.contains(&(p.j, p.i))));
+    }
+
+    #[test]
+    fn bytes_outside_the_memory_space_are_rejected() {
+        assert!(parse("6,6", EXAMPLE_SIZE).is_ok());
+        let e = parse("5,4\n7,0", EXAMPLE_SIZE).unwrap_err();
+        assert_eq!(e.line, 2);
+    }
+}
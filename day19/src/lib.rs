@@ -0,0 +1,97 @@
+//! Day 19: Linen Layout, arranging towel designs from striped patterns
+#![warn(missing_docs)]
+
+use parser::ParseError;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Number of ways to make the design `query` by concatenating patterns, each
+/// pattern can be used any number of times
+pub fn count_arrangements(patterns: &[String], query: &str) -> u64 {
+    let mut dp: Vec<u64> = vec![0; query.len() + 1];
+
+    dp[0] = 1;
+    for i in 1..query.len() + 1 {
+        for pat in patterns.iter() {
+            if i >= pat.len() && query[(i - pat.len())..i].starts_with(pat.as_str()) {
+                dp[i] += dp[i - pat.len()];
+            }
+        }
+    }
+
+    dp[query.len()]
+}
+
+/// Ways to arrange each design, in the order of the designs
+pub fn count_all(patterns: &[String], queries: &[String]) -> Vec<u64> {
+    #[cfg(feature = "parallel")]
+    let queries = queries.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let queries = queries.iter();
+
+    queries
+        .map(|query| count_arrangements(patterns, query))
+        .collect()
+}
+
+fn is_towel(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// The patterns on the first line, separated by ", ", then a blank line and
+/// one design per line
+pub fn parse(input: &str) -> Result<(Vec<String>, Vec<String>), ParseError> {
+    let mut line_iter = parser::lines(input);
+
+    let line = line_iter
+        .next()
+        .ok_or_else(|| parser::end_of_input(input, "a list of towel patterns"))?;
+    let mut patterns: Vec<String> = Vec::new();
+    for pattern in line.text.split(", ") {
+        if !is_towel(pattern) {
+            return Err(line.error(pattern, "a towel pattern of lowercase letters"));
+        }
+        patterns.push(pattern.to_string());
+    }
+
+    if let Some(separator) = line_iter.next().filter(|l| !l.is_empty()) {
+        return Err(separator.error(separator.text, "a blank line after the patterns"));
+    }
+
+    let mut queries: Vec<String> = Vec::new();
+    for line in line_iter {
+        if !is_towel(line.text) {
+            return Err(line.error(line.text, "a design of lowercase letters"));
+        }
+        queries.push(line.text.to_string());
+    }
+
+    Ok((patterns, queries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let (patterns, designs) = parse(EXAMPLE).unwrap();
+        let possible = designs
+            .iter()
+            .filter(|design| count_arrangements(&patterns, design) > 0)
+            .count();
+        assert_eq!(possible, 6);
+    }
+
+    #[test]
+    fn part2_example() {
+        let (patterns, designs) = parse(EXAMPLE).unwrap();
+        let arrangements: u64 = designs
+            .iter()
+            .map(|design| count_arrangements(&patterns, design))
+            .sum();
+        assert_eq!(arrangements, 16);
+    }
+}
@@ -0,0 +1,240 @@
+//! Day 2: Red-Nosed Reports, checking that levels change gradually
+#![warn(missing_docs)]
+
+/// Configurable safety rules, checked in O(n·k) for k removable levels
+pub mod rule;
+
+use parser::ParseError;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use rule::{Direction, SafetyRule, SafetyVerdict, Violation};
+use trace::{Fact, NoTrace, Trace};
+
+/// Levels all increase or all decrease by 1 to 3. With `tolerant`, a report
+/// is also safe if removing any single level makes it so
+pub fn is_safe(arr: &[u64], tolerant: bool) -> bool {
+    rule(tolerant).is_safe(arr)
+}
+
+/// How a report is safe with one level removed: `Some(None)` if it is safe as
+/// it is, `Some(Some(i))` if it is once level `i` is removed, `None` if it is not
+pub fn removal(arr: &[u64]) -> Option<Option<usize>> {
+    SafetyRule::TOLERANT
+        .removal(arr)
+        .map(|removed| removed.first().copied())
+}
+
+/// What is wrong with an unsafe report, or None for a safe one. Levels are
+/// numbered from 1 and shown with their values
+pub fn annotate(arr: &[u64], verdict: &SafetyVerdict) -> Option<String> {
+    let pair = |v: &Violation| {
+        format!(
+            "levels {} and {} ({} then {}) {}",
+            v.at + 1,
+            v.at + 2,
+            arr[v.at],
+            arr[v.at + 1],
+            v.reason
+        )
+    };
+
+    match verdict {
+        SafetyVerdict::Safe => None,
+        SafetyVerdict::Repaired { violation, removed } => {
+            let removed: Vec<String> = removed
+                .iter()
+                .map(|&i| format!("level {} ({})", i + 1, arr[i]))
+                .collect();
+            Some(format!(
+                "{}, safe without {}",
+                pair(violation),
+                removed.join(", ")
+            ))
+        }
+        SafetyVerdict::Unsafe(violation) => Some(format!("{}, unsafe", pair(violation))),
+    }
+}
+
+/// The parameters setting the rules of both parts, as `--param` takes them,
+/// with the puzzle's values: the smallest and largest steps, the direction
+/// (1 for increasing levels only, -1 for decreasing ones, 0 for either) and
+/// the levels parts 1 and 2 may remove
+pub const PARAMS: [(&str, i64); 5] = [
+    ("min_step", SafetyRule::STRICT.min_step as i64),
+    ("max_step", SafetyRule::STRICT.max_step as i64),
+    ("direction", 0),
+    ("removals1", SafetyRule::STRICT.removals as i64),
+    ("removals2", SafetyRule::TOLERANT.removals as i64),
+];
+
+/// The rules of parts 1 and 2 from the value of each of the [`PARAMS`], or
+/// why they make no rule
+pub fn rules(value: impl Fn(&str) -> i64) -> Result<(SafetyRule, SafetyRule), String> {
+    let count = |name: &str| {
+        u64::try_from(value(name))
+            .map_err(|_| format!("Parameter '{}' expects a number from 0", name))
+    };
+
+    let (min_step, max_step) = (count("min_step")?, count("max_step")?);
+    if min_step > max_step {
+        return Err(format!(
+            "Parameter 'min_step' ({}) cannot exceed 'max_step' ({})",
+            min_step, max_step
+        ));
+    }
+    let direction = match value("direction") {
+        1 => Direction::Increasing,
+        -1 => Direction::Decreasing,
+        0 => Direction::Either,
+        _ => return Err("Parameter 'direction' expects 1, -1 or 0".to_string()),
+    };
+    let rule = |removals: &str| -> Result<SafetyRule, String> {
+        Ok(SafetyRule {
+            min_step,
+            max_step,
+            direction,
+            removals: count(removals)? as usize,
+        })
+    };
+
+    Ok((rule("removals1")?, rule("removals2")?))
+}
+
+fn rule(tolerant: bool) -> SafetyRule {
+    if tolerant {
+        SafetyRule::TOLERANT
+    } else {
+        SafetyRule::STRICT
+    }
+}
+
+/// One report of levels per line
+pub fn parse(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    parser::lines(input)
+        .map(|line| line.parse_list::<u64>(line.text, " ", "a level"))
+        .collect()
+}
+
+/// Number of safe reports
+pub fn count_safe(reports: &[Vec<u64>], tolerant: bool) -> u64 {
+    count_safe_by(reports, &rule(tolerant))
+}
+
+/// Number of reports safe under `rule`
+pub fn count_safe_by(reports: &[Vec<u64>], rule: &SafetyRule) -> u64 {
+    count_safe_traced(reports, rule, &mut NoTrace)
+}
+
+/// [`count_safe_by`], reporting each level removed to make a report safe as a
+/// "removed" fact. Reports are checked one at a time when tracing so the facts
+/// come in order
+pub fn count_safe_traced<T: Trace + ?Sized>(
+    reports: &[Vec<u64>],
+    rule: &SafetyRule,
+    trace: &mut T,
+) -> u64 {
+    if rule.removals > 0 && trace.enabled() {
+        let mut safe = 0;
+        for (n, arr) in reports.iter().enumerate() {
+            let Some(removed) = rule.removal(arr) else {
+                continue;
+            };
+            for i in removed {
+                trace.fact(
+                    Fact::new("removed")
+                        .with("report", n + 1)
+                        .with("level", i + 1)
+                        .with("value", arr[i]),
+                );
+            }
+            safe += 1;
+        }
+        return safe;
+    }
+
+    #[cfg(feature = "parallel")]
+    let reports = reports.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let reports = reports.iter();
+
+    reports.filter(|arr| rule.is_safe(arr)).count() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let reports = parse(EXAMPLE).unwrap();
+        assert_eq!(count_safe(&reports, false), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        let reports = parse(EXAMPLE).unwrap();
+        assert_eq!(count_safe(&reports, true), 4);
+    }
+
+    #[test]
+    fn unsafe_reports_are_annotated() {
+        let reports = parse(EXAMPLE).unwrap();
+        let notes: Vec<String> = reports
+            .iter()
+            .filter_map(|arr| annotate(arr, &SafetyRule::TOLERANT.check(arr)))
+            .collect();
+        assert_eq!(
+            notes,
+            [
+                "levels 2 and 3 (2 then 7) step of 5 too large, unsafe",
+                "levels 3 and 4 (6 then 2) step of 4 too large, unsafe",
+                "levels 2 and 3 (3 then 2) not monotone, safe without level 2 (3)",
+                "levels 3 and 4 (4 then 4) equal neighbours, safe without level 3 (4)",
+            ]
+        );
+    }
+
+    #[test]
+    fn removals_are_reported() {
+        let reports = parse(EXAMPLE).unwrap();
+        let mut facts: Vec<Fact> = Vec::new();
+        assert_eq!(
+            count_safe_traced(&reports, &SafetyRule::TOLERANT, &mut facts),
+            4
+        );
+
+        let removed: Vec<String> = facts
+            .iter()
+            .map(|f| format!("{} {}", f.fields[0].1, f.fields[1].1))
+            .collect();
+        assert_eq!(removed, ["4 2", "5 3"]);
+    }
+
+    #[test]
+    fn rules_come_from_params() {
+        let with = |given: &[(&str, i64)]| {
+            rules(|name| {
+                let (_, value) = given
+                    .iter()
+                    .chain(&PARAMS)
+                    .find(|(n, _)| *n == name)
+                    .unwrap();
+                *value
+            })
+        };
+        assert_eq!(with(&[]), Ok((SafetyRule::STRICT, SafetyRule::TOLERANT)));
+
+        let (strict, tolerant) = with(&[("direction", -1), ("removals1", 2)]).unwrap();
+        assert_eq!(
+            (strict.direction, strict.removals),
+            (Direction::Decreasing, 2)
+        );
+        assert_eq!(tolerant.removals, 1);
+
+        assert!(with(&[("min_step", 5), ("max_step", 2)]).is_err());
+        assert!(with(&[("direction", 2)]).is_err());
+        assert!(with(&[("removals2", -1)]).is_err());
+    }
+}
@@ -1,66 +1,47 @@
-use std::fs;
+use day2::{annotate, count_safe_by, parse, rules, PARAMS};
+use parser::{unwrap_or_exit, DayArgs};
 
-fn _is_safe(arr: &[u64], desc: bool) -> bool {
-    let len = arr.len();
-
-    if len <= 1 {
-        return true;
+fn main() {
+    let mut args = DayArgs::from_env("[--param NAME=VALUE]... [--report] ");
+
+    // The puzzle's rules unless told otherwise, with the names aoc takes
+    let mut values = PARAMS;
+    for assignment in args.values("--param") {
+        let Some((name, value)) = assignment.split_once('=') else {
+            args.exit("--param expects NAME=VALUE");
+        };
+        let Some((_, current)) = values.iter_mut().find(|(n, _)| *n == name) else {
+            let names: Vec<&str> = PARAMS.iter().map(|(name, _)| *name).collect();
+            args.exit(&format!(
+                "Unknown parameter '{}', expected one of {}",
+                name,
+                names.join(", ")
+            ));
+        };
+        *current = value
+            .parse()
+            .unwrap_or_else(|_| args.exit(&format!("Parameter '{}' expects a number", name)));
     }
-
-    let mut prev = arr[0];
-    let mut i: usize = 1;
-
-    while i < len {
-        if desc {
-            if prev <= arr[i] || prev - arr[i] > 3 {
-                return false;
-            }
-        } else {
-            if prev >= arr[i] || arr[i] - prev > 3 {
-                return false;
+    let value = |name: &str| values.iter().find(|(n, _)| *n == name).unwrap().1;
+    let (strict, tolerant) = rules(value).unwrap_or_else(|e| args.exit(&e));
+    let report = args.flag("--report");
+
+    let (filename, input) = args.read_input_or_exit(env!("CARGO_MANIFEST_DIR"));
+    let reports = unwrap_or_exit(parse(&input), &filename, &input);
+
+    // Each unsafe report under the part 2 rule, with why and how to repair it
+    if report {
+        for (n, arr) in reports.iter().enumerate() {
+            if let Some(note) = annotate(arr, &tolerant.check(arr)) {
+                println!("Report {}: {}", n + 1, note);
             }
         }
-
-        prev = arr[i];
-        i += 1;
     }
 
-    true
-}
-
-fn is_safe(arr: &[u64], tolerant: bool) -> bool {
-    if tolerant {
-        let len = arr.len();
-        for i in 0..len {
-            let mut temp = arr.to_vec();
-            temp.remove(i);
-            if _is_safe(&temp, false) || _is_safe(&temp, true) {
-                return true;
-            }
-        }
-    } else {
-        return _is_safe(&arr, false) || _is_safe(&arr, true);
-    }
-
-    false
-}
-
-fn main() {
-    let filename = "input.txt";
-    let mut safe_count = (0, 0);
-
-    for line in fs::read_to_string(filename).unwrap().lines() {
-        let arr: Vec<u64> = line
-            .split(" ")
-            .map(str::parse::<u64>)
-            .map(|x| x.unwrap())
-            .collect();
-
-        // println!("{}", is_safe(&arr, true));
-
-        safe_count.0 += is_safe(&arr, false) as u64;
-        safe_count.1 += is_safe(&arr, true) as u64;
-    }
+    let safe_count = (
+        count_safe_by(&reports, &strict),
+        count_safe_by(&reports, &tolerant),
+    );
 
     println!("Safe Reports: {:?}", safe_count);
 }
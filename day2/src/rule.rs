@@ -0,0 +1,373 @@
+//! Configurable safety rules: the steps allowed between levels, their
+//! direction and how many levels may be removed, and why a report is unsafe
+
+use std::fmt;
+
+/// Which way the levels of a safe report go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Every step goes up
+    Increasing,
+    /// Every step goes down
+    Decreasing,
+    /// Every step goes up, or every step goes down
+    Either,
+}
+
+impl Direction {
+    /// The direction called `up`, `down` or `either`
+    pub fn from_name(name: &str) -> Option<Direction> {
+        match name {
+            "up" => Some(Direction::Increasing),
+            "down" => Some(Direction::Decreasing),
+            "either" => Some(Direction::Either),
+            _ => None,
+        }
+    }
+}
+
+/// Why two neighbouring levels break a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    /// The levels go the other way than the report does
+    NotMonotone,
+    /// The levels change by more than the largest step, this much
+    StepTooLarge(u64),
+    /// The levels change by less than the smallest step, this much
+    StepTooSmall(u64),
+    /// The levels are equal, where some change is needed
+    EqualNeighbours,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reason::NotMonotone => write!(f, "not monotone"),
+            Reason::StepTooLarge(step) => write!(f, "step of {} too large", step),
+            Reason::StepTooSmall(step) => write!(f, "step of {} too small", step),
+            Reason::EqualNeighbours => write!(f, "equal neighbours"),
+        }
+    }
+}
+
+/// The first pair of neighbouring levels breaking a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    /// Index of the first level of the pair, the second one follows it
+    pub at: usize,
+    /// What is wrong with the pair
+    pub reason: Reason,
+}
+
+/// How a report fares under a rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SafetyVerdict {
+    /// Safe as it is
+    Safe,
+    /// Unsafe as it is, but safe once the levels at these indices are removed
+    Repaired {
+        /// First pair of levels breaking the rule
+        violation: Violation,
+        /// Fewest levels to remove, by increasing index
+        removed: Vec<usize>,
+    },
+    /// Unsafe even with as many levels removed as the rule allows
+    Unsafe(Violation),
+}
+
+/// What makes a report safe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyRule {
+    /// Smallest change between neighbouring levels
+    pub min_step: u64,
+    /// Largest change between neighbouring levels
+    pub max_step: u64,
+    /// Way all the changes go
+    pub direction: Direction,
+    /// Levels that may be removed to make the report safe
+    pub removals: usize,
+}
+
+impl SafetyRule {
+    /// Levels all increase or all decrease by 1 to 3, as in part 1
+    pub const STRICT: SafetyRule = SafetyRule {
+        min_step: 1,
+        max_step: 3,
+        direction: Direction::Either,
+        removals: 0,
+    };
+
+    /// The part 1 rule with one level removable, as in part 2
+    pub const TOLERANT: SafetyRule = SafetyRule {
+        removals: 1,
+        ..SafetyRule::STRICT
+    };
+
+    /// Whether the report is safe, what is wrong with it otherwise and which
+    /// levels to remove to repair it if that can be done
+    pub fn check(&self, arr: &[u64]) -> SafetyVerdict {
+        let Some(violation) = self.first_violation(arr) else {
+            return SafetyVerdict::Safe;
+        };
+
+        match self.removal(arr) {
+            Some(removed) => SafetyVerdict::Repaired { violation, removed },
+            None => SafetyVerdict::Unsafe(violation),
+        }
+    }
+
+    /// Whether the report is safe once at most `removals` levels are removed
+    pub fn is_safe(&self, arr: &[u64]) -> bool {
+        self.removal(arr).is_some()
+    }
+
+    /// Fewest levels to remove to make the report safe, by increasing index,
+    /// or None if that takes more than `removals`. Among as few removals, the
+    /// earliest levels are removed. Takes O(n·k) time for k removals
+    pub fn removal(&self, arr: &[u64]) -> Option<Vec<usize>> {
+        let best = match self.direction {
+            Direction::Increasing => self.removal_going(arr, true),
+            Direction::Decreasing => self.removal_going(arr, false),
+            Direction::Either => {
+                let up = self.removal_going(arr, true);
+                let down = self.removal_going(arr, false);
+                match (up, down) {
+                    (Some(up), Some(down)) => Some(if (down.len(), &down) < (up.len(), &up) {
+                        down
+                    } else {
+                        up
+                    }),
+                    (up, down) => up.or(down),
+                }
+            }
+        };
+
+        best.filter(|removed| removed.len() <= self.removals)
+    }
+
+    // The report goes the way most of its steps do when either way is allowed,
+    // so the pair blamed is the odd one out
+    fn first_violation(&self, arr: &[u64]) -> Option<Violation> {
+        let increasing = match self.direction {
+            Direction::Increasing => true,
+            Direction::Decreasing => false,
+            Direction::Either => {
+                let ups = arr.windows(2).filter(|pair| pair[0] < pair[1]).count();
+                let downs = arr.windows(2).filter(|pair| pair[0] > pair[1]).count();
+                ups >= downs
+            }
+        };
+
+        arr.windows(2).enumerate().find_map(|(at, pair)| {
+            let reason = self.broken(pair[0], pair[1], increasing)?;
+            Some(Violation { at, reason })
+        })
+    }
+
+    fn broken(&self, from: u64, to: u64, increasing: bool) -> Option<Reason> {
+        let step = from.abs_diff(to);
+        if step == 0 && self.min_step > 0 {
+            Some(Reason::EqualNeighbours)
+        } else if step > 0 && (to > from) != increasing {
+            Some(Reason::NotMonotone)
+        } else if step > self.max_step {
+            Some(Reason::StepTooLarge(step))
+        } else if step < self.min_step {
+            Some(Reason::StepTooSmall(step))
+        } else {
+            None
+        }
+    }
+
+    fn step_allowed(&self, from: u64, to: u64, increasing: bool) -> bool {
+        self.broken(from, to, increasing).is_none()
+    }
+
+    // removed[i]: fewest levels removed before level i with it kept, coming
+    // from the kept level prev[i]. Only the k + 1 levels before i can precede
+    // it, any earlier one takes more than k removals
+    fn removal_going(&self, arr: &[u64], increasing: bool) -> Option<Vec<usize>> {
+        let n = arr.len();
+        if n == 0 {
+            return Some(Vec::new());
+        }
+
+        let k = self.removals;
+        let mut removed: Vec<usize> = Vec::with_capacity(n);
+        let mut prev: Vec<Option<usize>> = Vec::with_capacity(n);
+
+        for i in 0..n {
+            // Kept first, every level before it removed
+            let mut best = (i, None);
+            // Nearest kept level first, so ties remove the earlier levels
+            for p in (i.saturating_sub(k.saturating_add(1))..i).rev() {
+                let count = removed[p] + (i - p - 1);
+                if count < best.0 && self.step_allowed(arr[p], arr[i], increasing) {
+                    best = (count, Some(p));
+                }
+            }
+            removed.push(best.0);
+            prev.push(best.1);
+        }
+
+        // Kept last, every level after it removed, the latest on ties
+        let last = (0..n).rev().min_by_key(|&i| removed[i] + (n - 1 - i))?;
+
+        let mut kept = vec![false; n];
+        let mut at = Some(last);
+        while let Some(i) = at {
+            kept[i] = true;
+            at = prev[i];
+        }
+
+        Some((0..n).filter(|&i| !kept[i]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fewest levels to remove, trying every subset of them
+    fn exhaustive(rule: &SafetyRule, arr: &[u64]) -> Option<usize> {
+        let safe = |kept: &[u64], increasing: bool| {
+            kept.windows(2)
+                .all(|pair| rule.step_allowed(pair[0], pair[1], increasing))
+        };
+        (0..1u32 << arr.len())
+            .filter(|mask| {
+                let kept: Vec<u64> = (0..arr.len())
+                    .filter(|&i| mask & (1 << i) == 0)
+                    .map(|i| arr[i])
+                    .collect();
+                match rule.direction {
+                    Direction::Increasing => safe(&kept, true),
+                    Direction::Decreasing => safe(&kept, false),
+                    Direction::Either => safe(&kept, true) || safe(&kept, false),
+                }
+            })
+            .map(|mask| mask.count_ones() as usize)
+            .min()
+            .filter(|&count| count <= rule.removals)
+    }
+
+    #[test]
+    fn removes_the_fewest_and_earliest_levels() {
+        let rule = SafetyRule {
+            removals: 2,
+            ..SafetyRule::STRICT
+        };
+        assert_eq!(rule.removal(&[1, 3, 2, 4, 5]), Some(vec![1]));
+        assert_eq!(rule.removal(&[8, 6, 4, 4, 1]), Some(vec![2]));
+        assert_eq!(rule.removal(&[1, 9, 2, 9, 3]), Some(vec![1, 3]));
+        assert_eq!(rule.removal(&[1, 9, 9, 9, 3]), None);
+        assert_eq!(SafetyRule::TOLERANT.removal(&[7, 6, 4, 2, 1]), Some(vec![]));
+
+        let going_up = SafetyRule {
+            direction: Direction::Increasing,
+            ..rule
+        };
+        assert_eq!(going_up.removal(&[5, 1, 2, 3]), Some(vec![0]));
+        assert_eq!(going_up.removal(&[5, 4, 3, 2]), None);
+
+        let any = SafetyRule {
+            removals: usize::MAX,
+            ..rule
+        };
+        assert_eq!(any.removal(&[1, 2, 9, 3]), Some(vec![2]));
+        assert_eq!(Direction::from_name("up"), Some(Direction::Increasing));
+        assert_eq!(Direction::from_name("sideways"), None);
+    }
+
+    #[test]
+    fn verdicts_blame_the_first_bad_pair() {
+        let rule = SafetyRule::TOLERANT;
+        assert_eq!(rule.check(&[7, 6, 4, 2, 1]), SafetyVerdict::Safe);
+        assert_eq!(
+            rule.check(&[1, 2, 7, 8, 9]),
+            SafetyVerdict::Unsafe(Violation {
+                at: 1,
+                reason: Reason::StepTooLarge(5)
+            })
+        );
+        assert_eq!(
+            rule.check(&[1, 3, 2, 4, 5]),
+            SafetyVerdict::Repaired {
+                violation: Violation {
+                    at: 1,
+                    reason: Reason::NotMonotone
+                },
+                removed: vec![1]
+            }
+        );
+        assert_eq!(
+            rule.check(&[8, 6, 4, 4, 1]),
+            SafetyVerdict::Repaired {
+                violation: Violation {
+                    at: 2,
+                    reason: Reason::EqualNeighbours
+                },
+                removed: vec![2]
+            }
+        );
+
+        let wide = SafetyRule {
+            min_step: 2,
+            ..SafetyRule::STRICT
+        };
+        let Some(Violation { at, reason }) = wide.first_violation(&[1, 3, 4]) else {
+            panic!("a step of 1 is too small");
+        };
+        assert_eq!((at, reason), (1, Reason::StepTooSmall(1)));
+    }
+
+    #[test]
+    fn agrees_with_every_subset() {
+        // Small pseudo-random reports of levels close to each other
+        let mut seed: u64 = 1;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            seed >> 33
+        };
+
+        for _ in 0..500 {
+            let len = next() as usize % 9;
+            let arr: Vec<u64> = (0..len).map(|_| next() % 8).collect();
+            for removals in 0..4 {
+                for direction in [
+                    Direction::Increasing,
+                    Direction::Decreasing,
+                    Direction::Either,
+                ] {
+                    let rule = SafetyRule {
+                        min_step: 1,
+                        max_step: 2,
+                        direction,
+                        removals,
+                    };
+                    let removed = rule.removal(&arr);
+                    if let Some(removed) = &removed {
+                        let kept: Vec<u64> = (0..len)
+                            .filter(|i| !removed.contains(i))
+                            .map(|i| arr[i])
+                            .collect();
+                        let strict = SafetyRule {
+                            removals: 0,
+                            ..rule
+                        };
+                        assert_eq!(strict.removal(&kept), Some(Vec::new()));
+                    }
+                    assert_eq!(
+                        removed.as_ref().map(Vec::len),
+                        exhaustive(&rule, &arr),
+                        "{:?} {:?}",
+                        rule,
+                        arr
+                    );
+                }
+            }
+        }
+    }
+}
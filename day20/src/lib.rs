@@ -0,0 +1,216 @@
+//! Day 20: Race Condition, cheats that shorten a race through walls
+#![warn(missing_docs)]
+
+use algo::GridGraph;
+use grid::{Grid, Point};
+use parser::ParseError;
+use std::collections::HashMap;
+
+/// Longest cheat of part 1, in picoseconds
+pub const PART1_LIMIT: usize = 2;
+/// Longest cheat of part 2, in picoseconds
+pub const PART2_LIMIT: usize = 20;
+/// Fewest picoseconds a cheat has to save to count
+pub const PICOSEC_THRESHOLD: usize = 100;
+
+// Returns distance from start to every cell in the map
+fn bfs(map: &Grid<u8>, start: Point) -> Grid<Option<usize>> {
+    let track = GridGraph::new(map, |_, to| map[to] != b'#');
+    let tree = algo::bfs(&track, start);
+    let mut distances: Grid<Option<usize>> = Grid::new(map.height(), map.width(), None);
+    for p in map.points() {
+        distances[p] = tree.steps(p);
+    }
+
+    distances
+}
+
+// Return the number of steps to reach end_pos from start_pos if we can cheat
+fn cheating_distance(
+    map: &Grid<u8>,
+    start_pos: Point,
+    end_pos: Point,
+    limit: usize,
+) -> Option<usize> {
+    if map[start_pos] == b'#' || map[end_pos] == b'#' {
+        return None;
+    }
+
+    let distance = start_pos.manhattan(end_pos);
+
+    if distance > 1 && distance <= limit {
+        return Some(distance);
+    }
+
+    None
+}
+
+/// Number of cheats of at most `cheat_distance_limit` steps through walls that
+/// save at least `saving_threshold` picoseconds
+pub fn solve(map: &Grid<u8>, saving_threshold: usize, cheat_distance_limit: usize) -> usize {
+    let mut cheat_count = 0;
+
+    // steps => [(enter, exit)]
+    let mut cheatables: HashMap<usize, Vec<(Point, Point)>> = HashMap::new();
+
+    let start = map.find(b'S').unwrap();
+    let end = map.find(b'E').unwrap();
+
+    // Precompute distances from start and end
+    let distance_from_start: Grid<Option<usize>> = bfs(map, start);
+    let distance_from_end: Grid<Option<usize>> = bfs(map, end);
+
+    let normal_steps = distance_from_start[end].unwrap();
+
+    // For a pair of coordinates, check if it is cheatable
+    for p1 in map.points() {
+        for p2 in map.points() {
+            if p1 != p2 {
+                let cheatable = cheating_distance(map, p1, p2, cheat_distance_limit);
+
+                if let Some(cheat_steps) = cheatable {
+                    // If cheat distance is in the limit, calculate the steps using distance from start to p1 + cheat steps + distance from end to p2
+                    let before_enter_cheat = distance_from_start[p1].unwrap();
+                    let after_exit_cheat = distance_from_end[p2].unwrap();
+
+                    let steps = before_enter_cheat + cheat_steps + after_exit_cheat;
+
+                    if normal_steps > steps && normal_steps - steps >= saving_threshold {
+                        let saved = normal_steps - steps;
+                        cheatables.entry(saved).or_default().push((p1, p2));
+                        cheat_count += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    cheat_count
+}
+
+/// A racetrack of '#' walls and '.' track with a start 'S' and an end 'E'
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let map = Grid::parse(input, |c| b"#.SE".contains(&c), "'#', '.', 'S' or 'E'")?;
+
+    for tile in [b'S', b'E'] {
+        if map.find(tile).is_none() {
+            return Err(parser::end_of_input(
+                input,
+                &format!("a '{}' tile on the map", tile as char),
+            ));
+        }
+    }
+
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::Dir;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    // The example track is too short to save 100 picoseconds, so smaller thresholds are used
+    #[test]
+    fn part1_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&map, 64, PART1_LIMIT), 1);
+        assert_eq!(solve(&map, 20, PART1_LIMIT), 5);
+    }
+
+    #[test]
+    fn part2_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&map, 76, PART2_LIMIT), 3);
+        assert_eq!(solve(&map, 50, PART2_LIMIT), 285);
+    }
+
+    // Steps from `from` to every cell within `max` steps, optionally walking through walls
+    fn walk(map: &Grid<u8>, from: Point, through_walls: bool, max: usize) -> HashMap<Point, usize> {
+        let mut steps = HashMap::from([(from, 0)]);
+        let mut frontier = vec![from];
+
+        for d in 1..=max {
+            let mut next = Vec::new();
+            for p in frontier {
+                for (_, q) in map.neighbours(p) {
+                    if (through_walls || map[q] != b'#') && !steps.contains_key(&q) {
+                        steps.insert(q, d);
+                        next.push(q);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        steps
+    }
+
+    // Every cheat is a walk through walls of at most `limit` steps between two track cells
+    fn brute_force(map: &Grid<u8>, saving_threshold: usize, limit: usize) -> usize {
+        let everything = map.height() * map.width();
+        let from_start = walk(map, map.find(b'S').unwrap(), false, everything);
+        let from_end = walk(map, map.find(b'E').unwrap(), false, everything);
+        let normal = from_start[&map.find(b'E').unwrap()];
+
+        let mut count = 0;
+        for (&enter, &before) in &from_start {
+            for (exit, cheat) in walk(map, enter, true, limit) {
+                if let Some(&after) = from_end.get(&exit) {
+                    if before + cheat + after + saving_threshold <= normal {
+                        count += 1;
+                    }
+                }
+            }
+        }
+
+        count
+    }
+
+    // A random walk carves the track, so every track cell is reachable from 'S'
+    fn track() -> impl Strategy<Value = Grid<u8>> {
+        (
+            3..10usize,
+            3..10usize,
+            prop::collection::vec(0..4usize, 1..60),
+        )
+            .prop_filter_map(
+                "the walk must end away from the start",
+                |(height, width, moves)| {
+                    let mut map = Grid::new(height, width, b'#');
+                    let start = Point::new(0, 0);
+                    let mut p = start;
+                    map[p] = b'.';
+                    for m in moves {
+                        if let Some(q) = map.step(p, [Dir::UP, Dir::RIGHT, Dir::DOWN, Dir::LEFT][m])
+                        {
+                            map[q] = b'.';
+                            p = q;
+                        }
+                    }
+
+                    (p != start).then(|| {
+                        map[start] = b'S';
+                        map[p] = b'E';
+                        map
+                    })
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn solve_matches_brute_force(
+            map in track(),
+            saving_threshold in 1..10usize,
+            limit in 2..6usize,
+        ) {
+            prop_assert_eq!(
+                solve(&map, saving_threshold, limit),
+                brute_force(&map, saving_threshold, limit)
+            );
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Day 21: Keypad Conundrum, typing door codes through a chain of robots
+#![warn(missing_docs)]
+
+use algo::Memo;
+use parser::ParseError;
+use std::vec;
+
+#[derive(Clone, Debug, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Push,
+}
+
+/*
++---+---+---+
+| 7 | 8 | 9 |
++---+---+---+
+| 4 | 5 | 6 |
++---+---+---+
+| 1 | 2 | 3 |
++---+---+---+
+    | 0 | A |
+    +---+---+
+*/
+const NUMPAD_POSITIONS: [(usize, usize); 11] = [
+    (3, 1), // 0
+    (2, 0), // 1
+    (2, 1), // 2
+    (2, 2), // 3
+    (1, 0), // 4
+    (1, 1), // 5
+    (1, 2), // 6
+    (0, 0), // 7
+    (0, 1), // 8
+    (0, 2), // 9
+    (3, 2), // A
+];
+const NUMPAD_GAPS: [(usize, usize); 1] = [(3, 0)];
+const NUMPAD_A_BUTTON: (usize, usize) = (3, 2);
+
+/*
+    +---+---+
+    | ^ | A |
++---+---+---+
+| < | v | > |
++---+---+---+
+ */
+const ROBOTPAD_POSITIONS: [(usize, usize); 5] = [
+    (0, 1), // UP
+    (1, 1), // DOWN
+    (1, 0), // LEFT
+    (1, 2), // RIGHT
+    (0, 2), // A
+];
+const ROBOTPAD_GAPS: [(usize, usize); 1] = [(0, 0)];
+
+/// Robots on directional keypads in part 1
+pub const PART1_DEPTH: usize = 2;
+/// Robots on directional keypads in part 2
+pub const PART2_DEPTH: usize = 25;
+
+struct RobotPad {
+    position: (usize, usize),
+    gaps: Vec<(usize, usize)>,
+    depth: usize,
+}
+
+impl RobotPad {
+    fn new(depth: usize) -> Self {
+        RobotPad {
+            position: (0, 2),
+            gaps: ROBOTPAD_GAPS.to_vec(),
+            depth,
+        }
+    }
+}
+
+// (depth, arm position, button) => sequence length
+type Presses = Memo<(usize, (usize, usize), usize), usize>;
+
+fn simulate_robot_move(
+    memo: &mut Presses,
+    depth: usize,
+    sequence: Vec<Direction>,
+    current_depth: usize,
+) -> usize {
+    if depth == current_depth {
+        return sequence.len();
+    }
+
+    let mut robot: RobotPad = RobotPad::new(current_depth);
+    let mut sequence_length = 0;
+
+    for robot_move in sequence {
+        // Find the minimum sequence length for deeper levels, once per position and move
+        let key = (current_depth, robot.position, robot_move as usize);
+        sequence_length +=
+            memo.get_or_insert_with(key, |memo| robot.move_and_push(memo, depth, robot_move));
+        robot.position = ROBOTPAD_POSITIONS[robot_move as usize];
+    }
+
+    sequence_length
+}
+
+// Try all possible moves in current depth
+#[allow(clippy::too_many_arguments)]
+fn _find_best_path(
+    memo: &mut Presses,
+    gaps: &Vec<(usize, usize)>,
+    simulation_depth: usize,
+    current_depth: usize,
+    vertical_direction: i32,
+    horizontal_direction: i32,
+    sequence: Vec<Direction>,
+    current_position: (usize, usize),
+) -> usize {
+    if vertical_direction == 0 && horizontal_direction == 0 {
+        let mut final_sequence = sequence.clone();
+        final_sequence.push(Direction::Push);
+
+        // The sequence is ready, simulate find the length of the last controller
+        let best_sequence_len =
+            simulate_robot_move(memo, simulation_depth, final_sequence, current_depth);
+
+        return best_sequence_len;
+    }
+
+    if gaps.contains(&current_position) {
+        // Bad Cell not allowed
+        return usize::MAX;
+    }
+
+    let mut best_length = usize::MAX;
+    let mut new_sequence = sequence.clone();
+
+    // Try vertical move
+    if vertical_direction != 0 {
+        new_sequence.push(if vertical_direction < 0 {
+            Direction::Up
+        } else {
+            Direction::Down
+        });
+
+        let new_position = (
+            (current_position.0 as i32 + vertical_direction.signum()) as usize,
+            current_position.1,
+        );
+
+        let length = _find_best_path(
+            memo,
+            gaps,
+            simulation_depth,
+            current_depth,
+            vertical_direction - vertical_direction.signum(),
+            horizontal_direction,
+            new_sequence,
+            new_position,
+        );
+
+        if length < best_length {
+            best_length = length;
+        }
+    }
+
+    // Try horizontal move
+    let mut new_sequence = sequence.clone();
+    if horizontal_direction != 0 {
+        new_sequence.push(if horizontal_direction < 0 {
+            Direction::Left
+        } else {
+            Direction::Right
+        });
+
+        let new_position = (
+            current_position.0,
+            (current_position.1 as i32 + horizontal_direction.signum()) as usize,
+        );
+
+        let length = _find_best_path(
+            memo,
+            gaps,
+            simulation_depth,
+            current_depth,
+            vertical_direction,
+            horizontal_direction - horizontal_direction.signum(),
+            new_sequence,
+            new_position,
+        );
+
+        if length < best_length {
+            best_length = length;
+        }
+    }
+
+    best_length
+}
+
+// Wrapper function for find_best_path
+fn find_best_path(
+    memo: &mut Presses,
+    gaps: &Vec<(usize, usize)>,
+    robot_depth: usize,
+    current_depth: usize,
+    target_position: (usize, usize),
+    current_position: (usize, usize),
+) -> usize {
+    _find_best_path(
+        memo,
+        gaps,
+        robot_depth,
+        current_depth,
+        target_position.0 as i32 - current_position.0 as i32,
+        target_position.1 as i32 - current_position.1 as i32,
+        vec![],
+        current_position,
+    )
+}
+
+impl RobotPad {
+    fn move_and_push(
+        &mut self,
+        memo: &mut Presses,
+        robot_depth: usize,
+        direction: Direction,
+    ) -> usize {
+        if self.depth == robot_depth {
+            // Base case, no need to go deeper
+            return 1;
+        }
+
+        let best_sequence_len = find_best_path(
+            memo,
+            &self.gaps,
+            robot_depth,
+            self.depth + 1,
+            ROBOTPAD_POSITIONS[direction as usize],
+            self.position,
+        );
+
+        self.position = ROBOTPAD_POSITIONS[direction as usize];
+
+        best_sequence_len
+    }
+}
+
+/// The numeric keypad of the door, operated through `robot_depth` robots
+pub struct NumPad {
+    position: (usize, usize),
+    gaps: Vec<(usize, usize)>,
+    robot_depth: usize,
+
+    mem: Presses,
+}
+
+impl Default for NumPad {
+    fn default() -> Self {
+        NumPad::new(PART2_DEPTH)
+    }
+}
+
+impl NumPad {
+    /// Every arm starts on its keypad's A button
+    pub fn new(robot_depth: usize) -> Self {
+        NumPad {
+            position: NUMPAD_A_BUTTON,
+            gaps: NUMPAD_GAPS.to_vec(),
+            robot_depth,
+            mem: Presses::new(),
+        }
+    }
+
+    fn move_and_push(&mut self, num: usize) -> usize {
+        let length = find_best_path(
+            &mut self.mem,
+            &self.gaps,
+            self.robot_depth,
+            0,
+            NUMPAD_POSITIONS[num],
+            self.position,
+        );
+
+        self.position = NUMPAD_POSITIONS[num];
+
+        length
+    }
+
+    /// Length of the shortest sequence to type `line` on the numeric keypad,
+    /// from the keypad you press, times the numeric part of the code
+    pub fn calculate_complexity(&mut self, line: &str) -> usize {
+        let mut sum_length = 0;
+
+        for c in line.chars() {
+            let num = c.to_digit(16).unwrap() as usize;
+            let l = self.move_and_push(num);
+            sum_length += l;
+        }
+
+        let complexity = line
+            .chars()
+            .take(3)
+            .collect::<String>()
+            .parse::<usize>()
+            .unwrap()
+            * sum_length;
+
+        complexity
+    }
+}
+
+/// One door code per line, like 029A
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    parser::lines(input)
+        .map(|line| {
+            let bytes = line.text.as_bytes();
+            if bytes.len() != 4 || !bytes[..3].iter().all(u8::is_ascii_digit) || bytes[3] != b'A' {
+                return Err(line.error(line.text, "a door code like 029A"));
+            }
+            Ok(line.text.to_string())
+        })
+        .collect()
+}
+
+/// Sum of the complexities of the codes
+pub fn solve(codes: &[String], robot_depth: usize) -> usize {
+    let mut numpad = NumPad::new(robot_depth);
+
+    codes
+        .iter()
+        .map(|code| numpad.calculate_complexity(code))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let codes = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&codes, PART1_DEPTH), 126384);
+    }
+
+    // The puzzle gives no part 2 answer for the example
+    #[test]
+    fn part2_example() {
+        let codes = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&codes, PART2_DEPTH), 154115708116294);
+    }
+}
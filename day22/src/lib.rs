@@ -0,0 +1,180 @@
+//! Day 22: Monkey Market, pseudorandom secret numbers and the prices they give
+#![warn(missing_docs)]
+
+use parser::ParseError;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// First price each buyer offers after each sequence of 4 price changes, keyed by
+/// (initial secret, changes)
+pub type PriceMap = HashMap<(u64, i8, i8, i8, i8), i8>;
+
+/// Secret numbers each buyer generates in a day
+pub const SECRETS: usize = 2000;
+
+/// A buyer's secret number generator
+pub struct Prng {
+    /// The current secret number
+    pub secret: u64,
+}
+
+impl Prng {
+    /// Starts from an initial secret number
+    pub fn new(secret: u64) -> Prng {
+        Prng { secret }
+    }
+
+    fn mix(&mut self, value: u64) {
+        self.secret ^= value;
+    }
+
+    fn prune(&mut self) {
+        self.secret %= 16777216;
+    }
+
+    /// Moves to the next secret number and returns it
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> u64 {
+        let result = self.secret * 64;
+        self.mix(result);
+        self.prune();
+        let result = self.secret / 32;
+        self.mix(result);
+        self.prune();
+        let result = self.secret * 2048;
+        self.mix(result);
+        self.prune();
+
+        self.secret
+    }
+}
+
+/// Most bananas any sequence of 4 price changes gets, and the first sequence
+/// that gets them
+pub fn solve(initial_seeds: &[u64], price_map: &PriceMap) -> (u64, Vec<(i8, i8, i8, i8)>) {
+    let mut sequences: Vec<(i8, i8, i8, i8)> = Vec::new();
+    for i1 in -9..10 {
+        for i2 in -9..10 {
+            for i3 in -9..10 {
+                for i4 in -9..10 {
+                    sequences.push((i1, i2, i3, i4));
+                }
+            }
+        }
+    }
+
+    let total_price = |&(i1, i2, i3, i4): &(i8, i8, i8, i8)| -> u64 {
+        initial_seeds
+            .iter()
+            .filter_map(|seed| price_map.get(&(*seed, i1, i2, i3, i4)))
+            .map(|&price| price as u64)
+            .sum()
+    };
+
+    #[cfg(feature = "parallel")]
+    let totals: Vec<u64> = sequences.par_iter().map(total_price).collect();
+    #[cfg(not(feature = "parallel"))]
+    let totals: Vec<u64> = sequences.iter().map(total_price).collect();
+
+    // The first sequence with the best price wins, as in a serial search
+    let mut best_price: u64 = 0;
+    let mut best_sequence: Vec<(i8, i8, i8, i8)> = Vec::new();
+    for (sequence, total_price) in sequences.into_iter().zip(totals) {
+        if total_price > best_price {
+            best_price = total_price;
+            best_sequence = vec![sequence];
+        }
+    }
+
+    (best_price, best_sequence)
+}
+
+/// One initial secret number per line
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    parser::lines(input)
+        .map(|line| line.parse::<u64>(line.text, "an initial secret number"))
+        .collect()
+}
+
+// The last secret of a buyer and the first price after each sequence of 4 changes
+fn simulate_buyer(seed: u64, secrets: usize) -> (u64, PriceMap) {
+    let mut price_map: PriceMap = HashMap::new(); // initial seed + 4 consecutive changes => first occurrence of price
+    let mut prng = Prng::new(seed);
+
+    let mut prev_price: i8 = (seed % 10) as i8;
+    let mut price_changes: Vec<i8> = Vec::new();
+
+    for _ in 0..secrets {
+        let price = (prng.next() % 10) as i8;
+        price_changes.push(price - prev_price);
+
+        if price_changes.len() > 4 {
+            price_changes.remove(0);
+        }
+
+        if price_changes.len() == 4 {
+            price_map
+                .entry((
+                    seed,
+                    price_changes[0],
+                    price_changes[1],
+                    price_changes[2],
+                    price_changes[3],
+                ))
+                .or_insert(price);
+        }
+        prev_price = price;
+    }
+
+    (prng.secret, price_map)
+}
+
+/// Returns the sum of the last secrets and the price map of all buyers, after
+/// `secrets` new secret numbers each
+pub fn simulate(initial_seeds: &[u64], secrets: usize) -> (u64, PriceMap) {
+    #[cfg(feature = "parallel")]
+    let buyers: Vec<(u64, PriceMap)> = initial_seeds
+        .par_iter()
+        .map(|&seed| simulate_buyer(seed, secrets))
+        .collect();
+    #[cfg(not(feature = "parallel"))]
+    let buyers: Vec<(u64, PriceMap)> = initial_seeds
+        .iter()
+        .map(|&seed| simulate_buyer(seed, secrets))
+        .collect();
+
+    let mut sum = 0;
+    let mut price_map: PriceMap = HashMap::new();
+
+    // Keys start with the seed, so buyers never overwrite each other's prices
+    for (secret, buyer_prices) in buyers {
+        sum += secret;
+        price_map.extend(buyer_prices);
+    }
+
+    (sum, price_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    // test.txt is the part 2 example, part 1 uses the buyers of test2.txt
+    const EXAMPLE_PART1: &str = include_str!("../test2.txt");
+
+    #[test]
+    fn part1_example() {
+        let seeds = parse(EXAMPLE_PART1).unwrap();
+        assert_eq!(simulate(&seeds, SECRETS).0, 37327623);
+    }
+
+    #[test]
+    fn part2_example() {
+        let seeds = parse(EXAMPLE).unwrap();
+        let (_, price_map) = simulate(&seeds, SECRETS);
+        assert_eq!(solve(&seeds, &price_map).0, 23);
+    }
+}
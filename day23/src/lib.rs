@@ -0,0 +1,172 @@
+//! Day 23: LAN Party, triangles and the largest clique of a network
+#![warn(missing_docs)]
+
+use algo::Adjacency;
+use parser::ParseError;
+use std::collections::{HashMap, HashSet};
+use trace::{Fact, NoTrace, Trace};
+
+/// Number of sets of three interconnected computers with at least one name
+/// starting with a t
+pub fn part1(computer_vec: &[String], computer_map: &HashSet<(String, String)>) -> usize {
+    let mut triplets: Vec<Vec<String>> = Vec::new();
+
+    for comp1 in 0..computer_vec.len() {
+        for comp2 in comp1 + 1..computer_vec.len() {
+            for comp3 in comp2 + 1..computer_vec.len() {
+                if computer_map
+                    .contains(&(computer_vec[comp1].clone(), computer_vec[comp2].clone()))
+                    && computer_map
+                        .contains(&(computer_vec[comp2].clone(), computer_vec[comp3].clone()))
+                    && computer_map
+                        .contains(&(computer_vec[comp3].clone(), computer_vec[comp1].clone()))
+                    && (computer_vec[comp1].starts_with("t")
+                        || computer_vec[comp2].starts_with("t")
+                        || computer_vec[comp3].starts_with("t"))
+                {
+                    triplets.push(vec![
+                        computer_vec[comp1].clone(),
+                        computer_vec[comp2].clone(),
+                        computer_vec[comp3].clone(),
+                    ]);
+                }
+            }
+        }
+    }
+
+    triplets.len()
+}
+
+/// The largest set of computers all connected to each other
+pub fn max_clique(
+    adj_list: &HashMap<String, HashSet<String>>,
+    vertices: &HashSet<String>,
+) -> HashSet<String> {
+    max_clique_traced(adj_list, vertices, &mut NoTrace)
+}
+
+/// [`max_clique`], reporting its members as a "clique" fact
+pub fn max_clique_traced<T: Trace + ?Sized>(
+    adj_list: &HashMap<String, HashSet<String>>,
+    vertices: &HashSet<String>,
+    trace: &mut T,
+) -> HashSet<String> {
+    // Number the computers so the network is a graph of indices
+    let names: Vec<&String> = vertices.iter().collect();
+    let number: HashMap<&String, usize> = names
+        .iter()
+        .enumerate()
+        .map(|(n, &name)| (name, n))
+        .collect();
+    let network = Adjacency(
+        names
+            .iter()
+            .map(|&name| {
+                adj_list
+                    .get(name)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|other| number.get(other).copied())
+                    .collect()
+            })
+            .collect(),
+    );
+
+    // Find largest complete subgraph
+    let mut max_clique = HashSet::new();
+    for clique in algo::maximal_cliques(&network, 0..names.len()) {
+        if clique.len() > max_clique.len() {
+            max_clique = clique.into_iter().map(|n| names[n].clone()).collect();
+        }
+    }
+
+    if trace.enabled() {
+        let mut members: Vec<&str> = max_clique.iter().map(String::as_str).collect();
+        members.sort();
+        trace.fact(
+            Fact::new("clique")
+                .with("size", members.len())
+                .with("members", members),
+        );
+    }
+
+    max_clique
+}
+
+/// The connections of the network
+pub struct Network {
+    /// Connected pairs, both ways round
+    pub computer_map: HashSet<(String, String)>,
+    /// Every computer name
+    pub computers: HashSet<String>,
+    /// Computers each one is connected to
+    pub adj_list: HashMap<String, HashSet<String>>,
+}
+
+fn is_computer(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// One connection like kh-tc per line
+pub fn parse(input: &str) -> Result<Network, ParseError> {
+    let mut computer_map: HashSet<(String, String)> = HashSet::new();
+    let mut computers: HashSet<String> = HashSet::new();
+    let mut adj_list: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for line in parser::lines(input) {
+        let (comp1, comp2) = line.split_once("-", "a connection like kh-tc")?;
+        for name in [comp1, comp2] {
+            if !is_computer(name) {
+                return Err(line.error(name, "a computer name"));
+            }
+        }
+        let comp1 = comp1.to_string();
+        let comp2: String = comp2.to_string();
+
+        computers.insert(comp1.clone());
+        computers.insert(comp2.clone());
+        computer_map.insert((comp1.clone(), comp2.clone()));
+        computer_map.insert((comp2.clone(), comp1.clone()));
+
+        // Add to adjacency list
+        adj_list
+            .entry(comp1.clone())
+            .or_default()
+            .insert(comp2.clone());
+        adj_list.entry(comp2).or_default().insert(comp1);
+    }
+
+    Ok(Network {
+        computer_map,
+        computers,
+        adj_list,
+    })
+}
+
+/// Password is the names of the computers in the largest clique, sorted and joined by commas
+pub fn password(max_clique: HashSet<String>) -> String {
+    let mut sorted_clique = max_clique.into_iter().collect::<Vec<String>>();
+    sorted_clique.sort();
+    sorted_clique.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let network = parse(EXAMPLE).unwrap();
+        let computer_vec: Vec<String> = network.computers.iter().cloned().collect();
+        assert_eq!(part1(&computer_vec, &network.computer_map), 7);
+    }
+
+    #[test]
+    fn part2_example() {
+        let network = parse(EXAMPLE).unwrap();
+        let max_clique = max_clique(&network.adj_list, &network.computers);
+        assert_eq!(password(max_clique), "co,de,ka,ta");
+    }
+}
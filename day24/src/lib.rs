@@ -0,0 +1,644 @@
+//! Day 24: Crossed Wires, a network of logic gates meant to add two numbers
+#![warn(missing_docs)]
+
+use parser::{Line, ParseError};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// What a gate computes from its two inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// 1 when both inputs are 1
+    And,
+    /// 1 when either input is 1
+    Or,
+    /// 1 when the inputs differ
+    Xor,
+}
+
+impl Op {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Op::And => a && b,
+            Op::Or => a || b,
+            Op::Xor => a != b,
+        }
+    }
+
+    /// As written in the puzzle input
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Xor => "XOR",
+        }
+    }
+}
+
+/// A gate writing `op` of two wires to a third, wires are indices into
+/// [`Circuit::names`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gate {
+    /// The wires read
+    pub inputs: [usize; 2],
+    /// What is computed
+    pub op: Op,
+    /// The wire written
+    pub output: usize,
+}
+
+/// Wires with their initial values and the gates between them
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    /// Name of each wire
+    pub names: Vec<String>,
+    /// Wires set before any gate runs, with their values
+    pub initial: Vec<(usize, bool)>,
+    /// Gates in the order of the input
+    pub gates: Vec<Gate>,
+    // Indices of the gates, each after the gates writing its inputs
+    order: Vec<usize>,
+}
+
+// Gates ordered so their inputs are written first, or the first gate that
+// waits on a loop
+fn topological_order(
+    gates: &[Gate],
+    wires: usize,
+    initial: &[(usize, bool)],
+) -> Result<Vec<usize>, usize> {
+    let mut readers: Vec<Vec<usize>> = vec![Vec::new(); wires];
+    for (i, gate) in gates.iter().enumerate() {
+        for &input in &gate.inputs {
+            readers[input].push(i);
+        }
+    }
+
+    // Inputs each gate still waits for, a gate reading one wire twice waits twice
+    let mut waiting: Vec<usize> = vec![2; gates.len()];
+    let mut ready: Vec<usize> = initial.iter().map(|&(wire, _)| wire).collect();
+    let mut order: Vec<usize> = Vec::with_capacity(gates.len());
+
+    while let Some(wire) = ready.pop() {
+        for &i in &readers[wire] {
+            waiting[i] -= 1;
+            if waiting[i] == 0 {
+                order.push(i);
+                ready.push(gates[i].output);
+            }
+        }
+    }
+
+    match waiting.iter().position(|&w| w > 0) {
+        Some(stuck) => Err(stuck),
+        None => Ok(order),
+    }
+}
+
+// Wires are named with lowercase letters and digits
+fn is_wire(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+// The bit a wire like z07 stands for, for wires starting with `prefix`
+fn bit(name: &str, prefix: char) -> Option<u32> {
+    name.strip_prefix(prefix)?.parse().ok()
+}
+
+struct Wires<'a> {
+    names: Vec<String>,
+    index: HashMap<&'a str, usize>,
+}
+
+impl<'a> Wires<'a> {
+    fn get(&mut self, line: &Line<'a>, name: &'a str) -> Result<usize, ParseError> {
+        if !is_wire(name) {
+            return Err(line.error(name, "a wire name of lowercase letters and digits"));
+        }
+
+        let next = self.names.len();
+        let wire = *self.index.entry(name).or_insert(next);
+        if wire == next {
+            self.names.push(name.to_string());
+        }
+        Ok(wire)
+    }
+}
+
+fn parse_gate<'a>(line: &Line<'a>, wires: &mut Wires<'a>) -> Result<Gate, ParseError> {
+    let (lhs, output) = line.split_once(" -> ", "a gate like x00 AND y00 -> z00")?;
+    let words: Vec<&str> = lhs.split(' ').collect();
+    let [a, op, b] = words[..] else {
+        return Err(line.error(lhs, "two wires around AND, OR or XOR"));
+    };
+
+    let op = match op {
+        "AND" => Op::And,
+        "OR" => Op::Or,
+        "XOR" => Op::Xor,
+        _ => return Err(line.error(op, "AND, OR or XOR")),
+    };
+
+    Ok(Gate {
+        inputs: [wires.get(line, a)?, wires.get(line, b)?],
+        op,
+        output: wires.get(line, output)?,
+    })
+}
+
+/// Initial wire values like `x00: 1`, a blank line, then gates like
+/// `x00 AND y00 -> z00`. Every wire must be set once, by its initial value or a
+/// gate, and the gates must not loop
+pub fn parse(input: &str) -> Result<Circuit, ParseError> {
+    let sections = parser::sections(input);
+    let [values, gate_lines] = &sections[..] else {
+        return Err(match sections.get(2) {
+            Some(extra) => extra[0].error(extra[0].text, "the end of the input"),
+            None => parser::end_of_input(input, "wire values, a blank line and gates"),
+        });
+    };
+
+    let mut wires = Wires {
+        names: Vec::new(),
+        index: HashMap::new(),
+    };
+    // The line setting each wire, for errors
+    let mut set_by: HashMap<usize, Line> = HashMap::new();
+
+    let mut initial: Vec<(usize, bool)> = Vec::new();
+    for line in values {
+        let (name, value) = line.split_once(": ", "a wire value like x00: 1")?;
+        let wire = wires.get(line, name)?;
+        let value = match value {
+            "0" => false,
+            "1" => true,
+            _ => return Err(line.error(value, "0 or 1")),
+        };
+
+        if set_by.insert(wire, *line).is_some() {
+            return Err(line.error(name, "a wire that is not set yet"));
+        }
+        initial.push((wire, value));
+    }
+
+    let mut gates: Vec<Gate> = Vec::new();
+    for line in gate_lines {
+        let gate = parse_gate(line, &mut wires)?;
+        if set_by.insert(gate.output, *line).is_some() {
+            let (_, output) = line.text.rsplit_once(' ').unwrap();
+            return Err(line.error(output, "a wire that is not set yet"));
+        }
+        gates.push(gate);
+    }
+
+    for (gate, line) in gates.iter().zip(gate_lines) {
+        for (&input, name) in gate.inputs.iter().zip(line.text.split(' ').step_by(2)) {
+            if !set_by.contains_key(&input) {
+                return Err(line.error(name, "a wire with a value or a gate setting it"));
+            }
+        }
+    }
+
+    // Numbers are 64 bits, wider x, y or z wires have no bit of them
+    for (wire, name) in wires.names.iter().enumerate() {
+        for (prefix, expected) in [
+            ('x', "an input bit below x64"),
+            ('y', "an input bit below y64"),
+            ('z', "an output bit below z64"),
+        ] {
+            if bit(name, prefix).is_some_and(|bit| bit >= u64::BITS) {
+                let line = &set_by[&wire];
+                let start = line.text.rfind(name.as_str()).unwrap();
+                let at = &line.text[start..start + name.len()];
+                return Err(line.error(at, expected));
+            }
+        }
+    }
+
+    let order = topological_order(&gates, wires.names.len(), &initial).map_err(|stuck| {
+        gate_lines[stuck].error(gate_lines[stuck].text, "a gate outside any loop")
+    })?;
+
+    Ok(Circuit {
+        names: wires.names,
+        initial,
+        gates,
+        order,
+    })
+}
+
+impl Circuit {
+    // Runs the gates from the given initial values
+    fn run(&self, initial: impl IntoIterator<Item = (usize, bool)>) -> Vec<bool> {
+        let mut values = vec![false; self.names.len()];
+        for (wire, value) in initial {
+            values[wire] = value;
+        }
+
+        for &i in &self.order {
+            let gate = &self.gates[i];
+            values[gate.output] = gate
+                .op
+                .apply(values[gate.inputs[0]], values[gate.inputs[1]]);
+        }
+
+        values
+    }
+
+    // Wires named `prefix` and a bit number, with their bits
+    fn bits(&self, prefix: char) -> Vec<(usize, u32)> {
+        self.names
+            .iter()
+            .enumerate()
+            .filter_map(|(wire, name)| Some((wire, bit(name, prefix)?)))
+            .collect()
+    }
+
+    fn number(&self, values: &[bool]) -> u64 {
+        self.bits('z')
+            .into_iter()
+            .filter(|&(wire, _)| values[wire])
+            .map(|(_, bit)| 1 << bit)
+            .sum()
+    }
+
+    /// The number the z wires make once the gates ran from the initial values
+    pub fn output(&self) -> u64 {
+        self.number(&self.run(self.initial.iter().copied()))
+    }
+
+    /// The z number for the x and y wires set to the bits of `x` and `y`,
+    /// other initial values are kept
+    pub fn add(&self, x: u64, y: u64) -> u64 {
+        let bits = self
+            .bits('x')
+            .into_iter()
+            .map(|(wire, bit)| (wire, x >> bit & 1 == 1));
+        let bits = bits.chain(
+            self.bits('y')
+                .into_iter()
+                .map(|(wire, bit)| (wire, y >> bit & 1 == 1)),
+        );
+        self.number(&self.run(self.initial.iter().copied().chain(bits)))
+    }
+
+    /// The circuit with the outputs of each pair of wires exchanged, None when
+    /// that makes the gates loop
+    pub fn swapped(&self, pairs: &[(usize, usize)]) -> Option<Circuit> {
+        let mut gates = self.gates.clone();
+        for gate in &mut gates {
+            for &(a, b) in pairs {
+                if gate.output == a {
+                    gate.output = b;
+                } else if gate.output == b {
+                    gate.output = a;
+                }
+            }
+        }
+
+        let order = topological_order(&gates, self.names.len(), &self.initial).ok()?;
+        Some(Circuit {
+            gates,
+            order,
+            ..self.clone()
+        })
+    }
+
+    // x and y bits 0 to n-1 and z bits 0 to n, for n below 64
+    fn has_adder_wires(&self) -> bool {
+        let numbered = |prefix: char, n: usize| {
+            let mut bits: Vec<u32> = self.bits(prefix).into_iter().map(|(_, bit)| bit).collect();
+            bits.sort();
+            bits.into_iter().eq(0..n as u32)
+        };
+
+        let n = self.bits('x').len();
+        n > 0
+            && n < u64::BITS as usize
+            && numbered('x', n)
+            && numbered('y', n)
+            && numbered('z', n + 1)
+    }
+
+    // Whether x + y comes out of z for numbers that exercise each bit and the
+    // carries into it
+    fn is_adder(&self) -> bool {
+        if !self.has_adder_wires() {
+            return false;
+        }
+        let n = self.bits('x').len() as u32;
+
+        let mask = (1 << n) - 1;
+        let mut cases = vec![(0, 0), (mask, 1), (mask, mask)];
+        for bit in 0..n {
+            let b = 1 << bit;
+            cases.extend([(b, 0), (0, b), (b, b), (mask, b)]);
+        }
+
+        cases.into_iter().all(|(x, y)| self.add(x, y) == x + y)
+    }
+}
+
+/// Wires written by gates that do not fit a ripple-carry adder of the x and y
+/// bits: bit i is z_i = (x_i XOR y_i) XOR c_i, with the carry into the next bit
+/// c_i+1 = (x_i AND y_i) OR ((x_i XOR y_i) AND c_i), and the last z is the
+/// final carry. Bit 0 has no carry in
+pub fn misplaced_wires(circuit: &Circuit) -> Vec<usize> {
+    let names = &circuit.names;
+    let last_z = circuit.bits('z').into_iter().map(|(_, bit)| bit).max();
+    let is_input = |wire: usize| bit(&names[wire], 'x').or(bit(&names[wire], 'y'));
+    let first_bit = |gate: &Gate| gate.inputs.iter().all(|&wire| is_input(wire) == Some(0));
+    let read_by = |wire: usize, op: Op| {
+        circuit
+            .gates
+            .iter()
+            .any(|gate| gate.op == op && gate.inputs.contains(&wire))
+    };
+
+    let mut misplaced: Vec<usize> = circuit
+        .gates
+        .iter()
+        .filter(|gate| {
+            let output = gate.output;
+            let z = bit(&names[output], 'z');
+            let from_inputs = gate.inputs.iter().all(|&wire| is_input(wire).is_some());
+
+            match gate.op {
+                // Sums end in z, the carry is the only z from an OR
+                _ if z.is_some() && z == last_z => gate.op != Op::Or,
+                Op::Xor if z.is_some() => from_inputs && !first_bit(gate),
+                _ if z.is_some() => true,
+                // x_i XOR y_i goes into the sum and the carry of bit i
+                Op::Xor if from_inputs => !first_bit(gate) && !read_by(output, Op::Xor),
+                Op::Xor => true,
+                // Both ANDs of a bit go into its OR, except the carry out of bit 0
+                Op::And => !first_bit(gate) && !read_by(output, Op::Or),
+                // Carries go into the sum of the next bit
+                Op::Or => !read_by(output, Op::Xor),
+            }
+        })
+        .map(|gate| gate.output)
+        .collect();
+
+    misplaced.sort_by(|&a, &b| names[a].cmp(&names[b]));
+    misplaced
+}
+
+// Pairs up `wires` in every way until the swapped circuit adds
+fn pair_up(circuit: &Circuit, wires: &[usize], pairs: &mut Vec<(usize, usize)>) -> bool {
+    let Some((&first, rest)) = wires.split_first() else {
+        return circuit
+            .swapped(pairs)
+            .is_some_and(|swapped| swapped.is_adder());
+    };
+
+    for (i, &other) in rest.iter().enumerate() {
+        let mut remaining = rest.to_vec();
+        remaining.remove(i);
+
+        pairs.push((first, other));
+        if pair_up(circuit, &remaining, pairs) {
+            return true;
+        }
+        pairs.pop();
+    }
+
+    false
+}
+
+/// Most pairs of swapped wires looked for, the ways to pair up the misplaced
+/// wires grow as (2n-1)!!. The puzzle swaps 4
+pub const MAX_PAIRS: usize = 6;
+
+/// The pairs of wires whose gates' outputs were swapped, found among the
+/// [`misplaced_wires`] and checked by adding numbers with the outputs swapped
+/// back. None when the wires are not those of an adder, more than
+/// [`MAX_PAIRS`] look swapped or no pairing makes an adder
+pub fn swapped_pairs(circuit: &Circuit) -> Option<Vec<(String, String)>> {
+    if !circuit.has_adder_wires() {
+        return None;
+    }
+
+    let misplaced = misplaced_wires(circuit);
+    if misplaced.len() % 2 == 1 || misplaced.len() > 2 * MAX_PAIRS {
+        return None;
+    }
+
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    pair_up(circuit, &misplaced, &mut pairs).then(|| {
+        pairs
+            .iter()
+            .map(|&(a, b)| (circuit.names[a].clone(), circuit.names[b].clone()))
+            .collect()
+    })
+}
+
+/// The names of the swapped wires, sorted and joined by commas
+pub fn answer(pairs: &[(String, String)]) -> String {
+    let mut names: Vec<&str> = pairs
+        .iter()
+        .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+        .collect();
+    names.sort();
+    names.join(",")
+}
+
+/// The circuit in Graphviz DOT, gates as boxes between wires. Inputs are
+/// ranked first and outputs last, `highlight` wires are drawn in red
+pub fn dot(circuit: &Circuit, highlight: &[String]) -> String {
+    let mut out = String::from("digraph circuit {\n  rankdir=LR;\n  node [shape=ellipse];\n");
+
+    let mut ranked = |prefix: char, rank: &str| {
+        let mut wires = circuit.bits(prefix);
+        wires.sort_by_key(|&(_, bit)| bit);
+        let names: Vec<String> = wires
+            .iter()
+            .map(|&(wire, _)| format!("\"{}\"", circuit.names[wire]))
+            .collect();
+        if !names.is_empty() {
+            writeln!(out, "  {{ rank={}; {}; }}", rank, names.join("; ")).unwrap();
+        }
+    };
+    ranked('x', "source");
+    ranked('y', "source");
+    ranked('z', "sink");
+
+    for name in highlight {
+        writeln!(out, "  \"{}\" [color=red, fontcolor=red];", name).unwrap();
+    }
+
+    for (i, gate) in circuit.gates.iter().enumerate() {
+        let colour = match gate.op {
+            Op::And => "lightblue",
+            Op::Or => "palegreen",
+            Op::Xor => "khaki",
+        };
+        writeln!(
+            out,
+            "  g{} [shape=box, style=filled, fillcolor={}, label=\"{}\"];",
+            i,
+            colour,
+            gate.op.name()
+        )
+        .unwrap();
+
+        for input in gate.inputs {
+            writeln!(out, "  \"{}\" -> g{};", circuit.names[input], i).unwrap();
+        }
+        writeln!(out, "  g{} -> \"{}\";", i, circuit.names[gate.output]).unwrap();
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+    const EXAMPLE2: &str = include_str!("../test2.txt");
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(parse(EXAMPLE).unwrap().output(), 4);
+        assert_eq!(parse(EXAMPLE2).unwrap().output(), 2024);
+    }
+
+    // The examples of part 1 do not add, so there is nothing to repair
+    #[test]
+    fn part2_needs_an_adder() {
+        assert_eq!(swapped_pairs(&parse(EXAMPLE).unwrap()), None);
+        assert_eq!(swapped_pairs(&parse(EXAMPLE2).unwrap()), None);
+    }
+
+    // A ripple-carry adder of `bits` bits in the puzzle's layout, with the
+    // outputs of the named wires swapped
+    fn adder(bits: u32, swaps: &[(&str, &str)]) -> String {
+        let mut input = String::new();
+        for prefix in ["x", "y"] {
+            for i in 0..bits {
+                writeln!(input, "{}{:02}: {}", prefix, i, i % 3 % 2).unwrap();
+            }
+        }
+        input.push('\n');
+
+        let mut gates: Vec<(String, &str, String, String)> = Vec::new();
+        let mut gate = |a: String, op, b: String, out: String| gates.push((a, op, b, out));
+        gate("x00".into(), "XOR", "y00".into(), "z00".into());
+        gate("x00".into(), "AND", "y00".into(), "c01".into());
+        for i in 1..bits {
+            let carry = format!("c{:02}", i);
+            let next = if i + 1 == bits {
+                format!("z{:02}", bits)
+            } else {
+                format!("c{:02}", i + 1)
+            };
+            gate(
+                format!("x{:02}", i),
+                "XOR",
+                format!("y{:02}", i),
+                format!("s{:02}", i),
+            );
+            gate(
+                format!("x{:02}", i),
+                "AND",
+                format!("y{:02}", i),
+                format!("a{:02}", i),
+            );
+            gate(
+                format!("s{:02}", i),
+                "XOR",
+                carry.clone(),
+                format!("z{:02}", i),
+            );
+            gate(format!("s{:02}", i), "AND", carry, format!("b{:02}", i));
+            gate(format!("a{:02}", i), "OR", format!("b{:02}", i), next);
+        }
+
+        for (a, op, b, out) in gates {
+            let out = swaps.iter().fold(out, |out, &(p, q)| {
+                if out == p {
+                    q.to_string()
+                } else if out == q {
+                    p.to_string()
+                } else {
+                    out
+                }
+            });
+            writeln!(input, "{} {} {} -> {}", a, op, b, out).unwrap();
+        }
+        input
+    }
+
+    #[test]
+    fn sound_adders_add() {
+        let circuit = parse(&adder(12, &[])).unwrap();
+        assert!(misplaced_wires(&circuit).is_empty());
+        assert_eq!(swapped_pairs(&circuit), Some(Vec::new()));
+        assert_eq!(circuit.add(1234, 4000), 5234);
+        assert_eq!(circuit.output(), 0b010010010010 * 2);
+    }
+
+    #[test]
+    fn swapped_outputs_are_found() {
+        let swaps = [
+            ("z05", "c06"),
+            ("s08", "a08"),
+            ("z11", "b11"),
+            ("z15", "a15"),
+        ];
+        let circuit = parse(&adder(20, &swaps)).unwrap();
+
+        let pairs = swapped_pairs(&circuit).unwrap();
+        assert_eq!(pairs.len(), 4);
+        for (a, b) in &pairs {
+            assert!(
+                swaps.contains(&(a, b)) || swaps.contains(&(b, a)),
+                "{} {}",
+                a,
+                b
+            );
+        }
+        assert_eq!(answer(&pairs), "a08,a15,b11,c06,s08,z05,z11,z15");
+    }
+
+    #[test]
+    fn loops_and_missing_wires_are_rejected() {
+        let e = parse("x00: 1\n\nx00 AND abc -> def\nx00 OR def -> abc\n").unwrap_err();
+        assert_eq!(
+            (e.line, e.expected.as_str()),
+            (3, "a gate outside any loop")
+        );
+
+        let e = parse("x00: 1\n\nx00 AND abc -> z00\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 9));
+
+        let e = parse("x00: 1\n\nx00 AND x00 -> z00\nx00 OR x00 -> z00\n").unwrap_err();
+        assert_eq!((e.line, e.column), (4, 15));
+    }
+
+    #[test]
+    fn wires_past_64_bits_are_rejected() {
+        let e = parse("x00: 1\ny64: 0\n\nx00 AND y64 -> z00\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "y64"));
+        assert_eq!(e.expected, "an input bit below y64");
+
+        let e = parse("x00: 1\n\nx00 AND x00 -> z64\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 16, "z64"));
+    }
+
+    #[test]
+    fn dot_has_every_gate() {
+        let circuit = parse(EXAMPLE).unwrap();
+        let dot = dot(&circuit, &["z01".to_string()]);
+        assert!(dot.starts_with("digraph circuit {"));
+        assert!(dot.contains("{ rank=sink; \"z00\"; \"z01\"; \"z02\"; }"));
+        assert!(dot.contains("g1 [shape=box, style=filled, fillcolor=khaki, label=\"XOR\"];"));
+        assert!(dot.contains("\"x01\" -> g1;\n  \"y01\" -> g1;\n  g1 -> \"z01\";"));
+        assert!(dot.contains("\"z01\" [color=red, fontcolor=red];"));
+    }
+}
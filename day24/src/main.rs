@@ -0,0 +1,17 @@
+use day24::{answer, parse, swapped_pairs};
+use parser::{read_input_or_exit, unwrap_or_exit};
+
+fn main() {
+    let (input_file, input) = read_input_or_exit(env!("CARGO_MANIFEST_DIR"));
+    let circuit = unwrap_or_exit(parse(&input), &input_file, &input);
+
+    println!("Output: {}", circuit.output());
+
+    match swapped_pairs(&circuit) {
+        Some(pairs) => {
+            println!("Swapped: {:?}", pairs);
+            println!("Wires: {}", answer(&pairs));
+        }
+        None => println!("Swapping outputs does not make the circuit an adder"),
+    }
+}
@@ -0,0 +1,239 @@
+//! Day 25: Code Chronicle, which keys fit which locks
+#![warn(missing_docs)]
+
+use parser::{Line, ParseError};
+
+/// Locks and keys as the heights of their pins, one per column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematics {
+    /// Columns of every schematic
+    pub width: usize,
+    /// Rows a lock pin and a key pin share, the schematics minus their top and
+    /// bottom rows
+    pub space: usize,
+    /// Pin heights of each lock, from the top
+    pub locks: Vec<Vec<usize>>,
+    /// Pin heights of each key, from the bottom
+    pub keys: Vec<Vec<usize>>,
+}
+
+// Pin heights of a schematic with its full row first, checking that every pin
+// is one run of # from that row
+fn heights(rows: &[Line], kind: &str) -> Result<Vec<usize>, ParseError> {
+    let width = rows[0].text.len();
+    let mut heights = vec![0; width];
+
+    for (i, row) in rows.iter().enumerate().skip(1) {
+        for (column, cell) in row.text.bytes().enumerate() {
+            match cell {
+                b'#' if heights[column] == i - 1 => heights[column] = i,
+                b'#' => {
+                    return Err(row.error(
+                        &row.text[column..column + 1],
+                        &format!("a . past the end of the {} pin", kind),
+                    ))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(heights)
+}
+
+/// Schematics of # and . separated by blank lines, all of the same size.
+/// Locks have a top row of # and a bottom row of ., keys the other way round
+pub fn parse(input: &str) -> Result<Schematics, ParseError> {
+    let sections = parser::sections(input);
+    let Some(first) = sections.first() else {
+        return Err(parser::end_of_input(input, "a schematic"));
+    };
+    let (width, height) = (first[0].text.len(), first.len());
+    if height < 2 {
+        return Err(first[0].missing_next("a second row"));
+    }
+
+    let mut schematics = Schematics {
+        width,
+        space: height - 2,
+        locks: Vec::new(),
+        keys: Vec::new(),
+    };
+
+    for section in &sections {
+        parser::grid_from_lines(
+            section.iter().copied(),
+            |c| c == b'#' || c == b'.',
+            "# or .",
+        )?;
+
+        let (top, bottom) = (section[0], section[section.len() - 1]);
+        if top.text.len() != width {
+            let at = &top.text[width.min(top.text.len())..];
+            return Err(top.error(at, &format!("a schematic {} wide like the first", width)));
+        }
+        if section.len() != height {
+            let expected = format!("a schematic {} rows high like the first", height);
+            return Err(match section.get(height) {
+                Some(extra) => extra.error(extra.text, &expected),
+                None => bottom.missing_next(&expected),
+            });
+        }
+
+        let full = |row: &Line, c: u8| row.text.bytes().all(|cell| cell == c);
+        if full(&top, b'#') {
+            if !full(&bottom, b'.') {
+                return Err(bottom.error(bottom.text, "a bottom row of . under a lock"));
+            }
+            schematics.locks.push(heights(section, "lock")?);
+        } else if full(&top, b'.') {
+            if !full(&bottom, b'#') {
+                return Err(bottom.error(bottom.text, "a bottom row of # under a key"));
+            }
+            let rows: Vec<Line> = section.iter().rev().copied().collect();
+            schematics.keys.push(heights(&rows, "key")?);
+        } else {
+            return Err(top.error(top.text, "a top row of # for a lock or . for a key"));
+        }
+    }
+
+    Ok(schematics)
+}
+
+// Bit sets of keys
+type KeySet = Vec<u64>;
+
+/// The keys fitting each pin height, so the keys fitting a lock are found a
+/// whole word of keys at a time instead of trying every pair
+pub struct KeyIndex {
+    // fits[column][height] holds the keys with room for a lock pin that high
+    fits: Vec<Vec<KeySet>>,
+}
+
+impl KeyIndex {
+    /// Indexes `keys` of `width` columns in `space` rows
+    pub fn new(keys: &[Vec<usize>], width: usize, space: usize) -> KeyIndex {
+        let words = keys.len().div_ceil(64);
+
+        let fits = (0..width)
+            .map(|column| {
+                // Keys up to a height fit lock pins up to space minus that height
+                let mut by_height: Vec<KeySet> = vec![vec![0; words]; space + 1];
+                for (k, key) in keys.iter().enumerate() {
+                    by_height[key[column]][k / 64] |= 1 << (k % 64);
+                }
+
+                let mut fits: Vec<KeySet> = Vec::with_capacity(space + 1);
+                let mut shorter: KeySet = vec![0; words];
+                for keys in &by_height {
+                    for (word, &bits) in shorter.iter_mut().zip(keys) {
+                        *word |= bits;
+                    }
+                    fits.push(shorter.clone());
+                }
+                fits.reverse();
+                fits
+            })
+            .collect();
+
+        KeyIndex { fits }
+    }
+
+    /// How many keys fit `lock` without any pins overlapping
+    pub fn fitting(&self, lock: &[usize]) -> usize {
+        let mut keys = self.fits[0][lock[0]].clone();
+        for (column, &height) in lock.iter().enumerate().skip(1) {
+            for (word, &bits) in keys.iter_mut().zip(&self.fits[column][height]) {
+                *word &= bits;
+            }
+        }
+
+        keys.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+/// Lock and key pairs whose pins do not overlap in any column
+pub fn count_fits(schematics: &Schematics) -> usize {
+    let index = KeyIndex::new(&schematics.keys, schematics.width, schematics.space);
+    schematics
+        .locks
+        .iter()
+        .map(|lock| index.fitting(lock))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let schematics = parse(EXAMPLE).unwrap();
+        assert_eq!(schematics.locks, [[0, 5, 3, 4, 3], [1, 2, 0, 5, 3]]);
+        assert_eq!(schematics.keys[0], [5, 0, 2, 1, 3]);
+        assert_eq!(count_fits(&schematics), 3);
+    }
+
+    #[test]
+    fn broken_pins_are_rejected() {
+        let e = parse("###\n.#.\n#..\n...\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+
+        let e = parse("...\n.#.\n...\n###\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+
+        let e = parse("###\n...\n\n....\n####\n").unwrap_err();
+        assert_eq!((e.line, e.column), (4, 4));
+    }
+
+    // A schematic of pins with the given heights, a lock or a key
+    fn draw(pins: &[usize], space: usize, lock: bool) -> String {
+        let mut rows: Vec<String> = (0..space + 2)
+            .map(|row| {
+                pins.iter()
+                    .map(|&pin| if row <= pin { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        if !lock {
+            rows.reverse();
+        }
+        rows.join("\n")
+    }
+
+    fn schematics() -> impl Strategy<Value = (usize, Vec<Vec<usize>>, Vec<Vec<usize>>)> {
+        (1..8usize, 0..7usize).prop_flat_map(|(width, space)| {
+            let pins = prop::collection::vec(0..=space, width);
+            (
+                Just(space),
+                prop::collection::vec(pins.clone(), 0..20),
+                prop::collection::vec(pins, 0..150),
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn index_matches_every_pair((space, locks, keys) in schematics()) {
+            prop_assume!(!locks.is_empty() || !keys.is_empty());
+            let input: Vec<String> = locks
+                .iter()
+                .map(|lock| draw(lock, space, true))
+                .chain(keys.iter().map(|key| draw(key, space, false)))
+                .collect();
+            let schematics = parse(&input.join("\n\n")).unwrap();
+            prop_assert_eq!(&schematics.locks, &locks);
+            prop_assert_eq!(&schematics.keys, &keys);
+
+            let pairs = locks
+                .iter()
+                .flat_map(|lock| keys.iter().map(move |key| (lock, key)))
+                .filter(|(lock, key)| lock.iter().zip(key.iter()).all(|(l, k)| l + k <= space))
+                .count();
+            prop_assert_eq!(count_fits(&schematics), pairs);
+        }
+    }
+}
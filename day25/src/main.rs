@@ -0,0 +1,14 @@
+use day25::{count_fits, parse};
+use parser::{read_input_or_exit, unwrap_or_exit};
+
+fn main() {
+    let (input_file, input) = read_input_or_exit(env!("CARGO_MANIFEST_DIR"));
+    let schematics = unwrap_or_exit(parse(&input), &input_file, &input);
+
+    println!(
+        "Locks: {}, keys: {}",
+        schematics.locks.len(),
+        schematics.keys.len()
+    );
+    println!("Fitting pairs: {}", count_fits(&schematics));
+}
@@ -0,0 +1,62 @@
+//! Day 3: Mull It Over, adding up the multiplications of corrupted memory
+#![warn(missing_docs)]
+
+use parser::ParseError;
+use regex::Regex;
+
+/// Sum of the `mul(X,Y)` products. With `enable_do_dont`, products after a
+/// `don't()` are skipped until the next `do()`
+pub fn solve(instruction: &str, enable_do_dont: bool) -> u64 {
+    let re_operands = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
+    let re_operator = Regex::new(r"mul\(\d{1,3},\d{1,3}\)|do\(\)|don't\(\)").unwrap();
+    let ops: Vec<&str> = re_operator
+        .find_iter(instruction)
+        .map(|m| m.as_str())
+        .collect();
+
+    let mut mulsum = 0;
+    let mut enabled = true;
+    for op in ops {
+        match op {
+            "don't()" => {
+                enabled = false;
+            }
+            "do()" => {
+                enabled = true;
+            }
+            _ => {
+                let cap = re_operands.captures_iter(op).next().unwrap();
+                let (_, [a, b]) = cap.extract();
+                if !enable_do_dont || enabled {
+                    mulsum += a.parse::<u64>().unwrap() * b.parse::<u64>().unwrap();
+                }
+            }
+        }
+    }
+
+    mulsum
+}
+
+/// Any text is valid corrupted memory, so this never fails
+pub fn parse(input: &str) -> Result<String, ParseError> {
+    Ok(input.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    const EXAMPLE_PART2: &str = include_str!("../test2.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(&parse(EXAMPLE).unwrap(), false), 161);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(&parse(EXAMPLE_PART2).unwrap(), true), 48);
+    }
+}
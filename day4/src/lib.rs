@@ -0,0 +1,126 @@
+//! Day 4: Ceres Search, a word search for XMAS
+#![warn(missing_docs)]
+
+use grid::{Grid, Point};
+use parser::ParseError;
+
+// (vertical_offset, horizontal_offset, value)
+const ALIGNMENTS: [[(isize, isize, u8); 4]; 8] = [
+    [(0, 0, b'X'), (0, 1, b'M'), (0, 2, b'A'), (0, 3, b'S')], // Horizontal
+    [(0, 0, b'X'), (0, -1, b'M'), (0, -2, b'A'), (0, -3, b'S')], // Horizontal Backward
+    [(0, 0, b'X'), (1, 0, b'M'), (2, 0, b'A'), (3, 0, b'S')], // Vertical
+    [(0, 0, b'X'), (-1, 0, b'M'), (-2, 0, b'A'), (-3, 0, b'S')], // Vertical Backward
+    [(0, 0, b'X'), (1, 1, b'M'), (2, 2, b'A'), (3, 3, b'S')], // Diagonal DR
+    [(0, 0, b'X'), (-1, -1, b'M'), (-2, -2, b'A'), (-3, -3, b'S')], // Diagonal UL
+    [(0, 0, b'X'), (-1, 1, b'M'), (-2, 2, b'A'), (-3, 3, b'S')], // Diagonal UR
+    [(0, 0, b'X'), (1, -1, b'M'), (2, -2, b'A'), (3, -3, b'S')], // Diagonal DL
+];
+
+const X_ALIGNMENTS: [[(isize, isize, u8); 5]; 4] = [
+    [
+        (0, 0, b'A'),
+        (-1, -1, b'M'),
+        (-1, 1, b'M'),
+        (1, -1, b'S'),
+        (1, 1, b'S'),
+    ], // Top M M
+    [
+        (0, 0, b'A'),
+        (-1, -1, b'M'),
+        (-1, 1, b'S'),
+        (1, -1, b'M'),
+        (1, 1, b'S'),
+    ], // Top M S
+    [
+        (0, 0, b'A'),
+        (-1, -1, b'S'),
+        (-1, 1, b'M'),
+        (1, -1, b'S'),
+        (1, 1, b'M'),
+    ], // Top M M
+    [
+        (0, 0, b'A'),
+        (-1, -1, b'S'),
+        (-1, 1, b'S'),
+        (1, -1, b'M'),
+        (1, 1, b'M'),
+    ], // Top S S
+];
+
+/// What `Matrix::find_all` looks for
+pub enum AlignmentType {
+    /// XMAS in any of the 8 directions
+    Xmas = 0,
+    /// Two MAS crossing on their A
+    CrossMas = 1,
+}
+
+/// The word search
+pub struct Matrix {
+    /// One letter per cell
+    pub arr: Grid<u8>,
+}
+
+impl Matrix {
+    fn alignment_match(&self, alignment: &[(isize, isize, u8)], start: Point) -> bool {
+        for m in alignment {
+            let (offset_i, offset_j, value) = m;
+            match self.arr.offset(start, (*offset_i, *offset_j)) {
+                Some(p) if self.arr[p] == *value => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Number of times the word appears, overlaps included
+    pub fn find_all(&self, atype: AlignmentType) -> u64 {
+        let mut count = 0;
+        for p in self.arr.points() {
+            match atype {
+                AlignmentType::Xmas => {
+                    for alignment in ALIGNMENTS {
+                        if self.alignment_match(&alignment, p) {
+                            count += 1;
+                        }
+                    }
+                }
+                AlignmentType::CrossMas => {
+                    for alignment in X_ALIGNMENTS {
+                        if self.alignment_match(&alignment, p) {
+                            count += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        count
+    }
+}
+
+/// A grid of uppercase letters
+pub fn parse(input: &str) -> Result<Matrix, ParseError> {
+    let arr = Grid::parse(input, |c| c.is_ascii_uppercase(), "an uppercase letter")?;
+
+    Ok(Matrix { arr })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let matrix = parse(EXAMPLE).unwrap();
+        assert_eq!(matrix.find_all(AlignmentType::Xmas), 18);
+    }
+
+    #[test]
+    fn part2_example() {
+        let matrix = parse(EXAMPLE).unwrap();
+        assert_eq!(matrix.find_all(AlignmentType::CrossMas), 9);
+    }
+}
@@ -0,0 +1,143 @@
+//! Day 5: Print Queue, ordering safety manual updates by page rules
+#![warn(missing_docs)]
+
+use parser::{Line, ParseError};
+use trace::{Fact, NoTrace, Trace};
+
+/// Page numbers are below this
+pub const N_NODE: usize = 105;
+/// `prereq[b][a]` is set when a rule says page `a` comes before page `b`
+pub type Prerequisites = [[bool; N_NODE]; N_NODE];
+
+/// Indices `(i, j)` of the first pair of pages in the wrong order, `j < i`,
+/// or None when the update is in the right order
+pub fn validate_pages(prereq: &Prerequisites, pages: &[usize]) -> Option<(usize, usize)> {
+    for i in 0..pages.len() {
+        for j in 0..i {
+            if prereq[pages[j]][pages[i]] {
+                return Some((i, j));
+            }
+        }
+    }
+    None
+}
+
+/// The update reordered to follow the rules, by swapping misplaced pages
+pub fn fix_pages(prereq: &Prerequisites, pages: &[usize]) -> Vec<usize> {
+    let mut fixed = pages.to_vec();
+
+    let mut res = validate_pages(prereq, &fixed);
+    while let Some((i, j)) = res {
+        fixed.swap(i, j);
+
+        res = validate_pages(prereq, &fixed);
+    }
+    fixed
+}
+
+fn parse_page(line: &Line, field: &str) -> Result<usize, ParseError> {
+    match field.parse::<usize>() {
+        Ok(page) if page < N_NODE => Ok(page),
+        _ => Err(line.error(field, &format!("a page number below {}", N_NODE))),
+    }
+}
+
+/// The rules as a matrix and the updates as lists of pages
+pub fn parse(input: &str) -> Result<(Box<Prerequisites>, Vec<Vec<usize>>), ParseError> {
+    let mut line_iter = parser::lines(input);
+    let mut line = line_iter.next();
+
+    let mut prereq = Box::new([[false; N_NODE]; N_NODE]);
+
+    // Load Prerequisites
+    while let Some(rule) = line.filter(|l| !l.is_empty()) {
+        let (from, to) = rule.split_once("|", "a rule like 47|53")?;
+
+        let from = parse_page(&rule, from)?;
+        let to = parse_page(&rule, to)?;
+
+        prereq[to][from] = true;
+
+        line = line_iter.next();
+    }
+
+    if line.is_none() {
+        return Err(parser::end_of_input(
+            input,
+            "a blank line followed by updates",
+        ));
+    }
+
+    // Load Queries
+    let updates: Vec<Vec<usize>> = line_iter
+        .map(|line| line.text.split(",").map(|p| parse_page(&line, p)).collect())
+        .collect::<Result<_, _>>()?;
+
+    Ok((prereq, updates))
+}
+
+/// Returns (sum of middle pages of valid updates, sum of middle pages of fixed updates)
+pub fn solve(prereq: &Prerequisites, updates: &[Vec<usize>]) -> (usize, usize) {
+    solve_traced(prereq, updates, &mut NoTrace)
+}
+
+/// [`solve`], reporting each update in the wrong order as a "rejected" fact
+/// with the first rule it breaks
+pub fn solve_traced<T: Trace + ?Sized>(
+    prereq: &Prerequisites,
+    updates: &[Vec<usize>],
+    trace: &mut T,
+) -> (usize, usize) {
+    let mut midsum: usize = 0;
+    let mut fixedsum: usize = 0;
+
+    for (n, pages) in updates.iter().enumerate() {
+        match validate_pages(prereq, pages) {
+            None => midsum += pages[pages.len() / 2],
+            Some((i, j)) => {
+                if trace.enabled() {
+                    trace.fact(
+                        Fact::new("rejected")
+                            .with("update", n + 1)
+                            .with("pages", pages.as_slice())
+                            .with("rule", format!("{}|{}", pages[i], pages[j])),
+                    );
+                }
+
+                let fixed = fix_pages(prereq, pages);
+                fixedsum += fixed[fixed.len() / 2];
+            }
+        }
+    }
+
+    (midsum, fixedsum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let (prereq, updates) = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&prereq, &updates).0, 143);
+    }
+
+    #[test]
+    fn part2_example() {
+        let (prereq, updates) = parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&prereq, &updates).1, 123);
+    }
+
+    #[test]
+    fn rejections_name_the_broken_rule() {
+        let (prereq, updates) = parse(EXAMPLE).unwrap();
+        let mut facts: Vec<Fact> = Vec::new();
+        solve_traced(&prereq, &updates, &mut facts);
+
+        let rules: Vec<String> = facts.iter().map(|f| f.fields[2].1.to_string()).collect();
+        assert_eq!(rules, ["97|75", "29|13", "75|13"]);
+    }
+}
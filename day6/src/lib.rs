@@ -0,0 +1,174 @@
+//! Day 6: Guard Gallivant, following a guard that turns right at obstructions
+#![warn(missing_docs)]
+
+use grid::{Dir, Grid, Point};
+use parser::ParseError;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::HashSet;
+use viz::{Colour, Frame, Palette, Picture, Rgb, Sink};
+
+#[derive(Default)]
+struct LoopDetector {
+    collisions: HashSet<(Point, Point)>,
+    prev_pos: Option<Point>,
+}
+
+impl LoopDetector {
+    fn add(&mut self, pos: Point) -> bool {
+        // Consecutive obstructions happening twice (prev_hashtag, curr_hashtag) will cause loop
+        if let Some(prev_pos) = self.prev_pos {
+            let consecutive_hashtag = (prev_pos, pos);
+
+            if self.collisions.contains(&consecutive_hashtag) {
+                // Loop
+                return true;
+            }
+            self.collisions.insert(consecutive_hashtag);
+        }
+
+        self.prev_pos = Some(pos);
+        false
+    }
+}
+
+/// Number of distinct positions the guard visits before leaving the map, or
+/// None when the guard walks in a loop. Visited cells are marked 'X'
+pub fn walk(map: &mut Grid<u8>) -> Option<u64> {
+    walk_with(map, |_, _, _| true)
+}
+
+// Calls `observe` with the map, the guard and its direction before every step,
+// the walk stops early when it returns false
+fn walk_with(
+    map: &mut Grid<u8>,
+    mut observe: impl FnMut(&Grid<u8>, Point, Dir) -> bool,
+) -> Option<u64> {
+    let mut pos: Point = map.find(b'^').unwrap();
+    let mut dir: Dir = Dir::UP;
+    let mut count = 1;
+
+    let mut loop_detector: LoopDetector = Default::default();
+
+    loop {
+        if map[pos] == b'.' {
+            map[pos] = b'X';
+            count += 1;
+        }
+
+        if !observe(map, pos, dir) {
+            break;
+        }
+
+        let Some(next_pos) = map.step(pos, dir) else {
+            break;
+        };
+
+        if map[next_pos] == b'#' {
+            dir = dir.turn_right();
+
+            if loop_detector.add(next_pos) {
+                return None;
+            }
+        } else {
+            pos = next_pos;
+        }
+    }
+
+    Some(count)
+}
+
+fn frame(title: String, map: &Grid<u8>) -> Frame {
+    Frame::new(title, map, |&c| match c {
+        b'#' => ('#', Colour::Grey),
+        b'X' | b'^' => ('X', Colour::Cyan),
+        _ => ('.', Colour::Plain),
+    })
+}
+
+/// Shows the guard step by step, with the cells it visited in cyan
+pub fn animate_walk(map: &Grid<u8>, sink: &mut dyn Sink) {
+    let mut map = map.clone();
+    let mut steps = 0;
+
+    let result = walk_with(&mut map, |map, guard, dir| {
+        let mut frame = frame(format!("Step {}", steps), map);
+        frame.set(guard, viz::arrow(dir), Colour::Red);
+        steps += 1;
+        sink.show(&frame)
+    });
+
+    let title = match result {
+        Some(count) => format!(
+            "The guard left after {} steps, {} distinct cells",
+            steps, count
+        ),
+        None => format!("The guard is stuck in a loop after {} steps", steps),
+    };
+    sink.show(&frame(title, &map));
+}
+
+/// The map after the walk with the visited cells in cyan
+pub fn picture(map: &Grid<u8>) -> Picture<'static> {
+    let mut walked = map.clone();
+    walk(&mut walked);
+
+    let palette = Palette::new(&[
+        (b'#', Rgb(60, 60, 70)),
+        (b'.', Rgb(245, 245, 245)),
+        (b'X', Rgb(42, 161, 152)),
+        (b'^', Rgb(220, 50, 47)),
+    ]);
+    let mut picture = Picture::new(map.height(), map.width(), palette);
+    picture.add(walked);
+    picture
+}
+
+/// Number of empty cells where a new obstruction would trap the guard in a loop
+pub fn find_all_obstacles(map: &Grid<u8>) -> u64 {
+    let candidates: Vec<Point> = map.points().filter(|&p| map[p] == b'.').collect();
+
+    #[cfg(feature = "parallel")]
+    let candidates = candidates.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let candidates = candidates.into_iter();
+
+    candidates
+        .filter(|&p| {
+            let mut m = map.clone();
+            m[p] = b'#';
+            // Loop
+            walk(&mut m).is_none()
+        })
+        .count() as u64
+}
+
+/// A map of '.', '#' and the guard '^'
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let map = Grid::parse(input, |c| b".#^".contains(&c), "'.', '#' or '^'")?;
+
+    if map.find(b'^').is_none() {
+        return Err(parser::end_of_input(input, "a guard '^' on the map"));
+    }
+
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let mut map = parse(EXAMPLE).unwrap();
+        assert_eq!(walk(&mut map), Some(41));
+    }
+
+    #[test]
+    fn part2_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(find_all_obstacles(&map), 6);
+    }
+}
@@ -0,0 +1,244 @@
+//! Day 7: Bridge Repair, inserting operators to make calibration equations true
+#![warn(missing_docs)]
+
+use num_traits::CheckedAdd;
+use parser::ParseError;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt;
+use trace::{Fact, NoTrace, Trace};
+
+/// Sums of test values need more than u64 when there are many large ones
+#[cfg(not(feature = "bigint"))]
+pub type Total = u64;
+/// Sums of test values, without an upper bound
+#[cfg(feature = "bigint")]
+pub type Total = num_bigint::BigUint;
+
+/// An equation or the sum of test values does not fit in 64 bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the calibration result does not fit in 64 bits")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+// The digits of `b` appended to `a`, None when that does not fit in a u64
+fn concat(a: u64, b: u64) -> Option<u64> {
+    let mut shift: u64 = 10;
+    while shift <= b {
+        shift = shift.checked_mul(10)?;
+    }
+    a.checked_mul(shift)?.checked_add(b)
+}
+
+// A value past u64::MAX is above any target, and only a 0 operand can bring it
+// back down
+fn overflowed(arr: &[u64], index: usize) -> Result<(), Overflow> {
+    if arr[index + 1..].contains(&0) {
+        Err(Overflow)
+    } else {
+        Ok(())
+    }
+}
+
+/// Whether `+`, `*` and, with `concat_mode`, `||` evaluated left to right can turn
+/// `arr[index..]` into `target`, starting from `sum`
+pub fn solve(
+    target: u64,
+    arr: &[u64],
+    index: usize,
+    sum: u64,
+    concat_mode: bool,
+) -> Result<bool, Overflow> {
+    search(target, arr, index, sum, concat_mode, None)
+}
+
+/// The operators between the numbers of `arr` that turn it into `target`, the
+/// first ones [`solve`] would find, or None when the equation cannot be made true
+pub fn operators(
+    target: u64,
+    arr: &[u64],
+    concat_mode: bool,
+) -> Result<Option<Vec<&'static str>>, Overflow> {
+    let mut ops = Vec::new();
+    Ok(search(target, arr, 1, arr[0], concat_mode, Some(&mut ops))?.then_some(ops))
+}
+
+// Tries the operators in order, recording the ones taken in `ops` when given.
+// They are popped again on the way back, so a solution leaves exactly its own
+fn search(
+    target: u64,
+    arr: &[u64],
+    index: usize,
+    sum: u64,
+    concat_mode: bool,
+    mut ops: Option<&mut Vec<&'static str>>,
+) -> Result<bool, Overflow> {
+    if index >= arr.len() {
+        return Ok(sum == target);
+    }
+
+    let candidates = [
+        ("+", sum.checked_add(arr[index])),
+        ("*", sum.checked_mul(arr[index])),
+        ("||", concat(sum, arr[index])),
+    ];
+    let tried = if concat_mode { 3 } else { 2 };
+
+    for &(op, next) in &candidates[..tried] {
+        let Some(next) = next else {
+            overflowed(arr, index)?;
+            continue;
+        };
+
+        if let Some(ops) = ops.as_deref_mut() {
+            ops.push(op);
+        }
+        if search(
+            target,
+            arr,
+            index + 1,
+            next,
+            concat_mode,
+            ops.as_deref_mut(),
+        )? {
+            return Ok(true);
+        }
+        if let Some(ops) = ops.as_deref_mut() {
+            ops.pop();
+        }
+    }
+
+    Ok(false)
+}
+
+/// One equation per line, the test value and its numbers
+pub fn parse(input: &str) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
+    parser::lines(input)
+        .map(|line| {
+            let (target, numbers) = line.split_once(": ", "an equation like 190: 10 19")?;
+            let target = line.parse::<u64>(target, "a test value")?;
+            let arr: Vec<u64> = line.parse_list::<u64>(numbers, " ", "a number")?;
+
+            Ok((target, arr))
+        })
+        .collect()
+}
+
+/// Sum of the test values of the equations that can be made true
+pub fn calibration_sum(
+    equations: &[(u64, Vec<u64>)],
+    concat_mode: bool,
+) -> Result<Total, Overflow> {
+    calibration_sum_traced(equations, concat_mode, &mut NoTrace)
+}
+
+/// [`calibration_sum`], reporting the operators that make each equation true
+/// as a "solved" fact
+pub fn calibration_sum_traced<T: Trace + ?Sized>(
+    equations: &[(u64, Vec<u64>)],
+    concat_mode: bool,
+    trace: &mut T,
+) -> Result<Total, Overflow> {
+    #[cfg(feature = "parallel")]
+    let each_equation = equations.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let each_equation = equations.iter();
+
+    // The operators are only kept when tracing, each equation is searched once
+    let tracing = trace.enabled();
+    let solved: Vec<Option<Vec<&'static str>>> = each_equation
+        .map(|(target, arr)| {
+            if tracing {
+                operators(*target, arr, concat_mode)
+            } else {
+                Ok(solve(*target, arr, 1, arr[0], concat_mode)?.then(Vec::new))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut total = Total::from(0u8);
+    for (n, ((target, arr), solved)) in equations.iter().zip(solved).enumerate() {
+        if let Some(ops) = solved {
+            if tracing {
+                let mut expression = arr[0].to_string();
+                for (op, number) in ops.iter().zip(&arr[1..]) {
+                    expression += &format!(" {} {}", op, number);
+                }
+                trace.fact(
+                    Fact::new("solved")
+                        .with("equation", n + 1)
+                        .with("target", *target)
+                        .with("operators", expression),
+                );
+            }
+            total = CheckedAdd::checked_add(&total, &Total::from(*target)).ok_or(Overflow)?;
+        }
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let equations = parse(EXAMPLE).unwrap();
+        assert_eq!(calibration_sum(&equations, false), Ok(Total::from(3749u32)));
+    }
+
+    #[test]
+    fn part2_example() {
+        let equations = parse(EXAMPLE).unwrap();
+        assert_eq!(calibration_sum(&equations, true), Ok(Total::from(11387u32)));
+    }
+
+    #[test]
+    fn operators_are_reported() {
+        let equations = parse(EXAMPLE).unwrap();
+        let mut facts: Vec<Fact> = Vec::new();
+        calibration_sum_traced(&equations, true, &mut facts).unwrap();
+
+        let expressions: Vec<String> = facts.iter().map(|f| f.fields[2].1.to_string()).collect();
+        assert_eq!(
+            expressions,
+            [
+                "10 * 19",
+                "81 + 40 * 27",
+                "15 || 6",
+                "6 * 8 || 6 * 15",
+                "17 || 8 + 14",
+                "11 + 6 * 16 + 20"
+            ]
+        );
+    }
+
+    #[test]
+    fn concatenation_past_u64_fails_the_equation() {
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(7, 0), Some(70));
+        assert_eq!(concat(u64::MAX / 10, 99), None);
+
+        let arr = [10_u64.pow(10), 10_u64.pow(10), 5];
+        assert_eq!(solve(5, &arr, 1, arr[0], true), Ok(false));
+        // A 0 operand could bring the overflowed value back to the target
+        let arr = [10_u64.pow(10), 10_u64.pow(10), 0];
+        assert_eq!(solve(1, &arr, 1, arr[0], true), Err(Overflow));
+    }
+
+    #[cfg(not(feature = "bigint"))]
+    #[test]
+    fn large_sums_overflow() {
+        let equations = vec![(u64::MAX, vec![u64::MAX]), (1, vec![1])];
+        assert_eq!(calibration_sum(&equations, false), Err(Overflow));
+    }
+}
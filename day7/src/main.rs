@@ -1,46 +1,19 @@
-use std::fs;
-
-fn solve(target: u64, arr: &Vec<u64>, index: usize, sum: u64, concat_mode: bool) -> bool {
-    if index >= arr.len() {
-        return sum == target;
-    }
-    return solve(target, &arr, index + 1, sum + arr[index], concat_mode)
-        || solve(target, &arr, index + 1, sum * arr[index], concat_mode)
-        || (concat_mode
-            && solve(
-                target,
-                &arr,
-                index + 1,
-                (sum.to_string() + arr[index].to_string().as_str())
-                    .parse::<u64>()
-                    .unwrap(),
-                concat_mode,
-            ));
-}
+use day7::{calibration_sum, parse};
+use parser::{read_input_or_exit, unwrap_or_exit};
+use std::process;
 
 fn main() {
-    let input_file = "input.txt";
-    let mut sum: (u64, u64) = (0, 0);
-
-    for line in fs::read_to_string(input_file).unwrap().lines() {
-        let mut parts = line.split(": ");
-        let target = parts.next().unwrap().parse::<u64>().unwrap();
-        let arr: Vec<u64> = parts
-            .next()
-            .unwrap()
-            .split(" ")
-            .map(|n| n.parse::<u64>().unwrap())
-            .collect();
+    let (input_file, input) = read_input_or_exit(env!("CARGO_MANIFEST_DIR"));
+    let equations = unwrap_or_exit(parse(&input), &input_file, &input);
 
-        println!("{} {:?}", target, arr);
-
-        if solve(target, &arr, 1, arr[0], false) {
-            sum.0 += target;
-        }
-        if solve(target, &arr, 1, arr[0], true) {
-            sum.1 += target;
+    match (
+        calibration_sum(&equations, false),
+        calibration_sum(&equations, true),
+    ) {
+        (Ok(part1), Ok(part2)) => println!("Sum = {} {}", part1, part2),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{}", e);
+            process::exit(1);
         }
     }
-
-    println!("Sum = {} {}", sum.0, sum.1);
 }
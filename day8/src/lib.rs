@@ -0,0 +1,149 @@
+//! Day 8: Resonant Collinearity, finding the antinodes of antenna pairs
+#![warn(missing_docs)]
+
+use grid::{Grid, Point};
+use parser::ParseError;
+use std::collections::{HashMap, HashSet};
+use viz::{Palette, Picture, Rgb};
+
+// Key of the antinodes in pictures, the puzzle draws them as '#' too
+const ANTINODE: u8 = b'#';
+
+/// Positions of the antinodes on the map
+pub type Antinodes = HashSet<Point>;
+/// The map and where the antennas of each frequency are
+pub struct AntennaMap {
+    /// Antenna positions by frequency
+    pub antennas: HashMap<u8, Vec<Point>>,
+    /// The map as given, '.' or a frequency per cell
+    pub map: Grid<u8>,
+}
+
+impl AntennaMap {
+    fn calculate_antinodes(
+        &self,
+        antenna_base: Point,
+        antenna_another: Point,
+        unlimited_mode: bool,
+    ) -> Antinodes {
+        // Antinodes are on the far side of the base antenna, away from the other one
+        let (di, dj) = antenna_another.delta(antenna_base);
+
+        let mut antinodes: Antinodes = if unlimited_mode {
+            HashSet::from_iter([antenna_base])
+        } else {
+            HashSet::new()
+        };
+        let mut multipiler = 1;
+
+        while let Some(antinode) = self
+            .map
+            .offset(antenna_base, (di * multipiler, dj * multipiler))
+        {
+            antinodes.insert(antinode);
+            multipiler += 1;
+
+            if !unlimited_mode {
+                break;
+            }
+        }
+
+        antinodes
+    }
+
+    /// Number of distinct antinode positions, see `antinodes`
+    pub fn find_all_antinodes(&self, unlimited_mode: bool) -> usize {
+        self.antinodes(unlimited_mode).len()
+    }
+
+    /// Antinodes of every pair of antennas of the same frequency. With
+    /// `unlimited_mode`, every position in line with a pair counts, not just the
+    /// two at twice the distance
+    pub fn antinodes(&self, unlimited_mode: bool) -> Antinodes {
+        let mut antinodes: Antinodes = HashSet::new();
+
+        for freq in self.antennas.keys() {
+            let antennas_freq = self.antennas.get(freq).unwrap();
+            let len = antennas_freq.len();
+            for i in 0..len {
+                for j in 0..len {
+                    if i != j {
+                        let calculated_antinodes: Antinodes = self.calculate_antinodes(
+                            antennas_freq[i],
+                            antennas_freq[j],
+                            unlimited_mode,
+                        );
+
+                        antinodes.extend(calculated_antinodes);
+                    }
+                }
+            }
+        }
+
+        antinodes
+    }
+
+    /// Antinodes in red, antennas are drawn over them in a colour per frequency
+    pub fn picture(&self, unlimited_mode: bool) -> Picture<'_> {
+        let palette = Palette::new(&[(b'.', Rgb(245, 245, 245)), (ANTINODE, Rgb(220, 50, 47))]);
+        let mut picture = Picture::new(self.map.height(), self.map.width(), palette);
+
+        let mut antinodes: Grid<Option<u8>> = Grid::new(self.map.height(), self.map.width(), None);
+        for p in self.antinodes(unlimited_mode) {
+            antinodes[p] = Some(ANTINODE);
+        }
+
+        picture.add(&self.map);
+        picture.add(antinodes);
+        picture.add(self.map.map(|&c| (c != b'.').then_some(c)));
+        picture
+    }
+}
+
+/// A map of '.' and antennas, one letter or digit per frequency
+pub fn parse(input: &str) -> Result<AntennaMap, ParseError> {
+    let map = Grid::parse(
+        input,
+        |c| c == b'.' || c.is_ascii_alphanumeric(),
+        "'.' or an antenna frequency",
+    )?;
+
+    let mut antennas: HashMap<u8, Vec<Point>> = HashMap::new();
+    for (p, &c) in map.iter() {
+        if c != b'.' {
+            antennas.entry(c).or_default().push(p);
+        }
+    }
+
+    Ok(AntennaMap { antennas, map })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(map.find_all_antinodes(false), 14);
+    }
+
+    #[test]
+    fn part2_example() {
+        let map = parse(EXAMPLE).unwrap();
+        assert_eq!(map.find_all_antinodes(true), 34);
+    }
+
+    #[test]
+    fn picture_draws_antennas_over_antinodes() {
+        let map = parse(EXAMPLE).unwrap();
+        let ppm = map.picture(false).ppm(1);
+        let pixels = &ppm[ppm.len() - map.map.height() * map.map.width() * 3..];
+        let red = pixels.chunks(3).filter(|&p| p == [220, 50, 47]).count();
+
+        // One of the 14 antinodes is on the tile of an antenna
+        assert_eq!(red, 13);
+    }
+}
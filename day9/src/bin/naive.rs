@@ -0,0 +1,11 @@
+use day9::naive::{compact_files, expand, rearrange};
+use day9::parse;
+use parser::{read_input_or_exit, unwrap_or_exit};
+
+fn main() {
+    let (input_file, input) = read_input_or_exit(env!("CARGO_MANIFEST_DIR"));
+    let disk_map = unwrap_or_exit(parse(&input), &input_file, &input);
+
+    println!("Checksum {}", rearrange(&mut expand(&disk_map)));
+    println!("Checksum {}", compact_files(&mut expand(&disk_map)));
+}
@@ -0,0 +1,288 @@
+//! Day 9: Disk Fragmenter, compacting files and computing the filesystem checksum
+#![warn(missing_docs)]
+
+/// Reference implementation the fast one is tested against
+pub mod naive;
+
+use num_traits::{CheckedAdd, CheckedMul};
+use parser::ParseError;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Checksums of large disks need more than u64
+#[cfg(not(feature = "bigint"))]
+pub type Checksum = u64;
+/// Checksums without an upper bound
+#[cfg(feature = "bigint")]
+pub type Checksum = num_bigint::BigUint;
+
+/// The checksum does not fit in 64 bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the checksum does not fit in 64 bits, try the bigint feature"
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+fn checksum_of(n: usize) -> Result<Checksum, Overflow> {
+    let n = u64::try_from(n).map_err(|_| Overflow)?;
+    Ok(Checksum::from(n))
+}
+
+fn add(a: &Checksum, b: &Checksum) -> Result<Checksum, Overflow> {
+    CheckedAdd::checked_add(a, b).ok_or(Overflow)
+}
+
+fn mul(a: &Checksum, b: &Checksum) -> Result<Checksum, Overflow> {
+    CheckedMul::checked_mul(a, b).ok_or(Overflow)
+}
+
+#[derive(Debug)]
+struct DiskChunk {
+    id: Option<usize>,
+    size: usize,
+    start: usize,
+}
+
+/// Files and free space as runs of blocks
+#[derive(Debug)]
+pub struct Disk {
+    free_chunks: VecDeque<DiskChunk>,
+    alloc_chunks: Vec<DiskChunk>,
+    used: usize,
+}
+
+impl DiskChunk {
+    // Only files have a checksum
+    fn get_chunk_checksum(&self) -> Result<Checksum, Overflow> {
+        let id = checksum_of(self.id.expect("free space has no checksum"))?;
+        if self.size == 0 {
+            return Ok(Checksum::from(0u8));
+        }
+
+        // Sum of the positions from the first block to the last one
+        let ends = add(
+            &checksum_of(self.start)?,
+            &checksum_of(self.start + self.size - 1)?,
+        )?;
+        let positions = mul(&ends, &checksum_of(self.size)?)? / Checksum::from(2u8);
+
+        mul(&positions, &id)
+    }
+}
+
+/// The disk described by a disk map, whose digits alternate between file
+/// and free space lengths
+pub fn expand(disk_map: &str) -> Disk {
+    let mut disk: Disk = Disk {
+        free_chunks: VecDeque::new(),
+        alloc_chunks: Vec::new(),
+        used: 0,
+    };
+    let mut index = 0;
+
+    for (i, s) in disk_map.chars().enumerate() {
+        let size = s.to_string().parse::<usize>().unwrap();
+
+        if i % 2 == 0 {
+            disk.alloc_chunks.push(DiskChunk {
+                id: Some(i / 2),
+                size,
+                start: index,
+            });
+            disk.used += size;
+        } else {
+            disk.free_chunks.push_back(DiskChunk {
+                id: None,
+                size,
+                start: index,
+            });
+        }
+
+        index += size;
+    }
+
+    disk
+}
+
+/// Checksum after moving the files into the free space on their left: block
+/// by block, or with `chunk_mode` whole files at once
+pub fn rearranged_checksum(disk: &mut Disk, chunk_mode: bool) -> Result<Checksum, Overflow> {
+    let mut checksum = Checksum::from(0u8);
+
+    while !disk.free_chunks.is_empty() && !disk.alloc_chunks.is_empty() {
+        if disk.free_chunks.front().unwrap().start >= disk.alloc_chunks.last().unwrap().start {
+            break;
+        }
+
+        let mut free_chunk = disk.free_chunks.pop_front().unwrap();
+        let mut alloc_chunk = disk.alloc_chunks.pop().unwrap();
+
+        if alloc_chunk.size > free_chunk.size {
+            // Split is not allowed in chunk mode
+            if chunk_mode {
+                // Find first-fit
+                let mut found = false;
+                disk.free_chunks.push_front(free_chunk);
+                for i in 0..disk.free_chunks.len() {
+                    if disk.free_chunks[i].start > alloc_chunk.start {
+                        break;
+                    }
+                    if disk.free_chunks[i].size >= alloc_chunk.size {
+                        let new_chunk = DiskChunk {
+                            id: alloc_chunk.id,
+                            size: alloc_chunk.size,
+                            start: disk.free_chunks[i].start,
+                        };
+                        checksum = add(&checksum, &new_chunk.get_chunk_checksum()?)?;
+
+                        if disk.free_chunks[i].size == alloc_chunk.size {
+                            disk.free_chunks.remove(i);
+                        } else {
+                            disk.free_chunks[i].size -= alloc_chunk.size;
+                            disk.free_chunks[i].start += alloc_chunk.size;
+                        }
+                        found = true;
+                        break;
+                    }
+                }
+                if !found {
+                    checksum = add(&checksum, &alloc_chunk.get_chunk_checksum()?)?;
+                }
+            } else {
+                // Split Alloc Chunk
+                let new_chunk = DiskChunk {
+                    id: alloc_chunk.id,
+                    size: free_chunk.size,
+                    start: free_chunk.start,
+                };
+                checksum = add(&checksum, &new_chunk.get_chunk_checksum()?)?;
+
+                alloc_chunk.size -= free_chunk.size;
+                disk.alloc_chunks.push(alloc_chunk);
+            }
+        } else if alloc_chunk.size < free_chunk.size {
+            // Split Free Chunk
+            let new_chunk = DiskChunk {
+                id: alloc_chunk.id,
+                size: alloc_chunk.size,
+                start: free_chunk.start,
+            };
+            checksum = add(&checksum, &new_chunk.get_chunk_checksum()?)?;
+
+            free_chunk.start += alloc_chunk.size;
+            free_chunk.size -= alloc_chunk.size;
+            disk.free_chunks.push_front(free_chunk);
+        } else {
+            // Exactly same size, no need to split
+            let new_chunk = DiskChunk {
+                id: alloc_chunk.id,
+                size: alloc_chunk.size,
+                start: free_chunk.start,
+            };
+            checksum = add(&checksum, &new_chunk.get_chunk_checksum()?)?;
+        }
+    }
+
+    while let Some(chunk) = disk.alloc_chunks.pop() {
+        checksum = add(&checksum, &chunk.get_chunk_checksum()?)?;
+    }
+
+    Ok(checksum)
+}
+
+/// The disk map is a single line of digits
+pub fn parse(input: &str) -> Result<String, ParseError> {
+    let mut lines = parser::lines(input);
+    let line = lines
+        .next()
+        .ok_or_else(|| parser::end_of_input(input, "a disk map"))?;
+
+    if let Some(j) = line.text.find(|c: char| !c.is_ascii_digit()) {
+        let end = j + line.text[j..].chars().next().unwrap().len_utf8();
+        return Err(line.error(&line.text[j..end], "a digit"));
+    }
+
+    if let Some(extra) = lines.next() {
+        return Err(extra.error(extra.text, "the end of the disk map"));
+    }
+
+    Ok(line.text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn part1_example() {
+        let disk_map = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            rearranged_checksum(&mut expand(&disk_map), false),
+            Ok(Checksum::from(1928u32))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        let disk_map = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            rearranged_checksum(&mut expand(&disk_map), true),
+            Ok(Checksum::from(2858u32))
+        );
+    }
+
+    #[test]
+    fn chunk_checksum_is_checked() {
+        let chunk = |id: usize, start: usize| DiskChunk {
+            id: Some(id),
+            size: 3,
+            start,
+        };
+        assert_eq!(chunk(2, 5).get_chunk_checksum(), Ok(Checksum::from(36u8)));
+
+        let huge = chunk(1 << 40, 1 << 40).get_chunk_checksum();
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(huge, Err(Overflow));
+        #[cfg(feature = "bigint")]
+        assert_eq!(
+            huge,
+            Ok(Checksum::from(3u8) * (1u64 << 40) * (1u64 << 40) + Checksum::from(3u64 << 40))
+        );
+    }
+
+    // File sizes are never 0 in the puzzle, free space can be
+    fn disk_map() -> impl Strategy<Value = String> {
+        prop::collection::vec((1..=9u8, 0..=9u8), 1..20).prop_map(|chunks| {
+            chunks
+                .iter()
+                .flat_map(|&(file, free)| [file, free])
+                .map(|d| (b'0' + d) as char)
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn chunked_matches_naive(disk_map in disk_map()) {
+            prop_assert_eq!(
+                rearranged_checksum(&mut expand(&disk_map), false),
+                checksum_of(naive::rearrange(&mut naive::expand(&disk_map)))
+            );
+            prop_assert_eq!(
+                rearranged_checksum(&mut expand(&disk_map), true),
+                checksum_of(naive::compact_files(&mut naive::expand(&disk_map)))
+            );
+        }
+    }
+}
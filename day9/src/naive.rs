@@ -1,30 +1,41 @@
-use std::fs;
+//! Block-by-block versions of both parts, slow but easy to check by hand
 
+/// Every block of the disk
 #[derive(Debug)]
-struct Disk {
-    map: Vec<Option<usize>>,
+pub struct Disk {
+    /// The file ID of each block, None for free space
+    pub map: Vec<Option<usize>>,
 }
 
-fn expand(disk_map: &str) -> Disk {
+/// The blocks described by a disk map
+pub fn expand(disk_map: &str) -> Disk {
     let mut map: Vec<Option<usize>> = Vec::new();
 
     for (i, s) in disk_map.chars().enumerate() {
         let size = s.to_string().parse::<usize>().unwrap();
 
         if i % 2 == 0 {
-            map.extend(std::iter::repeat(Some(i / 2)).take(size));
+            map.extend(std::iter::repeat_n(Some(i / 2), size));
         } else {
-            map.extend(std::iter::repeat(None).take(size));
+            map.extend(std::iter::repeat_n(None, size));
         }
     }
 
     Disk { map }
 }
 
-fn rearrange(disk: &mut Disk) -> usize {
+fn checksum(disk: &Disk) -> usize {
+    disk.map
+        .iter()
+        .enumerate()
+        .filter_map(|(i, id)| id.map(|id| i * id))
+        .sum()
+}
+
+/// Moves the last block into the first free one until there is no gap left
+pub fn rearrange(disk: &mut Disk) -> usize {
     let mut start: usize = 0;
-    let mut end: usize = disk.map.len() - 1;
-    let mut checksum: usize = 0;
+    let mut end: usize = disk.map.len().saturating_sub(1);
 
     loop {
         while start < end && disk.map[end].is_none() {
@@ -32,7 +43,6 @@ fn rearrange(disk: &mut Disk) -> usize {
         }
 
         while start < end && disk.map[start].is_some() {
-            checksum += disk.map[start].unwrap() * start;
             start += 1;
         }
 
@@ -44,19 +54,40 @@ fn rearrange(disk: &mut Disk) -> usize {
         disk.map[end] = None;
     }
 
-    // Add the rest
-    while disk.map[start].is_some() {
-        checksum += disk.map[start].unwrap() * start;
-        start += 1;
-    }
-
-    checksum
+    checksum(disk)
 }
 
-fn main() {
-    let input_file = "test.txt";
-    let disk_map = fs::read_to_string(input_file).unwrap();
-    let mut disk = expand(&disk_map.trim());
+/// Moves each whole file, highest ID first, into the leftmost gap before it that fits
+pub fn compact_files(disk: &mut Disk) -> usize {
+    let max_id = disk.map.iter().flatten().copied().max().unwrap_or(0);
+
+    for id in (0..=max_id).rev() {
+        let Some(start) = disk.map.iter().position(|&b| b == Some(id)) else {
+            continue;
+        };
+        let size = disk.map[start..]
+            .iter()
+            .take_while(|&&b| b == Some(id))
+            .count();
+
+        let mut gap = 0;
+        for i in 0..start {
+            if disk.map[i].is_some() {
+                gap = 0;
+                continue;
+            }
+
+            gap += 1;
+            if gap == size {
+                let to = i + 1 - size;
+                for k in 0..size {
+                    disk.map[to + k] = Some(id);
+                    disk.map[start + k] = None;
+                }
+                break;
+            }
+        }
+    }
 
-    println!("Checksum {}", rearrange(&mut disk));
+    checksum(disk)
 }
@@ -0,0 +1,276 @@
+use parser::{Line, ParseError};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// (vertical_offset, horizontal_offset) of the 8-connected neighbours
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub i: usize, // Row
+    pub j: usize, // Column
+}
+
+impl Point {
+    pub fn new(i: usize, j: usize) -> Point {
+        Point { i, j }
+    }
+
+    // None if the result would be negative, the upper bound is checked by the grid
+    pub fn offset(self, (di, dj): (isize, isize)) -> Option<Point> {
+        Some(Point {
+            i: self.i.checked_add_signed(di)?,
+            j: self.j.checked_add_signed(dj)?,
+        })
+    }
+
+    pub fn step(self, dir: Dir) -> Option<Point> {
+        self.offset(dir.offset())
+    }
+
+    // Signed (di, dj) to get from self to other
+    pub fn delta(self, other: Point) -> (isize, isize) {
+        (
+            other.i as isize - self.i as isize,
+            other.j as isize - self.j as isize,
+        )
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.i.abs_diff(other.i) + self.j.abs_diff(other.j)
+    }
+}
+
+// Clockwise order, so `dir as usize` can index per-direction tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir {
+    UP,
+    RIGHT,
+    DOWN,
+    LEFT,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::UP, Dir::DOWN, Dir::LEFT, Dir::RIGHT];
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Dir::UP => (-1, 0),
+            Dir::RIGHT => (0, 1),
+            Dir::DOWN => (1, 0),
+            Dir::LEFT => (0, -1),
+        }
+    }
+
+    pub fn turn_right(self) -> Dir {
+        match self {
+            Dir::UP => Dir::RIGHT,
+            Dir::RIGHT => Dir::DOWN,
+            Dir::DOWN => Dir::LEFT,
+            Dir::LEFT => Dir::UP,
+        }
+    }
+
+    pub fn turn_left(self) -> Dir {
+        self.reverse().turn_right()
+    }
+
+    pub fn reverse(self) -> Dir {
+        self.turn_right().turn_right()
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        self == Dir::LEFT || self == Dir::RIGHT
+    }
+}
+
+// Row-major map stored in a single Vec
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            height,
+            width,
+            cells: vec![fill; height * width],
+        }
+    }
+
+    // Panics if the rows are not all the same width
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "rows of a grid must have the same width"
+        );
+
+        Grid {
+            height,
+            width,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.i < self.height && p.j < self.width
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.cells[p.i * self.width + p.j])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.i * self.width + p.j])
+        } else {
+            None
+        }
+    }
+
+    // Checked moves, None when leaving the grid
+    pub fn offset(&self, p: Point, delta: (isize, isize)) -> Option<Point> {
+        p.offset(delta).filter(|&q| self.contains(q))
+    }
+
+    pub fn step(&self, p: Point, dir: Dir) -> Option<Point> {
+        self.offset(p, dir.offset())
+    }
+
+    // 4-connected neighbours that are inside the grid
+    pub fn neighbours(&self, p: Point) -> impl Iterator<Item = (Dir, Point)> + '_ {
+        Dir::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(p, dir).map(|q| (dir, q)))
+    }
+
+    // 8-connected neighbours that are inside the grid
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .filter_map(move |delta| self.offset(p, delta))
+    }
+
+    // All points in row-major order
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |i| (0..width).map(move |j| Point { i, j }))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn position(&self, pred: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, c)| pred(c)).map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn swap(&mut self, a: Point, b: Point) {
+        let (a, b) = (a.i * self.width + a.j, b.i * self.width + b.j);
+        self.cells.swap(a, b);
+    }
+
+    // One line per row, without a trailing newline
+    pub fn render(&self, to_char: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&to_char).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn find(&self, value: T) -> Option<Point> {
+        self.position(|c| *c == value)
+    }
+
+    pub fn find_all(&self, value: T) -> impl Iterator<Item = Point> + '_ {
+        self.iter()
+            .filter(move |(_, c)| **c == value)
+            .map(|(p, _)| p)
+    }
+}
+
+impl Grid<u8> {
+    // Rectangular text map, each byte must satisfy `allowed`
+    pub fn parse(
+        input: &str,
+        allowed: impl Fn(u8) -> bool,
+        expected: &str,
+    ) -> Result<Grid<u8>, ParseError> {
+        Ok(Grid::from_rows(parser::grid(input, allowed, expected)?))
+    }
+
+    pub fn from_lines<'a>(
+        lines: impl IntoIterator<Item = Line<'a>>,
+        allowed: impl Fn(u8) -> bool,
+        expected: &str,
+    ) -> Result<Grid<u8>, ParseError> {
+        Ok(Grid::from_rows(parser::grid_from_lines(
+            lines, allowed, expected,
+        )?))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        assert!(self.contains(p), "{:?} is outside the grid", p);
+        &self.cells[p.i * self.width + p.j]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        assert!(self.contains(p), "{:?} is outside the grid", p);
+        &mut self.cells[p.i * self.width + p.j]
+    }
+}
+
+impl fmt::Display for Grid<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(|&c| c as char))
+    }
+}
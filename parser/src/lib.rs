@@ -0,0 +1,201 @@
+mod source;
+
+pub use source::{input_path, read_input_or_exit, source_from_args, DayArgs, Source, YEAR};
+
+use std::fmt;
+use std::process;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,   // 1-based
+    pub column: usize, // 1-based, in characters
+    pub text: String,  // Offending text, empty at the end of a line or input
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, expected: &str) -> ParseError {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    // Rustc-like diagnostic with a caret under the offending text
+    pub fn render(&self, source_name: &str, input: &str) -> String {
+        let source_line = input.lines().nth(self.line - 1).unwrap_or("");
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let width = self.text.chars().count().max(1);
+
+        format!(
+            "error: {}\n{} --> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            gutter,
+            source_name,
+            self.line,
+            self.column,
+            gutter,
+            number,
+            source_line,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.text.is_empty() {
+            write!(f, "expected {}, found nothing", self.expected)
+        } else {
+            write!(f, "expected {}, found {:?}", self.expected, self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize, // 1-based
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    // `at` must be a slice of this line, the column is derived from its position
+    pub fn error(&self, at: &str, expected: &str) -> ParseError {
+        let offset = (at.as_ptr() as usize)
+            .saturating_sub(self.text.as_ptr() as usize)
+            .min(self.text.len());
+        let column = self.text[..offset].chars().count() + 1;
+
+        ParseError::new(self.number, column, at, expected)
+    }
+
+    // Error for a line that should have followed this one
+    pub fn missing_next(&self, expected: &str) -> ParseError {
+        ParseError::new(self.number + 1, 1, "", expected)
+    }
+
+    // Error pointing just past the end of the line
+    pub fn error_at_end(&self, expected: &str) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, field: &str, expected: &str) -> Result<T, ParseError> {
+        field.parse::<T>().map_err(|_| self.error(field, expected))
+    }
+
+    pub fn split_once(&self, sep: &str, expected: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(sep)
+            .ok_or_else(|| self.error(self.text, expected))
+    }
+
+    pub fn parse_list<T: FromStr>(
+        &self,
+        field: &str,
+        sep: &str,
+        expected: &str,
+    ) -> Result<Vec<T>, ParseError> {
+        field
+            .split(sep)
+            .map(|item| self.parse::<T>(item, expected))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+// Lines with their numbers, trailing blank lines are ignored
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .trim_end_matches(['\n', '\r'])
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Line {
+            number: i + 1,
+            text,
+        })
+}
+
+// Groups of lines separated by blank lines
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections: Vec<Vec<Line>> = vec![Vec::new()];
+
+    for line in lines(input) {
+        if line.is_empty() {
+            sections.push(Vec::new());
+        } else {
+            sections.last_mut().unwrap().push(line);
+        }
+    }
+
+    sections.retain(|section| !section.is_empty());
+    sections
+}
+
+// Error for input that ends before `expected` was found
+pub fn end_of_input(input: &str, expected: &str) -> ParseError {
+    ParseError::new(lines(input).count() + 1, 1, "", expected)
+}
+
+// Rectangular map of bytes, each byte must satisfy `allowed`
+pub fn grid(
+    input: &str,
+    allowed: impl Fn(u8) -> bool,
+    expected: &str,
+) -> Result<Vec<Vec<u8>>, ParseError> {
+    grid_from_lines(lines(input), allowed, expected)
+}
+
+pub fn grid_from_lines<'a>(
+    lines: impl IntoIterator<Item = Line<'a>>,
+    allowed: impl Fn(u8) -> bool,
+    expected: &str,
+) -> Result<Vec<Vec<u8>>, ParseError> {
+    let mut map: Vec<Vec<u8>> = Vec::new();
+
+    for line in lines {
+        if let Some(j) = line.text.bytes().position(|c| !allowed(c)) {
+            let end = line.text[j..]
+                .chars()
+                .next()
+                .map_or(j, |c| j + c.len_utf8());
+            return Err(line.error(&line.text[j..end], expected));
+        }
+
+        if let Some(first) = map.first() {
+            if line.text.len() != first.len() {
+                let at = &line.text[first.len().min(line.text.len())..];
+                return Err(line.error(
+                    at,
+                    &format!("a row of width {} like the first row", first.len()),
+                ));
+            }
+        }
+
+        map.push(line.text.as_bytes().to_vec());
+    }
+
+    if map.is_empty() {
+        return Err(ParseError::new(1, 1, "", "a map"));
+    }
+
+    Ok(map)
+}
+
+// For the day binaries, print the diagnostic and exit instead of panicking
+pub fn unwrap_or_exit<T>(result: Result<T, ParseError>, source_name: &str, input: &str) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e.render(source_name, input));
+        process::exit(1);
+    })
+}
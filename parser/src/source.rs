@@ -0,0 +1,204 @@
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+// Event the puzzle inputs are downloaded from
+pub const YEAR: u32 = 2024;
+
+// Inputs are personal and not committed, `aoc fetch` caches them in
+// inputs/<year>/<day>.txt at the workspace root
+pub fn input_path(workspace: &Path, day: u32) -> PathBuf {
+    workspace
+        .join("inputs")
+        .join(YEAR.to_string())
+        .join(format!("{}.txt", day))
+}
+
+// Day crates live in dayN directories at the workspace root
+fn cached_input(day_dir: &Path) -> PathBuf {
+    let day = day_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("day"))
+        .and_then(|n| n.parse::<u32>().ok())
+        .unwrap_or(0);
+
+    input_path(day_dir.parent().unwrap_or(day_dir), day)
+}
+
+// Where a day reads its puzzle input from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,        // The cached input, see input_path
+    Stdin,          // Given as "-"
+    Path(PathBuf),  // Relative to the current directory
+    Example(usize), // test.txt for the first example, testN.txt for the others
+}
+
+impl Source {
+    // "-" is stdin, anything else is a path
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::Path(PathBuf::from(arg))
+        }
+    }
+
+    pub fn example(n: &str) -> Result<Source, String> {
+        match n.parse::<usize>() {
+            Ok(n) if n >= 1 => Ok(Source::Example(n)),
+            _ => Err(format!("Invalid example '{}', expected 1 or more", n)),
+        }
+    }
+
+    pub fn path(&self, day_dir: &Path) -> Option<PathBuf> {
+        match self {
+            Source::Default => Some(cached_input(day_dir)),
+            Source::Stdin => None,
+            Source::Path(path) => Some(path.clone()),
+            Source::Example(1) => Some(day_dir.join("test.txt")),
+            Source::Example(n) => Some(day_dir.join(format!("test{}.txt", n))),
+        }
+    }
+
+    // Shown in diagnostics in place of a file name
+    pub fn name(&self, day_dir: &Path) -> String {
+        self.path(day_dir)
+            .map_or("<stdin>".to_string(), |path| path.display().to_string())
+    }
+
+    pub fn read(&self, day_dir: &Path) -> Result<String, String> {
+        match self.path(day_dir) {
+            Some(path) => fs::read_to_string(&path).map_err(|e| {
+                let hint = match self {
+                    Source::Default if e.kind() == io::ErrorKind::NotFound => {
+                        ", download it with `aoc fetch`"
+                    }
+                    _ => "",
+                };
+                format!("Unable to read {}: {}{}", path.display(), e, hint)
+            }),
+            None => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("Unable to read stdin: {}", e))?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+// Arguments of the day binaries: nothing, a path, "-" for stdin or --example N
+pub fn source_from_args(mut args: impl Iterator<Item = String>) -> Result<Source, String> {
+    let source = match args.next().as_deref() {
+        None => Source::Default,
+        Some("--example") => Source::example(&args.next().ok_or("--example expects a number")?)?,
+        Some(arg) => Source::from_arg(arg),
+    };
+
+    match args.next() {
+        Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+        None => Ok(source),
+    }
+}
+
+// For the day binaries, returns the source name and its contents or exits
+pub fn read_input_or_exit(day_dir: &str) -> (String, String) {
+    DayArgs::from_env("").read_input_or_exit(day_dir)
+}
+
+// Arguments of a day binary with options of its own. The options are taken
+// out wherever they are, what is left chooses the input
+pub struct DayArgs {
+    program: String,
+    args: Vec<String>,
+    options: String, // Usage of the options, like "[--report] "
+}
+
+impl DayArgs {
+    pub fn from_env(options: &str) -> DayArgs {
+        let mut args = env::args();
+        DayArgs {
+            program: args.next().unwrap_or_default(),
+            args: args.collect(),
+            options: options.to_string(),
+        }
+    }
+
+    // Whether the switch was given
+    pub fn flag(&mut self, name: &str) -> bool {
+        let given = self.args.iter().any(|arg| arg == name);
+        self.args.retain(|arg| arg != name);
+        given
+    }
+
+    // The value after the option, exits when it has none
+    pub fn value(&mut self, name: &str) -> Option<String> {
+        let at = self.args.iter().position(|arg| arg == name)?;
+        if at + 1 == self.args.len() {
+            self.exit(&format!("{} expects a value", name));
+        }
+        let value = self.args.remove(at + 1);
+        self.args.remove(at);
+        Some(value)
+    }
+
+    // The values after each time the option is given, in order
+    pub fn values(&mut self, name: &str) -> Vec<String> {
+        std::iter::from_fn(|| self.value(name)).collect()
+    }
+
+    // Prints the message with the usage and exits
+    pub fn exit(&self, message: &str) -> ! {
+        eprintln!("{}", message);
+        eprintln!(
+            "Usage: {} {}[PATH | - | --example N]",
+            self.program, self.options
+        );
+        process::exit(1);
+    }
+
+    // The source name and its contents, exits when the arguments left do
+    // not name a source or it cannot be read
+    pub fn read_input_or_exit(self, day_dir: &str) -> (String, String) {
+        let day_dir = Path::new(day_dir);
+
+        let source = source_from_args(self.args.iter().cloned()).unwrap_or_else(|e| self.exit(&e));
+
+        let input = source.read(day_dir).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(1);
+        });
+
+        (source.name(day_dir), input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_are_taken_from_anywhere() {
+        let mut args = DayArgs {
+            program: "day1".to_string(),
+            args: [
+                "--param", "a=1", "in.txt", "--pairs", "--metric", "l2", "--param", "b=2",
+            ]
+            .map(String::from)
+            .to_vec(),
+            options: String::new(),
+        };
+        assert_eq!(args.values("--param"), ["a=1", "b=2"]);
+        assert_eq!(args.value("--metric").as_deref(), Some("l2"));
+        assert!(args.flag("--pairs"));
+        assert!(!args.flag("--pairs"));
+        assert_eq!(args.value("--unmatched"), None);
+        assert_eq!(
+            source_from_args(args.args.into_iter()),
+            Ok(Source::Path(PathBuf::from("in.txt")))
+        );
+    }
+}
@@ -0,0 +1,270 @@
+// Facts the solvers report about how they reached an answer, for --explain.
+// Solvers are generic over the sink and only build facts when it is enabled, so
+// with NoTrace the reporting compiles away
+use std::fmt::{self, Display, Write as _};
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Number(String), // Decimal digits, of any size
+    Text(String),
+    List(Vec<Value>),
+}
+
+impl Value {
+    // For number types without a From impl, such as big integers
+    pub fn number(n: impl Display) -> Value {
+        Value::Number(n.to_string())
+    }
+}
+
+macro_rules! numbers {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(n: $t) -> Value {
+                Value::Number(n.to_string())
+            }
+        })*
+    };
+}
+
+numbers!(u8, u32, u64, usize, i32, i64);
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Value {
+        Value::Text(text.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Value {
+        Value::Text(text)
+    }
+}
+
+impl<T: Into<Value> + Clone> From<&[T]> for Value {
+    fn from(items: &[T]) -> Value {
+        Value::List(items.iter().cloned().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Value {
+        Value::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+// Lists are comma separated, as in the puzzle inputs
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Text(text) => write!(f, "{}", text),
+            Value::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Value {
+    fn json(&self, out: &mut String) {
+        match self {
+            Value::Number(n) => out.push_str(n),
+            Value::Text(text) => json_string(text, out),
+            Value::List(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.json(out);
+                }
+                out.push(']');
+            }
+        }
+    }
+}
+
+fn json_string(text: &str, out: &mut String) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+// Something a solver found out, e.g. "rejected" with the update and the rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fact {
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+impl Fact {
+    pub fn new(name: &'static str) -> Fact {
+        Fact {
+            name,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, key: &'static str, value: impl Into<Value>) -> Fact {
+        self.fields.push((key, value.into()));
+        self
+    }
+}
+
+pub trait Trace {
+    // Solvers check this before building a fact
+    fn enabled(&self) -> bool;
+    fn fact(&mut self, fact: Fact);
+}
+
+// The sink of solvers that are not explaining
+pub struct NoTrace;
+
+impl Trace for NoTrace {
+    #[inline(always)]
+    fn enabled(&self) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn fact(&mut self, _: Fact) {}
+}
+
+// Keeps the facts, for tests and callers that render them themselves
+impl Trace for Vec<Fact> {
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    fn fact(&mut self, fact: Fact) {
+        self.push(fact);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Text, // Day 5 part 1: rejected update=75,97,47,61,53 rule=97|75
+    Json, // One object per line, with "day", "part" and "fact" first
+}
+
+// Writes each fact on its own line as it is reported
+pub struct Writer<W: Write> {
+    out: W,
+    style: Style,
+    puzzle: (u32, u8),
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(out: W, style: Style) -> Writer<W> {
+        Writer {
+            out,
+            style,
+            puzzle: (0, 0),
+            error: None,
+        }
+    }
+
+    // The day and part the next facts are about
+    pub fn begin(&mut self, day: u32, part: u8) {
+        self.puzzle = (day, part);
+    }
+
+    // Returns the output, or the first error writing it
+    pub fn finish(mut self) -> io::Result<W> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => self.out.flush().map(|_| self.out),
+        }
+    }
+
+    fn line(&self, fact: &Fact) -> String {
+        let (day, part) = self.puzzle;
+        let mut line = String::new();
+
+        match self.style {
+            Style::Text => {
+                write!(line, "Day {} part {}: {}", day, part, fact.name).unwrap();
+                for (key, value) in &fact.fields {
+                    write!(line, " {}={}", key, value).unwrap();
+                }
+            }
+            Style::Json => {
+                write!(line, "{{\"day\":{},\"part\":{},\"fact\":", day, part).unwrap();
+                json_string(fact.name, &mut line);
+                for (key, value) in &fact.fields {
+                    line.push(',');
+                    json_string(key, &mut line);
+                    line.push(':');
+                    value.json(&mut line);
+                }
+                line.push('}');
+            }
+        }
+
+        line.push('\n');
+        line
+    }
+}
+
+impl<W: Write> Trace for Writer<W> {
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    fn fact(&mut self, fact: Fact) {
+        if self.error.is_none() {
+            let line = self.line(&fact);
+            self.error = self.out.write_all(line.as_bytes()).err();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fact() -> Fact {
+        Fact::new("rejected")
+            .with("update", vec![75, 97, 47])
+            .with("rule", "97|75")
+            .with("note", "says \"no\"")
+    }
+
+    #[test]
+    fn text_and_json_lines() {
+        let mut text = Writer::new(Vec::new(), Style::Text);
+        text.begin(5, 1);
+        text.fact(fact());
+        assert_eq!(
+            String::from_utf8(text.finish().unwrap()).unwrap(),
+            "Day 5 part 1: rejected update=75,97,47 rule=97|75 note=says \"no\"\n"
+        );
+
+        let mut json = Writer::new(Vec::new(), Style::Json);
+        json.begin(5, 1);
+        json.fact(fact());
+        json.fact(Fact::new("clique").with("size", Value::number(10u128.pow(30))));
+        assert_eq!(
+            String::from_utf8(json.finish().unwrap()).unwrap(),
+            "{\"day\":5,\"part\":1,\"fact\":\"rejected\",\"update\":[75,97,47],\"rule\":\"97|75\",\"note\":\"says \\\"no\\\"\"}\n\
+             {\"day\":5,\"part\":1,\"fact\":\"clique\",\"size\":1000000000000000000000000000000}\n"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{Frame, Sink};
+use serde_json::json;
+use std::io::{self, Write};
+
+// Headless sink that records an asciinema v2 cast, one frame every `delay` seconds
+pub struct Cast<W: Write> {
+    out: W,
+    delay: f64,
+    frames: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Cast<W> {
+    pub fn new(out: W, delay: f64) -> Cast<W> {
+        Cast {
+            out,
+            delay,
+            frames: 0,
+            error: None,
+        }
+    }
+
+    fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        let mut data = frame.ansi();
+
+        // The header fixes the size of the recording, so it comes from the first frame
+        if self.frames == 0 {
+            let header = json!({
+                "version": 2,
+                "width": frame.width(),
+                "height": frame.height(),
+            });
+            writeln!(self.out, "{}", header)?;
+            data.insert_str(0, "\x1b[2J");
+        }
+
+        let time = self.frames as f64 * self.delay;
+        writeln!(self.out, "{}", json!([time, "o", data]))?;
+        self.frames += 1;
+
+        Ok(())
+    }
+
+    // Returns the writer, or the first error hit while recording
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+impl<W: Write> Sink for Cast<W> {
+    fn show(&mut self, frame: &Frame) -> bool {
+        match self.write(frame) {
+            Ok(()) => true,
+            Err(e) => {
+                self.error = Some(e);
+                false
+            }
+        }
+    }
+}
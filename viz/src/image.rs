@@ -0,0 +1,386 @@
+use crate::png;
+use grid::{Dir, Grid, Point};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    // "rrggbb", optionally with a leading '#'
+    pub fn from_hex(hex: &str) -> Option<Rgb> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if hex.len() != 6 || !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+        Some(Rgb(channel(0), channel(2), channel(4)))
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+// Colours of the keys that layers put in cells
+#[derive(Debug, Clone, Default)]
+pub struct Palette {
+    colours: HashMap<u8, Rgb>,
+}
+
+impl Palette {
+    pub fn new(colours: &[(u8, Rgb)]) -> Palette {
+        Palette {
+            colours: colours.iter().copied().collect(),
+        }
+    }
+
+    pub fn set(&mut self, key: u8, colour: Rgb) {
+        self.colours.insert(key, colour);
+    }
+
+    // Keys without a colour, such as the plants of day 12, get a stable one
+    // spread around the colour wheel
+    pub fn colour(&self, key: u8) -> Rgb {
+        if let Some(&colour) = self.colours.get(&key) {
+            return colour;
+        }
+
+        // Golden ratio steps keep neighbouring keys apart
+        let hue = (key as f64 * 0.618_033_988_75).fract() * 6.0;
+        let (value, saturation) = (230.0, 0.55);
+        let channel = |offset: f64| {
+            let k = (hue + offset) % 6.0;
+            let weight = (k.min(4.0 - k).clamp(0.0, 1.0)) * saturation;
+            (value * (1.0 - weight)) as u8
+        };
+        Rgb(channel(5.0), channel(3.0), channel(1.0))
+    }
+
+    // Overrides like "#=404040,O=ff0000", each key is a single character
+    pub fn parse(spec: &str) -> Result<Palette, String> {
+        let mut palette = Palette::default();
+
+        for entry in spec.split(',') {
+            let invalid = || format!("Invalid palette entry '{}', expected KEY=rrggbb", entry);
+            let (key, hex) = entry.split_once('=').ok_or_else(invalid)?;
+            let &[key] = key.as_bytes() else {
+                return Err(invalid());
+            };
+            palette.set(key, Rgb::from_hex(hex).ok_or_else(invalid)?);
+        }
+
+        Ok(palette)
+    }
+
+    // Colours of `overrides` win over the ones set here
+    pub fn extend(&mut self, overrides: &Palette) {
+        self.colours.extend(&overrides.colours);
+    }
+}
+
+// A line along one side of a cell, drawn inside the cell above every layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub cell: Point,
+    pub side: Dir,
+    pub key: u8,
+}
+
+// What a day shows of its state, a picture stacks layers from the bottom up
+pub trait Layer {
+    // Palette key of a cell, None leaves the layers below visible
+    fn cell(&self, p: Point) -> Option<u8>;
+
+    // Borders such as fences
+    fn edges(&self) -> Vec<Edge> {
+        Vec::new()
+    }
+}
+
+impl<L: Layer + ?Sized> Layer for &L {
+    fn cell(&self, p: Point) -> Option<u8> {
+        (**self).cell(p)
+    }
+
+    fn edges(&self) -> Vec<Edge> {
+        (**self).edges()
+    }
+}
+
+// Maps are drawn as they are parsed, one key per byte
+impl Layer for Grid<u8> {
+    fn cell(&self, p: Point) -> Option<u8> {
+        self.get(p).copied()
+    }
+}
+
+// Marks such as a path, the other cells are left alone
+impl Layer for Grid<Option<u8>> {
+    fn cell(&self, p: Point) -> Option<u8> {
+        self.get(p).copied().flatten()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    // From the file extension
+    pub fn from_path(path: &Path) -> Option<ImageFormat> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+pub struct Picture<'a> {
+    height: usize,
+    width: usize,
+    layers: Vec<Box<dyn Layer + 'a>>,
+    pub palette: Palette,
+}
+
+impl<'a> Picture<'a> {
+    pub fn new(height: usize, width: usize, palette: Palette) -> Picture<'a> {
+        Picture {
+            height,
+            width,
+            layers: Vec::new(),
+            palette,
+        }
+    }
+
+    // Goes on top of the layers added before
+    pub fn add(&mut self, layer: impl Layer + 'a) {
+        self.layers.push(Box::new(layer));
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    // Colour of the topmost layer covering each cell, white where none does
+    fn colours(&self) -> Grid<Rgb> {
+        let mut colours = Grid::new(self.height, self.width, Rgb::WHITE);
+        for p in colours.points() {
+            if let Some(key) = self.layers.iter().rev().find_map(|layer| layer.cell(p)) {
+                colours[p] = self.palette.colour(key);
+            }
+        }
+        colours
+    }
+
+    // Edges as (x, y, width, height, colour) rectangles in pixels
+    fn edge_rects(&self, scale: usize) -> Vec<(usize, usize, usize, usize, Rgb)> {
+        let thickness = (scale / 5).max(1);
+
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.edges())
+            .filter(|edge| edge.cell.i < self.height && edge.cell.j < self.width)
+            .map(|edge| {
+                let (x, y) = (edge.cell.j * scale, edge.cell.i * scale);
+                let (x, y, w, h) = match edge.side {
+                    Dir::UP => (x, y, scale, thickness),
+                    Dir::DOWN => (x, y + scale - thickness, scale, thickness),
+                    Dir::LEFT => (x, y, thickness, scale),
+                    Dir::RIGHT => (x + scale - thickness, y, thickness, scale),
+                };
+                (x, y, w, h, self.palette.colour(edge.key))
+            })
+            .collect()
+    }
+
+    // Three bytes per pixel, rows top to bottom, each cell `scale` pixels wide
+    fn pixels(&self, scale: usize) -> Vec<u8> {
+        let width = self.width * scale;
+        let mut pixels = vec![0; width * self.height * scale * 3];
+
+        let mut fill = |x: usize, y: usize, w: usize, h: usize, colour: Rgb| {
+            for row in y..y + h {
+                for column in x..x + w {
+                    let at = (row * width + column) * 3;
+                    pixels[at..at + 3].copy_from_slice(&[colour.0, colour.1, colour.2]);
+                }
+            }
+        };
+
+        let colours = self.colours();
+        for p in colours.points() {
+            fill(p.j * scale, p.i * scale, scale, scale, colours[p]);
+        }
+        for (x, y, w, h, colour) in self.edge_rects(scale) {
+            fill(x, y, w, h, colour);
+        }
+
+        pixels
+    }
+
+    pub fn ppm(&self, scale: usize) -> Vec<u8> {
+        let mut out =
+            format!("P6\n{} {}\n255\n", self.width * scale, self.height * scale).into_bytes();
+        out.extend(self.pixels(scale));
+        out
+    }
+
+    pub fn png(&self, scale: usize) -> Vec<u8> {
+        png::encode(self.width * scale, self.height * scale, &self.pixels(scale))
+    }
+
+    // Runs of same coloured cells in a row become one rectangle
+    pub fn svg(&self, scale: usize) -> String {
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n"
+        );
+
+        let mut rect = |x: usize, y: usize, w: usize, h: usize, colour: Rgb| {
+            writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                x,
+                y,
+                w,
+                h,
+                colour.hex()
+            )
+            .unwrap();
+        };
+
+        let colours = self.colours();
+        for (i, row) in colours.rows().enumerate() {
+            let mut j = 0;
+            while j < row.len() {
+                let run = row[j..].iter().take_while(|&&c| c == row[j]).count();
+                rect(j * scale, i * scale, run * scale, scale, row[j]);
+                j += run;
+            }
+        }
+        for (x, y, w, h, colour) in self.edge_rects(scale) {
+            rect(x, y, w, h, colour);
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+
+    pub fn encode(&self, format: ImageFormat, scale: usize) -> Vec<u8> {
+        match format {
+            ImageFormat::Ppm => self.ppm(scale),
+            ImageFormat::Png => self.png(scale),
+            ImageFormat::Svg => self.svg(scale).into_bytes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb = Rgb(255, 0, 0);
+    const BLACK: Rgb = Rgb(0, 0, 0);
+
+    // A map with a mark on its top right cell and a line below the bottom left one
+    fn picture() -> Picture<'static> {
+        struct Underline;
+        impl Layer for Underline {
+            fn cell(&self, _: Point) -> Option<u8> {
+                None
+            }
+            fn edges(&self) -> Vec<Edge> {
+                vec![Edge {
+                    cell: Point::new(1, 0),
+                    side: Dir::DOWN,
+                    key: b'-',
+                }]
+            }
+        }
+
+        let mut marks = Grid::new(2, 2, None);
+        marks[Point::new(0, 1)] = Some(b'O');
+
+        let palette = Palette::new(&[(b'#', BLACK), (b'.', Rgb::WHITE), (b'O', RED)]);
+        let mut picture = Picture::new(2, 2, palette);
+        picture.add(Grid::from_rows(vec![b"#.".to_vec(), b"..".to_vec()]));
+        picture.add(marks);
+        picture.add(Underline);
+        picture
+    }
+
+    #[test]
+    fn palette_overrides_and_generated_colours() {
+        let mut palette = Palette::new(&[(b'A', BLACK), (b'B', BLACK)]);
+        assert_eq!(palette.colour(b'C'), palette.colour(b'C'));
+        assert_ne!(palette.colour(b'C'), palette.colour(b'D'));
+
+        palette.extend(&Palette::parse("A=ff0000,#=#000000").unwrap());
+        assert_eq!(palette.colour(b'A'), RED);
+        assert_eq!(palette.colour(b'B'), BLACK);
+        assert_eq!(palette.colour(b'#'), BLACK);
+        assert!(Palette::parse("A=red").is_err());
+        assert!(Palette::parse("AB=ff0000").is_err());
+    }
+
+    #[test]
+    fn ppm_scales_cells_and_draws_edges_on_top() {
+        let ppm = picture().ppm(5);
+        let header = b"P6\n10 10\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+
+        let pixel = |x: usize, y: usize| {
+            let at = header.len() + (y * 10 + x) * 3;
+            Rgb(ppm[at], ppm[at + 1], ppm[at + 2])
+        };
+        assert_eq!(pixel(0, 0), BLACK);
+        assert_eq!(pixel(9, 0), RED);
+        assert_eq!(pixel(9, 9), Rgb::WHITE);
+        // The line is a fifth of a cell thick
+        assert_eq!(pixel(2, 8), Rgb::WHITE);
+        assert_eq!(pixel(2, 9), Palette::default().colour(b'-'));
+    }
+
+    #[test]
+    fn svg_merges_runs_of_one_colour() {
+        let svg = picture().svg(10);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\""));
+        assert!(
+            svg.contains("<rect x=\"0\" y=\"10\" width=\"20\" height=\"10\" fill=\"#ffffff\"/>")
+        );
+        assert!(
+            svg.contains("<rect x=\"10\" y=\"0\" width=\"10\" height=\"10\" fill=\"#ff0000\"/>")
+        );
+        assert!(svg.contains("<rect x=\"0\" y=\"18\" width=\"10\" height=\"2\""));
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(
+            ImageFormat::from_path(Path::new("out/garden.PNG")),
+            Some(ImageFormat::Png)
+        );
+        assert_eq!(
+            ImageFormat::from_path(Path::new("a.svg")),
+            Some(ImageFormat::Svg)
+        );
+        assert_eq!(ImageFormat::from_path(Path::new("a.jpg")), None);
+    }
+}
@@ -0,0 +1,161 @@
+mod cast;
+mod image;
+mod png;
+mod terminal;
+
+pub use cast::Cast;
+pub use image::{Edge, ImageFormat, Layer, Palette, Picture, Rgb};
+pub use terminal::Terminal;
+
+use grid::{Dir, Grid, Point};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Plain,
+    Grey,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    // ANSI select graphic rendition sequence
+    fn sgr(self) -> &'static str {
+        match self {
+            Colour::Plain => "\x1b[0m",
+            Colour::Grey => "\x1b[90m",
+            Colour::Red => "\x1b[31m",
+            Colour::Green => "\x1b[32m",
+            Colour::Yellow => "\x1b[33m",
+            Colour::Blue => "\x1b[34m",
+            Colour::Magenta => "\x1b[35m",
+            Colour::Cyan => "\x1b[36m",
+        }
+    }
+}
+
+// How the days draw something facing a direction
+pub fn arrow(dir: Dir) -> char {
+    match dir {
+        Dir::UP => '^',
+        Dir::RIGHT => '>',
+        Dir::DOWN => 'v',
+        Dir::LEFT => '<',
+    }
+}
+
+// One picture of a simulation: a title line above a coloured map
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub title: String,
+    cells: Grid<(char, Colour)>,
+}
+
+impl Frame {
+    pub fn new<T>(title: String, grid: &Grid<T>, style: impl Fn(&T) -> (char, Colour)) -> Frame {
+        Frame {
+            title,
+            cells: grid.map(style),
+        }
+    }
+
+    pub fn set(&mut self, p: Point, glyph: char, colour: Colour) {
+        self.cells[p] = (glyph, colour);
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width().max(self.title.chars().count())
+    }
+
+    // Lines including the title
+    pub fn height(&self) -> usize {
+        self.cells.height() + 1
+    }
+
+    // Without colours, one line per row
+    pub fn text(&self) -> String {
+        format!("{}\n{}", self.title, self.cells.render(|&(c, _)| c))
+    }
+
+    // Draws over the previous frame from the top left corner. Lines end with
+    // "\r\n" as the terminal is in raw mode
+    pub fn ansi(&self) -> String {
+        let mut out = format!("\x1b[H\x1b[0m{}\x1b[K", self.title);
+
+        for row in self.cells.rows() {
+            out.push_str("\r\n");
+
+            let mut current = Colour::Plain;
+            for &(glyph, colour) in row {
+                if colour != current {
+                    out.push_str(colour.sgr());
+                    current = colour;
+                }
+                out.push(glyph);
+            }
+            write!(out, "{}\x1b[K", Colour::Plain.sgr()).unwrap();
+        }
+
+        out
+    }
+}
+
+// Where the frames of an animation go
+pub trait Sink {
+    // Returns false once the viewer wants to stop, the simulation should then stop too
+    fn show(&mut self, frame: &Frame) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        let grid = Grid::from_rows(vec![b"#.".to_vec(), b".#".to_vec()]);
+        let mut frame = Frame::new("Step 1".to_string(), &grid, |&c| match c {
+            b'#' => ('#', Colour::Grey),
+            _ => ('.', Colour::Plain),
+        });
+        frame.set(Point::new(0, 1), '@', Colour::Red);
+        frame
+    }
+
+    #[test]
+    fn text_has_title_and_rows() {
+        assert_eq!(frame().text(), "Step 1\n#@\n.#");
+        assert_eq!((frame().width(), frame().height()), (6, 3));
+    }
+
+    #[test]
+    fn ansi_only_switches_colour_when_it_changes() {
+        assert_eq!(
+            frame().ansi(),
+            "\x1b[H\x1b[0mStep 1\x1b[K\r\n\x1b[90m#\x1b[31m@\x1b[0m\x1b[K\r\n.\x1b[90m#\x1b[0m\x1b[K"
+        );
+    }
+
+    #[test]
+    fn cast_has_header_and_one_event_per_frame() {
+        let mut cast = Cast::new(Vec::new(), 0.5);
+        assert!(cast.show(&frame()));
+        assert!(cast.show(&frame()));
+        let out = String::from_utf8(cast.finish().unwrap()).unwrap();
+
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 6);
+        assert_eq!(lines[0]["height"], 3);
+        assert_eq!(lines[1][0], 0.0);
+        assert_eq!(lines[2][0], 0.5);
+        assert_eq!(lines[2][1], "o");
+        assert!(lines[2][2].as_str().unwrap().ends_with(&frame().ansi()));
+    }
+}
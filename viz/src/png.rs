@@ -0,0 +1,122 @@
+// Minimal PNG encoder: 8-bit RGB, no filtering and deflate "stored" blocks. The
+// files are bigger than they need to be but any viewer reads them, run them
+// through a PNG optimiser to shrink them
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// Largest payload of a stored deflate block
+const MAX_STORED: usize = 65535;
+
+fn crc32(data: &[&[u8]]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 == 1 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+        }
+        *entry = c;
+    }
+
+    let mut crc = u32::MAX;
+    for &byte in data.iter().flat_map(|d| d.iter()) {
+        crc = table[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc ^ u32::MAX
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    out.extend(kind);
+    out.extend(data);
+    out.extend(crc32(&[kind, data]).to_be_bytes());
+}
+
+// Zlib stream made of stored blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+
+    let blocks = data.chunks(MAX_STORED).collect::<Vec<_>>();
+    if blocks.is_empty() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    for (n, block) in blocks.iter().enumerate() {
+        let last = n + 1 == blocks.len();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(*block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+// `rgb` holds the rows top to bottom, three bytes per pixel
+pub fn encode(width: usize, height: usize, rgb: &[u8]) -> Vec<u8> {
+    assert_eq!(rgb.len(), width * height * 3);
+
+    let mut header = Vec::new();
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // Bit depth 8, colour type RGB, default compression and filters, no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+
+    // Every row starts with its filter type, 0 is none
+    let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+    for row in rgb.chunks(width * 3) {
+        raw.push(0);
+        raw.extend(row);
+    }
+
+    let mut out = SIGNATURE.to_vec();
+    chunk(&mut out, b"IHDR", &header);
+    chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_match_known_values() {
+        assert_eq!(crc32(&[b"IEND"]), 0xae426082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn decodes_with_the_png_crate() {
+        // Wide enough to need several stored blocks
+        let (width, height) = (300, 100);
+        let rgb: Vec<u8> = (0..width * height * 3).map(|n| (n % 251) as u8).collect();
+        let encoded = encode(width, height, &rgb);
+
+        let decoder = ::png::Decoder::new(encoded.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+
+        assert_eq!((info.width, info.height), (width as u32, height as u32));
+        assert_eq!(info.color_type, ::png::ColorType::Rgb);
+        assert_eq!(&decoded[..info.buffer_size()], rgb.as_slice());
+    }
+}
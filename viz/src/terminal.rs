@@ -0,0 +1,156 @@
+use crate::{Frame, Sink};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, terminal};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+const CONTROLS: &str = "space: pause  n: step  +/-: speed  q: quit";
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+// Interactive sink, plays the frames in the alternate screen until the viewer quits
+pub struct Terminal {
+    delay: Duration,
+    skip: usize, // Only every skip-th frame is drawn, to go faster than no delay at all
+    paused: bool,
+    frames: usize,
+    quit: bool,
+}
+
+impl Terminal {
+    pub fn new(delay: Duration) -> io::Result<Terminal> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            io::stdout(),
+            terminal::EnterAlternateScreen,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::Hide
+        )?;
+
+        Ok(Terminal {
+            delay,
+            skip: 1,
+            paused: false,
+            frames: 0,
+            quit: false,
+        })
+    }
+
+    fn faster(&mut self) {
+        if self.delay > Duration::from_millis(1) {
+            self.delay /= 2;
+        } else {
+            self.delay = Duration::ZERO;
+            self.skip *= 2;
+        }
+    }
+
+    fn slower(&mut self) {
+        if self.skip > 1 {
+            self.skip /= 2;
+        } else {
+            self.delay = (self.delay * 2).clamp(Duration::from_millis(1), MAX_DELAY);
+        }
+    }
+
+    fn status(&self) -> String {
+        if self.paused {
+            format!("paused | {}", CONTROLS)
+        } else if self.skip > 1 {
+            format!("every {} frames | {}", self.skip, CONTROLS)
+        } else {
+            format!("{} ms/frame | {}", self.delay.as_millis(), CONTROLS)
+        }
+    }
+
+    fn draw(&self, frame: &Frame) -> io::Result<()> {
+        let mut out = io::stdout().lock();
+        write!(out, "{}\r\n\x1b[0m{}\x1b[K", frame.ansi(), self.status())?;
+        out.flush()
+    }
+
+    // Handles keys until the next frame is due, false when the viewer quits
+    fn wait(&mut self, frame: &Frame) -> io::Result<bool> {
+        let deadline = Instant::now() + self.delay;
+
+        loop {
+            let now = Instant::now();
+            if !self.paused && now >= deadline {
+                return Ok(true);
+            }
+
+            let timeout = if self.paused {
+                Duration::from_millis(250)
+            } else {
+                deadline - now
+            };
+            if !event::poll(timeout)? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(false)
+                }
+                KeyCode::Char('n') | KeyCode::Right if self.paused => return Ok(true),
+                KeyCode::Char(' ') => self.paused = !self.paused,
+                KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => self.faster(),
+                KeyCode::Char('-') | KeyCode::Down => self.slower(),
+                _ => continue,
+            }
+
+            // Show the new state in the status line
+            self.draw(frame)?;
+        }
+    }
+
+    // Keeps the last frame on screen until a key is pressed, unless the viewer already quit
+    pub fn finish(self) -> io::Result<()> {
+        if self.quit {
+            return Ok(());
+        }
+
+        let mut out = io::stdout().lock();
+        write!(out, "\r\x1b[0mdone, press any key to exit\x1b[K")?;
+        out.flush()?;
+
+        loop {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+impl Sink for Terminal {
+    fn show(&mut self, frame: &Frame) -> bool {
+        if self.quit {
+            return false;
+        }
+
+        self.frames += 1;
+        if !self.paused && !self.frames.is_multiple_of(self.skip) {
+            return true;
+        }
+
+        let result = self.draw(frame).and_then(|()| self.wait(frame));
+        self.quit = !result.unwrap_or(false);
+        !self.quit
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // Nothing else can be done when restoring the terminal fails
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}